@@ -0,0 +1,117 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// The major and minor version of `milvus-proto` the code is written against. The `milvus::proto` types are generated by
+// the `milvus-sdk-rust` dependency, from a checkout whose tag must have the same major and minor version.
+const PROTO_MAJOR_MINOR: (u32, u32) = (2, 6);
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    println!("cargo:rerun-if-env-changed=MILVUS_PROTO_DIR");
+    println!("cargo:rerun-if-env-changed=MILVUS_PROTO_VERSION");
+
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap());
+    // The `milvus-sdk-rust` path dependency.
+    let sdk_dir = manifest_dir.join("../milvus-sdk-rust");
+
+    check_tonic_version(&manifest_dir, &sdk_dir);
+
+    // The `milvus-proto` submodule of the `milvus-sdk-rust` path dependency, unless overridden.
+    let proto_dir = env::var_os("MILVUS_PROTO_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| sdk_dir.join("milvus-proto"));
+
+    if proto_dir.exists() {
+        println!("cargo:rerun-if-changed={}", proto_dir.display());
+    }
+
+    // `MILVUS_PROTO_VERSION` states the version of a checkout without git metadata.
+    let version = env::var("MILVUS_PROTO_VERSION")
+        .ok()
+        .or_else(|| checkout_tag(&proto_dir))
+        .unwrap_or_else(|| {
+            panic!(
+                "cannot find the milvus-proto version at {}, set MILVUS_PROTO_DIR or MILVUS_PROTO_VERSION",
+                proto_dir.display()
+            )
+        });
+
+    match major_minor(&version) {
+        Some(major_minor) if major_minor == PROTO_MAJOR_MINOR => {}
+        _ => panic!(
+            "the milvus-proto checkout at {} is at {}, but this crate is written against v{}.{}",
+            proto_dir.display(),
+            version,
+            PROTO_MAJOR_MINOR.0,
+            PROTO_MAJOR_MINOR.1
+        ),
+    }
+
+    println!("cargo:rustc-env=PROTO_MILVUS_VERSION={}", version);
+}
+
+/// Fail unless this crate and `milvus-sdk-rust` require the same major and minor version of tonic: the gRPC client is
+/// generated by the dependency, and used with the channel and interceptor types of this crate.
+fn check_tonic_version(manifest_dir: &Path, sdk_dir: &Path) {
+    let sdk_manifest = sdk_dir.join("Cargo.toml");
+    println!("cargo:rerun-if-changed={}", sdk_manifest.display());
+
+    let ours = dependency_version(&manifest_dir.join("Cargo.toml"), "tonic");
+    let theirs = dependency_version(&sdk_manifest, "tonic");
+    match (&ours, &theirs) {
+        (Some(ours), Some(theirs)) if major_minor(ours) == major_minor(theirs) => {}
+        _ => panic!(
+            "this crate requires tonic {:?} but {} requires {:?}, they must have the same major and minor version",
+            ours,
+            sdk_manifest.display(),
+            theirs
+        ),
+    }
+}
+
+/// The version requirement of the dependency `name` in the manifest at `path`, e.g. `0.9` for
+/// `tonic = {version = "0.9", features = ["tls"]}`.
+fn dependency_version(path: &Path, name: &str) -> Option<String> {
+    let manifest = std::fs::read_to_string(path).ok()?;
+    let value = manifest.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == name).then_some(value)
+    })?;
+
+    let value = match value.find("version") {
+        Some(start) => value[start..].split_once('=')?.1,
+        None => value,
+    };
+    let version = value.trim_start().strip_prefix('"')?.split('"').next()?;
+    Some(
+        version
+            .trim_start_matches(|c: char| "^~=".contains(c))
+            .to_string(),
+    )
+}
+
+/// The latest tag reachable from the commit `dir` is checked out at, if it is a git checkout.
+fn checkout_tag(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["describe", "--tags", "--abbrev=0", "HEAD"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let tag = String::from_utf8(output.stdout).ok()?;
+    Some(tag.trim().to_string())
+}
+
+/// The major and minor version of a tag such as `v2.6.1`.
+fn major_minor(version: &str) -> Option<(u32, u32)> {
+    let mut parts = version.trim_start_matches('v').split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
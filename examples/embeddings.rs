@@ -0,0 +1,87 @@
+//! Search book introductions by the embedding of a text.
+//!
+//! Expects a Milvus server at `VDB_HOST`, port 19530. The embeddings are made up, a real application would compute them with
+//! the same model for the books and the query text.
+
+use std::collections::HashMap;
+
+use wasmedge_vdb::{
+    my_collection::Value,
+    schema::{CollectionSchema, FieldSchema, FieldType},
+    Client, Result,
+};
+
+const COLLECTION_NAME: &str = "books";
+const DIM: i64 = 4;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<()> {
+    let host = std::env::var("VDB_HOST").unwrap_or_else(|_| "localhost".to_string());
+    let client = Client::new(&host, 19530, None, None, None).await?;
+
+    let schema = CollectionSchema::new(
+        COLLECTION_NAME,
+        vec![
+            FieldSchema::new("book_id", FieldType::Int64(true, false), None),
+            FieldSchema::new("book_name", FieldType::VarChar(64, false, false), None),
+            FieldSchema::new("book_intro", FieldType::FloatVector(DIM), None),
+        ],
+        Some("books and the embeddings of their introduction"),
+    )?;
+    if client.has_collection(COLLECTION_NAME).await? {
+        client.drop_collection(COLLECTION_NAME).await?;
+    }
+    client
+        .create_collection(COLLECTION_NAME, schema, None, None)
+        .await?;
+
+    let params = HashMap::from([
+        ("index_type".to_string(), "IVF_FLAT".to_string()),
+        ("metric_type".to_string(), "L2".to_string()),
+        ("params".to_string(), r#"{"nlist":16}"#.to_string()),
+    ]);
+    client
+        .create_index(
+            COLLECTION_NAME,
+            "book_intro",
+            Some(params),
+            "book_intro_index",
+        )
+        .await?;
+
+    let books = client.collection(COLLECTION_NAME).await?;
+    let rows = [
+        (1, "Dune", [0.9, 0.1, 0.1, 0.3]),
+        (2, "Hyperion", [0.8, 0.2, 0.1, 0.4]),
+        (3, "Emma", [0.1, 0.9, 0.7, 0.1]),
+        (4, "Persuasion", [0.2, 0.8, 0.8, 0.2]),
+    ]
+    .into_iter()
+    .map(|(id, name, intro)| {
+        HashMap::from([
+            ("book_id".to_string(), Value::Long(id)),
+            ("book_name".to_string(), Value::String(name.to_string())),
+            ("book_intro".to_string(), Value::FloatVector(intro.to_vec())),
+        ])
+    })
+    .collect();
+    books.insert_rows("", rows).await?;
+    client.flush(vec![COLLECTION_NAME]).await?;
+    client.load_collection(COLLECTION_NAME, None).await?;
+
+    // the embeddings of "desert planet" and "country manners"
+    let queries = vec![vec![0.85, 0.15, 0.1, 0.35], vec![0.15, 0.85, 0.75, 0.15]];
+    let groups = books
+        .search_text_vectors(queries, "book_intro", 2, vec!["book_name"])
+        .await?;
+    for (i, query_hits) in groups.iter().enumerate() {
+        println!("query {}:", i);
+        for hit in &query_hits.hits {
+            if let Some(Value::String(name)) = hit.fields.get("book_name") {
+                println!("  {} (distance {})", name, hit.score);
+            }
+        }
+    }
+
+    client.drop_collection(COLLECTION_NAME).await
+}
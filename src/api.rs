@@ -0,0 +1,148 @@
+//! The operations of `Client` as a trait, so the code using them can be tested without a server, see `mock::MockClient`.
+
+use crate::{
+    my_client::Client,
+    my_collection::{
+        CollectionMetadata, FieldData, FlushResult, MutationResult, QueryResult, SearchResult,
+    },
+    my_error::Result,
+    options::{QueryOptions, SearchOptions},
+    schema::CollectionSchema,
+};
+use async_trait::async_trait;
+use milvus::proto::common::ConsistencyLevel;
+
+/// The common operations on the collections and their entities.
+///
+/// Each method behaves as the method of `Client` with the same name.
+#[async_trait]
+pub trait MilvusApi: Send + Sync {
+    async fn has_collection(&self, name: &str) -> Result<bool>;
+
+    async fn create_collection(
+        &self,
+        collection_name: &str,
+        schema: CollectionSchema,
+        shards_num: Option<i32>,
+        level: Option<ConsistencyLevel>,
+    ) -> Result<()>;
+
+    async fn drop_collection(&self, name: &str) -> Result<()>;
+
+    async fn describe_collection(&self, name: &str) -> Result<CollectionMetadata>;
+
+    async fn load_collection(&self, name: &str, replica_num: Option<i32>) -> Result<()>;
+
+    async fn release_collection(&self, name: &str) -> Result<()>;
+
+    async fn insert(
+        &self,
+        collection_name: &str,
+        partition_name: &str,
+        fields_data: Vec<FieldData>,
+    ) -> Result<MutationResult>;
+
+    async fn delete(
+        &self,
+        collection_name: &str,
+        partition_name: Option<&str>,
+        expr: &str,
+    ) -> Result<MutationResult>;
+
+    async fn search_with(
+        &self,
+        collection_name: &str,
+        vectors: Vec<Vec<f32>>,
+        field_name: &str,
+        options: SearchOptions,
+    ) -> Result<SearchResult>;
+
+    async fn query_with(
+        &self,
+        collection_name: &str,
+        expr: &str,
+        options: QueryOptions,
+    ) -> Result<QueryResult>;
+
+    async fn count(&self, collection_name: &str, expr: Option<&str>) -> Result<i64>;
+
+    async fn flush(&self, collection_names: Vec<&str>) -> Result<FlushResult>;
+}
+
+#[async_trait]
+impl MilvusApi for Client {
+    async fn has_collection(&self, name: &str) -> Result<bool> {
+        Client::has_collection(self, name).await
+    }
+
+    async fn create_collection(
+        &self,
+        collection_name: &str,
+        schema: CollectionSchema,
+        shards_num: Option<i32>,
+        level: Option<ConsistencyLevel>,
+    ) -> Result<()> {
+        Client::create_collection(self, collection_name, schema, shards_num, level).await
+    }
+
+    async fn drop_collection(&self, name: &str) -> Result<()> {
+        Client::drop_collection(self, name).await
+    }
+
+    async fn describe_collection(&self, name: &str) -> Result<CollectionMetadata> {
+        Client::describe_collection(self, name).await
+    }
+
+    async fn load_collection(&self, name: &str, replica_num: Option<i32>) -> Result<()> {
+        Client::load_collection(self, name, replica_num).await
+    }
+
+    async fn release_collection(&self, name: &str) -> Result<()> {
+        Client::release_collection(self, name).await
+    }
+
+    async fn insert(
+        &self,
+        collection_name: &str,
+        partition_name: &str,
+        fields_data: Vec<FieldData>,
+    ) -> Result<MutationResult> {
+        Client::insert(self, collection_name, partition_name, fields_data).await
+    }
+
+    async fn delete(
+        &self,
+        collection_name: &str,
+        partition_name: Option<&str>,
+        expr: &str,
+    ) -> Result<MutationResult> {
+        Client::delete(self, collection_name, partition_name, expr).await
+    }
+
+    async fn search_with(
+        &self,
+        collection_name: &str,
+        vectors: Vec<Vec<f32>>,
+        field_name: &str,
+        options: SearchOptions,
+    ) -> Result<SearchResult> {
+        Client::search_with(self, collection_name, vectors, field_name, options).await
+    }
+
+    async fn query_with(
+        &self,
+        collection_name: &str,
+        expr: &str,
+        options: QueryOptions,
+    ) -> Result<QueryResult> {
+        Client::query_with(self, collection_name, expr, options).await
+    }
+
+    async fn count(&self, collection_name: &str, expr: Option<&str>) -> Result<i64> {
+        Client::count(self, collection_name, expr).await
+    }
+
+    async fn flush(&self, collection_names: Vec<&str>) -> Result<FlushResult> {
+        Client::flush(self, collection_names).await
+    }
+}
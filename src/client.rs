@@ -1,3 +1,6 @@
+//! The deprecated client wrapping the high level client of the Milvus SDK, see `crate::Client` instead.
+#![allow(deprecated)]
+
 use crate::{
     collection::{Collection, CollectionSchema},
     error::VDBResult,
@@ -1,3 +1,6 @@
+//! The deprecated collection wrapper of `client`, see `my_collection` and `schema` instead.
+#![allow(deprecated)]
+
 use crate::error::VDBResult;
 use std::fmt;
 
@@ -139,6 +142,8 @@ impl From<milvus::schema::FieldSchema> for FieldSchema {
             },
             milvus::proto::schema::DataType::BinaryVector => FieldType::BinaryVector(field.dim),
             milvus::proto::schema::DataType::FloatVector => FieldType::FloatVector(field.dim),
+            // the newer data types are only supported by `schema::FieldType`
+            _ => FieldType::None,
         };
 
         Self {
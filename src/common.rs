@@ -1,4 +1,6 @@
 use num_traits::{FromPrimitive, ToPrimitive};
+use std::collections::HashMap;
+use std::fmt;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, FromPrimitive, ToPrimitive)]
 #[repr(i32)]
@@ -21,3 +23,140 @@ impl From<milvus::proto::common::ConsistencyLevel> for ConsistencyLevel {
         ConsistencyLevel::from_i32(level as i32).unwrap()
     }
 }
+
+/// The build state of an index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, FromPrimitive, ToPrimitive)]
+#[repr(i32)]
+pub enum IndexBuildState {
+    IndexStateNone = 0,
+    Unissued = 1,
+    InProgress = 2,
+    Finished = 3,
+    /// The reason is reported along with the state
+    Failed = 4,
+    Retry = 5,
+}
+impl IndexBuildState {
+    pub fn is_finished(&self) -> bool {
+        *self == IndexBuildState::Finished
+    }
+
+    pub fn is_failed(&self) -> bool {
+        *self == IndexBuildState::Failed
+    }
+}
+impl From<i32> for IndexBuildState {
+    /// States unknown to this client map to `IndexStateNone`.
+    fn from(state: i32) -> Self {
+        IndexBuildState::from_i32(state).unwrap_or(IndexBuildState::IndexStateNone)
+    }
+}
+
+/// The load state of a collection or a partition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, FromPrimitive, ToPrimitive)]
+#[repr(i32)]
+pub enum LoadState {
+    NotExist = 0,
+    NotLoad = 1,
+    Loading = 2,
+    /// Ready to be searched and queried
+    Loaded = 3,
+}
+impl LoadState {
+    pub fn is_loaded(&self) -> bool {
+        *self == LoadState::Loaded
+    }
+}
+impl From<i32> for LoadState {
+    /// States unknown to this client map to `NotExist`.
+    fn from(state: i32) -> Self {
+        LoadState::from_i32(state).unwrap_or(LoadState::NotExist)
+    }
+}
+
+/// A limit the server puts on requests when a quota is exceeded, e.g. denying writes once the disk quota is reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, FromPrimitive, ToPrimitive)]
+#[repr(i32)]
+pub enum QuotaState {
+    Unknown = 0,
+    ReadLimited = 2,
+    WriteLimited = 3,
+    DenyToRead = 4,
+    DenyToWrite = 5,
+}
+impl From<i32> for QuotaState {
+    /// States unknown to this client map to `Unknown`.
+    fn from(state: i32) -> Self {
+        QuotaState::from_i32(state).unwrap_or(QuotaState::Unknown)
+    }
+}
+
+/// The metric measuring the distance, or the similarity, between two vectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MetricType {
+    /// Euclidean distance, for float vectors
+    L2,
+    /// Inner product, for float vectors
+    IP,
+    /// Cosine similarity, for float vectors
+    Cosine,
+    /// Hamming distance, for binary vectors
+    Hamming,
+    /// Jaccard distance, for binary vectors
+    Jaccard,
+}
+impl MetricType {
+    /// The name of the metric as known by the server.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricType::L2 => "L2",
+            MetricType::IP => "IP",
+            MetricType::Cosine => "COSINE",
+            MetricType::Hamming => "HAMMING",
+            MetricType::Jaccard => "JACCARD",
+        }
+    }
+
+    /// Whether a higher score means closer vectors, i.e. the metric is a similarity rather than a distance.
+    pub fn is_similarity(&self) -> bool {
+        matches!(self, MetricType::IP | MetricType::Cosine)
+    }
+
+    /// Whether the metric compares binary vectors, the only metrics a binary vector field can be searched with.
+    pub fn is_binary(&self) -> bool {
+        matches!(self, MetricType::Hamming | MetricType::Jaccard)
+    }
+}
+impl fmt::Display for MetricType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A resource group, i.e. a set of query nodes isolated from the other groups.
+#[derive(Debug, Clone)]
+pub struct ResourceGroupInfo {
+    pub name: String,
+    /// The number of query nodes allocated to the group
+    pub capacity: i32,
+    /// The number of allocated query nodes which are available
+    pub num_available_node: i32,
+    /// The number of replicas loaded in the group, by collection name
+    pub num_loaded_replica: HashMap<String, i32>,
+    /// The number of nodes lent to other groups, by collection name
+    pub num_outgoing_node: HashMap<String, i32>,
+    /// The number of nodes borrowed from other groups, by collection name
+    pub num_incoming_node: HashMap<String, i32>,
+}
+impl From<milvus::proto::milvus::ResourceGroup> for ResourceGroupInfo {
+    fn from(group: milvus::proto::milvus::ResourceGroup) -> Self {
+        ResourceGroupInfo {
+            name: group.name,
+            capacity: group.capacity,
+            num_available_node: group.num_available_node,
+            num_loaded_replica: group.num_loaded_replica,
+            num_outgoing_node: group.num_outgoing_node,
+            num_incoming_node: group.num_incoming_node,
+        }
+    }
+}
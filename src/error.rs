@@ -1,3 +1,6 @@
+//! The deprecated errors of `client`, see `crate::Error` instead.
+#![allow(deprecated)]
+
 /// The WasmEdge result type.
 use thiserror::Error;
 
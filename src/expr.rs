@@ -0,0 +1,322 @@
+use crate::my_error::{Error, Result};
+use crate::utils::quote_str;
+use std::fmt;
+
+/// A boolean filter expression of `query`, `delete` and `search`, rendered with `to_string()`.
+///
+/// Literals are formatted by the builder, strings being quoted and escaped, so the values need no manual escaping. Field
+/// names must be identifiers and floats finite, or the builder returns `Error::InvalidParameter`:
+///
+/// ```
+/// use wasmedge_vdb::expr::Expr;
+///
+/// # fn main() -> wasmedge_vdb::Result<()> {
+/// let expr = Expr::field("age")?
+///     .gt(18)?
+///     .and(Expr::field("city")?.in_list(vec!["NYC", "SF"])?);
+/// assert_eq!(expr.to_string(), r#"age > 18 and city in ["NYC", "SF"]"#);
+///
+/// assert!(Expr::field("a == 1 or b").is_err());
+/// assert!(Expr::field("score")?.gt(f64::NAN).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// `field op value`
+    Compare(String, CompareOp, Literal),
+    /// `field in [values]`, or `field not in [values]` if negated
+    In(String, Vec<Literal>, bool),
+    /// `field like "pattern"`, `%` matching any string
+    Like(String, String),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+}
+impl Expr {
+    /// Start an expression on the field `name`, which must be an identifier: an ASCII letter or `_`, then ASCII letters,
+    /// digits or `_`.
+    pub fn field(name: &str) -> Result<FieldRef> {
+        let mut chars = name.chars();
+        let is_identifier = matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_identifier {
+            return Err(Error::InvalidParameter(
+                "field".to_string(),
+                name.to_string(),
+            ));
+        }
+
+        Ok(FieldRef {
+            name: name.to_string(),
+        })
+    }
+
+    pub fn and(self, other: Expr) -> Expr {
+        Expr::And(Box::new(self), Box::new(other))
+    }
+
+    pub fn or(self, other: Expr) -> Expr {
+        Expr::Or(Box::new(self), Box::new(other))
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Expr {
+        Expr::Not(Box::new(self))
+    }
+
+    /// Write `operand` of the logical operator `parent`, parenthesized unless it binds at least as tightly.
+    ///
+    /// `not` binds tighter than the comparisons, so its operand is always parenthesized.
+    fn fmt_operand(f: &mut fmt::Formatter<'_>, parent: &Expr, operand: &Expr) -> fmt::Result {
+        let needs_parens = matches!(
+            (parent, operand),
+            (Expr::And(..), Expr::Or(..)) | (Expr::Or(..), Expr::And(..)) | (Expr::Not(..), _)
+        );
+        if needs_parens {
+            write!(f, "({})", operand)
+        } else {
+            write!(f, "{}", operand)
+        }
+    }
+}
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Compare(field, op, value) => write!(f, "{} {} {}", field, op, value),
+            Expr::In(field, values, negated) => {
+                let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+                let op = if *negated { "not in" } else { "in" };
+                write!(f, "{} {} [{}]", field, op, values.join(", "))
+            }
+            Expr::Like(field, pattern) => write!(f, "{} like {}", field, quote_str(pattern)),
+            Expr::And(left, right) | Expr::Or(left, right) => {
+                let op = if matches!(self, Expr::And(..)) {
+                    "and"
+                } else {
+                    "or"
+                };
+                Expr::fmt_operand(f, self, left)?;
+                write!(f, " {} ", op)?;
+                Expr::fmt_operand(f, self, right)
+            }
+            Expr::Not(operand) => {
+                f.write_str("not ")?;
+                Expr::fmt_operand(f, self, operand)
+            }
+        }
+    }
+}
+
+/// A field of an expression being built, see `Expr::field`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldRef {
+    name: String,
+}
+impl FieldRef {
+    fn compare(self, op: CompareOp, value: impl Into<Literal>) -> Result<Expr> {
+        let value = value.into();
+        value.check()?;
+        Ok(Expr::Compare(self.name, op, value))
+    }
+
+    fn in_values<T: Into<Literal>>(self, values: Vec<T>, negated: bool) -> Result<Expr> {
+        let values: Vec<Literal> = values.into_iter().map(Into::into).collect();
+        for value in &values {
+            value.check()?;
+        }
+        Ok(Expr::In(self.name, values, negated))
+    }
+
+    pub fn eq(self, value: impl Into<Literal>) -> Result<Expr> {
+        self.compare(CompareOp::Eq, value)
+    }
+
+    pub fn ne(self, value: impl Into<Literal>) -> Result<Expr> {
+        self.compare(CompareOp::Ne, value)
+    }
+
+    pub fn gt(self, value: impl Into<Literal>) -> Result<Expr> {
+        self.compare(CompareOp::Gt, value)
+    }
+
+    pub fn ge(self, value: impl Into<Literal>) -> Result<Expr> {
+        self.compare(CompareOp::Ge, value)
+    }
+
+    pub fn lt(self, value: impl Into<Literal>) -> Result<Expr> {
+        self.compare(CompareOp::Lt, value)
+    }
+
+    pub fn le(self, value: impl Into<Literal>) -> Result<Expr> {
+        self.compare(CompareOp::Le, value)
+    }
+
+    /// Match the values in `values`.
+    pub fn in_list<T: Into<Literal>>(self, values: Vec<T>) -> Result<Expr> {
+        self.in_values(values, false)
+    }
+
+    /// Match the values not in `values`.
+    pub fn not_in<T: Into<Literal>>(self, values: Vec<T>) -> Result<Expr> {
+        self.in_values(values, true)
+    }
+
+    /// Match the VarChar values like `pattern`, e.g. `"prefix%"`.
+    pub fn like(self, pattern: &str) -> Expr {
+        Expr::Like(self.name, pattern.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+impl fmt::Display for CompareOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CompareOp::Eq => "==",
+            CompareOp::Ne => "!=",
+            CompareOp::Gt => ">",
+            CompareOp::Ge => ">=",
+            CompareOp::Lt => "<",
+            CompareOp::Le => "<=",
+        })
+    }
+}
+
+/// A literal value of an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Bool(bool),
+    Int(i64),
+    /// Rejected by the builder if NaN or infinite, which have no literal
+    Float(f64),
+    /// Quoted and escaped when rendered
+    String(String),
+}
+impl Literal {
+    fn check(&self) -> Result<()> {
+        match self {
+            Literal::Float(v) if !v.is_finite() => {
+                Err(Error::InvalidParameter("value".to_string(), v.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::Bool(v) => write!(f, "{}", v),
+            Literal::Int(v) => write!(f, "{}", v),
+            // `Debug` keeps the decimal point of whole numbers, so the server parses a float
+            Literal::Float(v) => write!(f, "{:?}", v),
+            Literal::String(v) => f.write_str(&quote_str(v)),
+        }
+    }
+}
+impl From<bool> for Literal {
+    fn from(v: bool) -> Self {
+        Literal::Bool(v)
+    }
+}
+impl From<i32> for Literal {
+    fn from(v: i32) -> Self {
+        Literal::Int(v as i64)
+    }
+}
+impl From<i64> for Literal {
+    fn from(v: i64) -> Self {
+        Literal::Int(v)
+    }
+}
+impl From<f32> for Literal {
+    // the nearest f64 of the shortest decimal of `v`, e.g. 0.1 instead of 0.10000000149011612
+    fn from(v: f32) -> Self {
+        Literal::Float(v.to_string().parse().unwrap())
+    }
+}
+impl From<f64> for Literal {
+    fn from(v: f64) -> Self {
+        Literal::Float(v)
+    }
+}
+impl From<&str> for Literal {
+    fn from(v: &str) -> Self {
+        Literal::String(v.to_string())
+    }
+}
+impl From<String> for Literal {
+    fn from(v: String) -> Self {
+        Literal::String(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expr_to_string() -> Result<()> {
+        let expr = Expr::field("age")?
+            .gt(18)?
+            .and(Expr::field("city")?.in_list(vec!["NYC", "SF"])?);
+        assert_eq!(expr.to_string(), r#"age > 18 and city in ["NYC", "SF"]"#);
+
+        // precedence is kept with parentheses
+        let expr = Expr::field("a")?
+            .eq(1)?
+            .or(Expr::field("b")?.le(2.5)?)
+            .and(Expr::field("c")?.ne(true)?.not());
+        assert_eq!(expr.to_string(), "(a == 1 or b <= 2.5) and not (c != true)");
+        let expr = Expr::field("a")?.eq(1)?.and(Expr::field("b")?.eq(2)?).not();
+        assert_eq!(expr.to_string(), "not (a == 1 and b == 2)");
+
+        // floats stay floats, f32 without the digits of widening
+        assert_eq!(Expr::field("score")?.ge(1.0)?.to_string(), "score >= 1.0");
+        assert_eq!(
+            Expr::field("score")?.ge(0.1f32)?.to_string(),
+            "score >= 0.1"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_expr_escapes_strings() -> Result<()> {
+        let expr = Expr::field("title")?.eq(r#"say "hi" \ bye"#)?;
+        assert_eq!(expr.to_string(), r#"title == "say \"hi\" \\ bye""#);
+
+        let expr = Expr::field("title")?.like(r#"The "%"#);
+        assert_eq!(expr.to_string(), r#"title like "The \"%""#);
+
+        let expr = Expr::field("id")?.not_in(vec![1, 2])?;
+        assert_eq!(expr.to_string(), "id not in [1, 2]");
+        Ok(())
+    }
+
+    #[test]
+    fn test_expr_rejects_invalid_input() {
+        for name in ["a == 1 or b", "", "1a", "a.b", "a\"b"] {
+            assert!(
+                matches!(Expr::field(name), Err(Error::InvalidParameter(ref p, _)) if p == "field"),
+                "{:?}",
+                name
+            );
+        }
+        assert!(Expr::field("_id1").is_ok());
+
+        let score = || Expr::field("score").unwrap();
+        assert!(matches!(
+            score().gt(f64::NAN),
+            Err(Error::InvalidParameter(ref p, _)) if p == "value"
+        ));
+        assert!(score().lt(f32::INFINITY).is_err());
+        assert!(score().in_list(vec![1.0, f64::NEG_INFINITY]).is_err());
+    }
+}
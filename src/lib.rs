@@ -1,13 +1,45 @@
+//! A client of the Milvus vector database.
+//!
+//! `Client` talks to the server through the gRPC API directly, with the data types of `my_collection` and the errors of
+//! `my_error`. The `client`, `collection` and `error` modules are an older wrapper around the high level client of the
+//! Milvus SDK, kept for compatibility and deprecated.
+//!
+//! The `tracing` feature runs every RPC of `Client` in a `milvus_rpc` span of the `tracing` crate, with the `method`, `db`
+//! and `collection` fields, and ends it with an event recording `latency_ms`, the gRPC `code` and the `outcome`. A failure
+//! reported in the status of a reply is recorded on the span as `status_code` and `reason`.
+//!
+//! The `test-util` feature adds `mock::MockClient`, an implementation of `MilvusApi` returning canned responses.
+
 #[macro_use]
 extern crate num_derive;
 
+pub mod api;
+#[deprecated(note = "use `wasmedge_vdb::Client` instead")]
 pub mod client;
+#[deprecated(note = "use `my_collection` and `schema` instead")]
 pub mod collection;
 pub mod common;
+#[deprecated(note = "use `wasmedge_vdb::Error` instead")]
 pub mod error;
+pub mod expr;
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock;
 pub mod my_client;
 pub mod my_collection;
 pub mod my_error;
 pub mod options;
 pub mod schema;
 pub mod utils;
+
+/// The tag of `milvus-proto` the `milvus::proto` types used by this crate are generated from, by the `milvus-sdk-rust`
+/// dependency.
+///
+/// It is read by `build.rs` from the dependency's `milvus-proto` checkout (or `MILVUS_PROTO_DIR`, or stated with
+/// `MILVUS_PROTO_VERSION`), failing the build when it is unknown or of another major and minor version than the one the
+/// crate is written against. The `DataType`s this crate has no `FieldType` for are rejected with
+/// `SchemaError::UnsupportedDataType`. Compare it with `Client::get_version` through `utils::is_compatible_server_version`.
+pub const PROTO_MILVUS_VERSION: &str = env!("PROTO_MILVUS_VERSION");
+
+pub use api::MilvusApi;
+pub use my_client::{Client, ClientBuilder, ClientPool};
+pub use my_error::{Error, Result};
@@ -0,0 +1,334 @@
+//! An in-memory `MilvusApi`, to test the code using the client without a server.
+
+use crate::{
+    api::MilvusApi,
+    my_collection::{
+        CollectionMetadata, FieldData, FlushResult, MutationResult, QueryResult, SearchResult,
+    },
+    my_error::{Error, Result},
+    options::{QueryOptions, SearchOptions},
+    schema::CollectionSchema,
+};
+use async_trait::async_trait;
+use milvus::proto::common::ConsistencyLevel;
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// A call made to a `MockClient`.
+#[derive(Debug, Clone)]
+pub struct MockCall {
+    /// The name of the `MilvusApi` method, e.g. `search_with`
+    pub method: &'static str,
+    /// The collection of the call, empty for `flush`
+    pub collection_name: String,
+    /// The other arguments of the call
+    pub args: MockArgs,
+}
+
+/// The arguments of a `MockCall` besides the collection name, one variant per `MilvusApi` method taking some.
+#[derive(Debug, Clone)]
+pub enum MockArgs {
+    /// `has_collection`, `drop_collection`, `describe_collection` and `release_collection`
+    None,
+    CreateCollection {
+        schema: CollectionSchema,
+        shards_num: Option<i32>,
+        level: Option<ConsistencyLevel>,
+    },
+    LoadCollection {
+        replica_num: Option<i32>,
+    },
+    Insert {
+        partition_name: String,
+        fields_data: Vec<FieldData>,
+    },
+    Delete {
+        partition_name: Option<String>,
+        expr: String,
+    },
+    Search {
+        vectors: Vec<Vec<f32>>,
+        field_name: String,
+        options: SearchOptions,
+    },
+    Query {
+        expr: String,
+        options: QueryOptions,
+    },
+    Count {
+        expr: Option<String>,
+    },
+    Flush {
+        collection_names: Vec<String>,
+    },
+}
+
+/// A `MilvusApi` recording its calls and returning the responses queued with `respond`.
+///
+/// ```
+/// use wasmedge_vdb::{mock::MockClient, MilvusApi};
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> wasmedge_vdb::Result<()> {
+/// let mock = MockClient::new();
+/// mock.respond("has_collection", Ok(true));
+/// assert!(mock.has_collection("books").await?);
+/// assert_eq!(mock.calls()[0].method, "has_collection");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct MockClient {
+    calls: Mutex<Vec<MockCall>>,
+    responses: Mutex<HashMap<&'static str, VecDeque<Box<dyn Any + Send>>>>,
+}
+impl MockClient {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue `response` for the next call to `method`, after the responses already queued for it.
+    ///
+    /// `T` must be the type returned by `method`, e.g. `bool` for `has_collection`.
+    pub fn respond<T: Send + 'static>(&self, method: &'static str, response: Result<T>) -> &Self {
+        self.responses
+            .lock()
+            .unwrap()
+            .entry(method)
+            .or_default()
+            .push_back(Box::new(response));
+        self
+    }
+
+    /// The calls made so far, in order.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    /// Record a call and pop its queued response, an `Error::Unexpected` if there is none.
+    fn call<T: 'static>(
+        &self,
+        method: &'static str,
+        collection_name: &str,
+        args: MockArgs,
+    ) -> Result<T> {
+        self.calls.lock().unwrap().push(MockCall {
+            method,
+            collection_name: collection_name.to_string(),
+            args,
+        });
+
+        let response = self
+            .responses
+            .lock()
+            .unwrap()
+            .get_mut(method)
+            .and_then(VecDeque::pop_front)
+            .ok_or_else(|| Error::Unexpected(format!("no mock response queued for {}", method)))?;
+
+        match response.downcast::<Result<T>>() {
+            Ok(response) => *response,
+            Err(_) => Err(Error::Unexpected(format!(
+                "the mock response queued for {} has the wrong type",
+                method
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl MilvusApi for MockClient {
+    async fn has_collection(&self, name: &str) -> Result<bool> {
+        self.call("has_collection", name, MockArgs::None)
+    }
+
+    async fn create_collection(
+        &self,
+        collection_name: &str,
+        schema: CollectionSchema,
+        shards_num: Option<i32>,
+        level: Option<ConsistencyLevel>,
+    ) -> Result<()> {
+        let args = MockArgs::CreateCollection {
+            schema,
+            shards_num,
+            level,
+        };
+        self.call("create_collection", collection_name, args)
+    }
+
+    async fn drop_collection(&self, name: &str) -> Result<()> {
+        self.call("drop_collection", name, MockArgs::None)
+    }
+
+    async fn describe_collection(&self, name: &str) -> Result<CollectionMetadata> {
+        self.call("describe_collection", name, MockArgs::None)
+    }
+
+    async fn load_collection(&self, name: &str, replica_num: Option<i32>) -> Result<()> {
+        self.call(
+            "load_collection",
+            name,
+            MockArgs::LoadCollection { replica_num },
+        )
+    }
+
+    async fn release_collection(&self, name: &str) -> Result<()> {
+        self.call("release_collection", name, MockArgs::None)
+    }
+
+    async fn insert(
+        &self,
+        collection_name: &str,
+        partition_name: &str,
+        fields_data: Vec<FieldData>,
+    ) -> Result<MutationResult> {
+        let args = MockArgs::Insert {
+            partition_name: partition_name.to_string(),
+            fields_data,
+        };
+        self.call("insert", collection_name, args)
+    }
+
+    async fn delete(
+        &self,
+        collection_name: &str,
+        partition_name: Option<&str>,
+        expr: &str,
+    ) -> Result<MutationResult> {
+        let args = MockArgs::Delete {
+            partition_name: partition_name.map(str::to_string),
+            expr: expr.to_string(),
+        };
+        self.call("delete", collection_name, args)
+    }
+
+    async fn search_with(
+        &self,
+        collection_name: &str,
+        vectors: Vec<Vec<f32>>,
+        field_name: &str,
+        options: SearchOptions,
+    ) -> Result<SearchResult> {
+        let args = MockArgs::Search {
+            vectors,
+            field_name: field_name.to_string(),
+            options,
+        };
+        self.call("search_with", collection_name, args)
+    }
+
+    async fn query_with(
+        &self,
+        collection_name: &str,
+        expr: &str,
+        options: QueryOptions,
+    ) -> Result<QueryResult> {
+        let args = MockArgs::Query {
+            expr: expr.to_string(),
+            options,
+        };
+        self.call("query_with", collection_name, args)
+    }
+
+    async fn count(&self, collection_name: &str, expr: Option<&str>) -> Result<i64> {
+        let args = MockArgs::Count {
+            expr: expr.map(str::to_string),
+        };
+        self.call("count", collection_name, args)
+    }
+
+    async fn flush(&self, collection_names: Vec<&str>) -> Result<FlushResult> {
+        let args = MockArgs::Flush {
+            collection_names: collection_names.into_iter().map(str::to_string).collect(),
+        };
+        self.call("flush", "", args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Application code generic over the client.
+    async fn ensure_collection(api: &impl MilvusApi, name: &str) -> Result<bool> {
+        if api.has_collection(name).await? {
+            return Ok(false);
+        }
+        api.load_collection(name, None).await?;
+        Ok(true)
+    }
+
+    #[tokio::test]
+    async fn test_mock_client() {
+        let mock = MockClient::new();
+        mock.respond("has_collection", Ok(false))
+            .respond("has_collection", Ok(true))
+            .respond("load_collection", Ok(()));
+
+        assert!(ensure_collection(&mock, "books").await.unwrap());
+        assert!(!ensure_collection(&mock, "books").await.unwrap());
+        let methods: Vec<_> = mock.calls().iter().map(|call| call.method).collect();
+        assert_eq!(
+            methods,
+            vec!["has_collection", "load_collection", "has_collection"]
+        );
+        assert_eq!(mock.calls()[0].collection_name, "books");
+        assert!(matches!(
+            mock.calls()[1].args,
+            MockArgs::LoadCollection { replica_num: None }
+        ));
+
+        // nothing queued
+        let result = mock.count("books", None).await;
+        assert!(matches!(result, Err(Error::Unexpected(_))));
+
+        // wrong type
+        mock.respond("count", Ok("10"));
+        let result = mock.count("books", None).await;
+        assert!(matches!(result, Err(Error::Unexpected(_))));
+
+        mock.respond::<i64>("count", Err(Error::Unexpected("down".to_string())));
+        let result = mock.count("books", None).await;
+        assert!(matches!(result, Err(Error::Unexpected(ref msg)) if msg == "down"));
+    }
+
+    #[tokio::test]
+    async fn test_mock_client_records_args() {
+        let mock = MockClient::new();
+        let _ = mock.delete("books", Some("2024"), "id in [1, 2]").await;
+        let _ = mock
+            .search_with(
+                "books",
+                vec![vec![0.1, 0.2]],
+                "embedding",
+                SearchOptions::new().top_k(3),
+            )
+            .await;
+        let _ = mock.flush(vec!["books", "authors"]).await;
+
+        let calls = mock.calls();
+        assert!(matches!(
+            calls[0].args,
+            MockArgs::Delete { ref partition_name, ref expr }
+                if partition_name.as_deref() == Some("2024") && expr == "id in [1, 2]"
+        ));
+        match &calls[1].args {
+            MockArgs::Search {
+                vectors,
+                field_name,
+                options,
+            } => {
+                assert_eq!(vectors, &vec![vec![0.1, 0.2]]);
+                assert_eq!(field_name, "embedding");
+                assert_eq!(options.top_k, 3);
+            }
+            args => panic!("unexpected {:?}", args),
+        }
+        assert!(matches!(
+            calls[2].args,
+            MockArgs::Flush { ref collection_names } if collection_names == &["books", "authors"]
+        ));
+    }
+}
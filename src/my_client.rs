@@ -1,7 +1,12 @@
 use base64::engine::general_purpose;
 use base64::Engine;
-use milvus::proto::common::{ConsistencyLevel, KeyValuePair, MsgType};
+use futures_util::{Stream, StreamExt};
+use milvus::proto::common::{
+    ConsistencyLevel, DslType, ErrorCode, KeyValuePair, MsgBase, MsgType, PlaceholderGroup,
+    PlaceholderType, PlaceholderValue,
+};
 use milvus::proto::milvus::milvus_service_client::MilvusServiceClient;
+use milvus::proto::schema::DataType;
 use num_traits::FromPrimitive;
 use prost::{bytes::BytesMut, Message};
 use tonic::codegen::InterceptedService;
@@ -11,26 +16,77 @@ use tonic::Request;
 
 use crate::my_collection::ComponentState;
 use crate::{
+    common::{LoadState, MetricType, ResourceGroupInfo},
     my_collection::{
-        Address, CollectionInfo, CollectionMetadata, CompactionMergeInfo, CompactionPlan,
-        CompactionState, CompactionStateResult, FieldData, FlushResult, GrantEntity, Health,
-        ImportState, ImportStateResult, IndexInfo, IndexProgress, IndexState, Metrics,
+        Address, CollectionInfo, CollectionMetadata, ColumnData, CompactionMergeInfo,
+        CompactionPlan, CompactionState, CompactionStateResult, CreateCollectionOutcome, Field,
+        FieldData, FlushResult, GrantEntity, Health, IdField, ImportState, ImportStateResult,
+        ImportTask, IndexInfo, IndexProgress, IndexState, InsertProgress, LoadingProgress, Metrics,
         MutationResult, OperatePrivilegeType, OperateUserRoleType, PartitionInfo,
-        PersistentSegmentInfo, QueryResult, QuerySegmentInfo, ReplicaInfo, RoleEntity, RoleResult,
-        SearchResult, SegmentState, User, UserEntity,
+        PersistentSegmentInfo, QueryHits, QueryResult, QuerySegmentInfo, ReplicaInfo, RoleEntity,
+        RoleResult, ScalarField, ScalarFieldData, SearchResult, SegmentState, ServerInfo, User,
+        UserEntity, Value, VectorField, VectorFieldData,
     },
     my_error::{Error, Result},
-    schema::CollectionSchema,
-    utils::{new_msg, status_to_result},
+    options::{
+        AnnSearchRequest, ConnectOptions, ImportOptions, LoadOptions, QueryOptions, RerankStrategy,
+        SearchOptions,
+    },
+    schema::{
+        CollectionSchema, FieldSchema, FieldType, MaxLength, SchemaError, DYNAMIC_FIELD_NAME,
+        MAX_VARCHAR_LENGTH,
+    },
+    utils::{get_gts, new_msg, poll_until, quote_str, status_to_result_ctx, WAIT_LOAD_DURATION_MS},
 };
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
-#[derive(Debug)]
+/// Default limit of the size of a gRPC message sent or received by `Client`, in bytes.
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+/// Collection property: the time to live of the entities, in seconds.
+pub const COLLECTION_TTL_SECONDS: &str = "collection.ttl.seconds";
+
+/// Collection property: whether the loaded data is memory-mapped instead of fully held in memory.
+pub const MMAP_ENABLED: &str = "mmap.enabled";
+
+/// The number of primary keys queried, then deleted, by each step of `delete_and_report`.
+const DELETE_REPORT_BATCH_SIZE: i64 = 1000;
+
+/// The generated gRPC client of the Milvus service, as configured by `Client`.
+pub type RawClient = MilvusServiceClient<InterceptedService<Channel, AuthInterceptor>>;
+
+#[derive(Debug, Clone)]
 pub struct Client {
-    client: MilvusServiceClient<InterceptedService<Channel, AuthInterceptor>>,
+    client: RawClient,
+    /// The session identifier negotiated by `handshake`, shared with the interceptor
+    identifier: Arc<RwLock<Option<i64>>>,
+    /// The server info returned by `handshake`
+    server_info: Arc<RwLock<Option<ServerInfo>>>,
+    /// The `authorization` token, shared with the interceptor so it can be replaced after connecting
+    token: Arc<RwLock<Option<String>>>,
+    request_id: Arc<RequestIdTracker>,
+    metrics: Option<Arc<dyn MetricsRecorder>>,
+    /// The last `msg_id` of the requests, shared by the clones so the ids stay unique
+    msg_id: Arc<AtomicU64>,
+    /// The `source_id` of the requests, identifying this client in the server logs
+    source_id: i64,
+    /// The database of the requests, the server default database if empty
+    db_name: String,
+    /// The timestamp of the last write of this session, by database and collection name
+    session_ts: Arc<RwLock<HashMap<(String, String), u64>>>,
+    collection_ids: Arc<CollectionIdCache>,
+    describe_cache: Arc<DescribeCache>,
 }
 impl Client {
+    /// Connect to the server at `host:port`.
+    ///
+    /// Authentication is enabled when both `username` and `password` are given; an empty one counts as not given.
+    /// Giving only one of them returns `Error::InvalidParameter`, use `no_auth` to connect anonymously on purpose.
     pub async fn new(
         host: &str,
         port: u16,
@@ -38,42 +94,465 @@ impl Client {
         password: Option<String>,
         timeout: Option<std::time::Duration>,
     ) -> Result<Self> {
-        let url = format!("{}:{}", host, port.to_string());
-        let timeout = match timeout {
-            Some(timeout) => timeout,
-            None => std::time::Duration::from_secs(10),
+        let mut builder = Self::builder(host, port);
+        builder.username = username;
+        builder.password = password;
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        builder.build().await
+    }
+
+    /// A builder of a client of the server at `host:port`, to configure it without a list of positional options.
+    ///
+    /// ```ignore
+    /// let client = Client::builder("localhost", 19530)
+    ///     .credentials("root", "Milvus")
+    ///     .timeout(Duration::from_secs(30))
+    ///     .database("books")
+    ///     .build()
+    ///     .await?;
+    /// ```
+    pub fn builder(host: &str, port: u16) -> ClientBuilder {
+        ClientBuilder::new(host, port)
+    }
+
+    /// Connect to the server at `host:port` with the connection settings `options`, e.g. to enable keepalive.
+    ///
+    /// See `new` for the authentication.
+    pub async fn connect(
+        host: &str,
+        port: u16,
+        username: Option<String>,
+        password: Option<String>,
+        options: ConnectOptions,
+    ) -> Result<Self> {
+        let mut builder = Self::builder(host, port).connect_options(options);
+        builder.username = username;
+        builder.password = password;
+
+        builder.build().await
+    }
+
+    /// Connect to the server at `host:port` authenticating with an API key, e.g. the one of a Zilliz Cloud cluster.
+    ///
+    /// The key can be replaced later with `set_api_key`, e.g. when it rotates.
+    pub async fn connect_with_api_key(
+        host: &str,
+        port: u16,
+        api_key: &str,
+        options: ConnectOptions,
+    ) -> Result<Self> {
+        Self::builder(host, port)
+            .connect_options(options)
+            .api_key(api_key)
+            .build()
+            .await
+    }
+
+    fn with_channel(conn: Channel, auth_interceptor: AuthInterceptor) -> Self {
+        let identifier = auth_interceptor.identifier.clone();
+        let token = auth_interceptor.token.clone();
+        let client = MilvusServiceClient::with_interceptor(conn, auth_interceptor)
+            .max_decoding_message_size(DEFAULT_MAX_MESSAGE_SIZE)
+            .max_encoding_message_size(DEFAULT_MAX_MESSAGE_SIZE);
+
+        Self {
+            client,
+            identifier,
+            server_info: Arc::new(RwLock::new(None)),
+            token,
+            request_id: Arc::new(RequestIdTracker::default()),
+            metrics: None,
+            msg_id: Arc::new(AtomicU64::new(0)),
+            source_id: 0,
+            db_name: String::new(),
+            session_ts: Arc::new(RwLock::new(HashMap::new())),
+            collection_ids: Arc::new(CollectionIdCache::default()),
+            describe_cache: Arc::new(DescribeCache::new(Duration::ZERO)),
+        }
+    }
+
+    /// Send the requests to the database `db_name` instead of the server default one.
+    ///
+    /// The database applies to every request of the returned client, including the ones without a `db_name` argument.
+    /// Clients are cheap to clone, so `client.clone().with_database("other")` targets another database for a few calls.
+    pub fn with_database(mut self, db_name: &str) -> Self {
+        self.db_name = db_name.to_string();
+        self
+    }
+
+    /// Report every RPC of the returned client and its clones to `recorder`, e.g. to export request counts and latencies.
+    pub fn with_metrics(mut self, recorder: impl MetricsRecorder + 'static) -> Self {
+        self.metrics = Some(Arc::new(recorder));
+        self
+    }
+
+    /// Cache the descriptions of the collections for `ttl`, to spare the helpers resolving a schema a `describe_collection` each.
+    ///
+    /// Caching is disabled by default. The cache is shared by the clones of the returned client and invalidated by the
+    /// collection changes made through them, e.g. `drop_collection`; call `invalidate` after the changes made by other clients.
+    /// `describe_collection` itself always describes the collection, and refreshes the cache.
+    pub fn with_describe_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.describe_cache = Arc::new(DescribeCache::new(ttl));
+        self
+    }
+
+    /// Forget the cached id and description of the collection or alias `collection_name` of the client database.
+    ///
+    /// The descriptions of the same collection cached under its name or its aliases are forgotten too.
+    pub fn invalidate(&self, collection_name: &str) {
+        self.collection_ids
+            .invalidate(&self.db_name, collection_name);
+        self.describe_cache
+            .invalidate(&self.db_name, collection_name);
+    }
+
+    /// Describe several collections concurrently, e.g. to list a catalog in a single round-trip time.
+    ///
+    /// The results are in the order of `names`; a collection failing to be described, e.g. because it does not exist,
+    /// only fails its own result.
+    pub async fn describe_collections(&self, names: Vec<&str>) -> Vec<Result<CollectionMetadata>> {
+        futures_util::future::join_all(names.into_iter().map(|name| self.describe_collection(name)))
+            .await
+    }
+
+    /// Describe a collection, from the cache if enabled with `with_describe_cache_ttl` and still fresh.
+    async fn describe_cached(&self, name: &str) -> Result<CollectionMetadata> {
+        match self.describe_cache.get(&self.db_name, name) {
+            Some(metadata) => Ok(metadata),
+            None => self.describe_collection(name).await,
+        }
+    }
+
+    /// Identify the requests of the returned client with `source_id` in the server logs, `0` by default.
+    pub fn with_source_id(mut self, source_id: i64) -> Self {
+        self.source_id = source_id;
+        self
+    }
+
+    /// The base of a request of type `mtype`, with the next `msg_id` of this client and its `source_id`.
+    fn new_msg(&self, mtype: MsgType) -> MsgBase {
+        MsgBase {
+            msg_id: self.msg_id.fetch_add(1, Ordering::Relaxed) + 1,
+            source_id: self.source_id,
+            ..new_msg(mtype)
+        }
+    }
+
+    /// The database of the requests, empty for the server default database.
+    pub fn database(&self) -> &str {
+        &self.db_name
+    }
+
+    /// Replace the value of the `authorization` metadata sent with the requests, `None` to stop authenticating.
+    ///
+    /// The token applies to the following requests of this client and its clones. Use `set_api_key` for an API key.
+    pub fn set_token(&self, token: Option<&str>) -> Result<()> {
+        if let Some(token) = token {
+            token
+                .parse::<tonic::metadata::AsciiMetadataValue>()
+                .map_err(|_| {
+                    Error::InvalidParameter(
+                        "token".to_owned(),
+                        "not a valid metadata value".to_owned(),
+                    )
+                })?;
+        }
+
+        *self.token.write().unwrap() = token.map(str::to_string);
+        Ok(())
+    }
+
+    /// Authenticate the following requests of this client and its clones with the API key `api_key`, e.g. a rotated one.
+    pub fn set_api_key(&self, api_key: &str) -> Result<()> {
+        if api_key.is_empty() {
+            return Err(Error::InvalidParameter(
+                "api_key".to_owned(),
+                "expected a non-empty key".to_owned(),
+            ));
+        }
+
+        self.set_token(Some(&general_purpose::STANDARD.encode(api_key)))
+    }
+
+    /// The underlying gRPC client, to call the RPCs not covered by this crate.
+    ///
+    /// The returned client shares the channel and the authentication of this one, including the session `identifier`.
+    /// It bypasses everything else: the caller sets `db_name` and `base` of the requests and checks the returned status itself.
+    pub fn raw(&self) -> RawClient {
+        self.client.clone()
+    }
+
+    /// The database for an optional `db_name` argument, falling back to the client database.
+    fn resolve_db_name(&self, db_name: Option<&str>) -> String {
+        db_name.map_or_else(|| self.db_name.clone(), str::to_string)
+    }
+
+    /// Connect to the server at `host:port` without authentication.
+    pub async fn no_auth(
+        host: &str,
+        port: u16,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Self> {
+        Self::new(host, port, None, None, timeout).await
+    }
+
+    /// Limit the size of a response message, `DEFAULT_MAX_MESSAGE_SIZE` by default.
+    ///
+    /// Raise it for searches or queries returning many output fields.
+    pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+        self.client = self.client.max_decoding_message_size(limit);
+        self
+    }
+
+    /// Limit the size of a request message, `DEFAULT_MAX_MESSAGE_SIZE` by default.
+    ///
+    /// Raise it for large inserts, or split them with `insert_batched`.
+    pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+        self.client = self.client.max_encoding_message_size(limit);
+        self
+    }
+
+    /// Enable or disable capturing the `request-id`/`trace-id` metadata returned by the server.
+    ///
+    /// Capturing is disabled by default. The setting is shared by all the clones of this client.
+    pub fn capture_request_id(&self, enabled: bool) {
+        self.request_id.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// The request id returned by the server for the last completed RPC, either successful or failed, `None` if its reply had
+    /// none.
+    ///
+    /// Always `None` unless capturing was enabled with `capture_request_id`.
+    pub fn last_request_id(&self) -> Option<String> {
+        self.request_id.last.lock().unwrap().clone()
+    }
+
+    /// Remember the timestamp of a write to a collection, for the searches with the `Session` consistency level.
+    fn record_write(&self, collection_name: &str, ts: u64) {
+        let key = (self.db_name.clone(), collection_name.to_string());
+        let mut session_ts = self.session_ts.write().unwrap();
+        let last = session_ts.entry(key).or_default();
+        *last = (*last).max(ts);
+    }
+
+    /// The timestamp of the last write of this session to a collection.
+    fn last_write_ts(&self, collection_name: &str) -> Option<u64> {
+        let key = (self.db_name.clone(), collection_name.to_string());
+        self.session_ts.read().unwrap().get(&key).copied()
+    }
+
+    /// Await the RPC `op` and unwrap its response, keeping track of the request id returned by the server.
+    ///
+    /// With the `tracing` feature, the RPC runs in a `milvus_rpc` span recording `op` and the target database and collection,
+    /// `collection_name` being empty for the RPCs not targeting one, and completes with an event recording its latency, gRPC code
+    /// and `RpcOutcome`. A failure status of the reply is recorded on the span as `status_code` and `reason`.
+    /// The metrics recorder set with `with_metrics` is notified of every RPC, with the status of the response.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    async fn call<T: RpcStatus>(
+        &self,
+        op: &'static str,
+        collection_name: &str,
+        rpc: impl Future<Output = std::result::Result<tonic::Response<T>, tonic::Status>>,
+    ) -> Result<T> {
+        let start = std::time::Instant::now();
+
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!(
+            "milvus_rpc",
+            method = op,
+            db = %self.db_name,
+            collection = collection_name,
+            status_code = tracing::field::Empty,
+            reason = tracing::field::Empty,
+        );
+        #[cfg(feature = "tracing")]
+        let result = {
+            use tracing::Instrument;
+
+            rpc.instrument(span.clone()).await
         };
+        #[cfg(not(feature = "tracing"))]
+        let result = rpc.await;
 
-        let mut dst: tonic::transport::Endpoint = url.try_into().map_err(|err| {
-            Error::InvalidParameter("url".to_owned(), format!("to parse {:?}", err))
-        })?;
+        let latency = start.elapsed();
+        let outcome = match &result {
+            Ok(response) => RpcOutcome::of_status(response.get_ref().rpc_status()),
+            Err(status) => RpcOutcome::Grpc(status.code()),
+        };
+
+        #[cfg(feature = "tracing")]
+        {
+            let code = match &result {
+                Ok(response) => {
+                    if let Some(status) = response.get_ref().rpc_status() {
+                        if !outcome.is_ok() {
+                            span.record("status_code", status.code);
+                            span.record("reason", status.reason.as_str());
+                        }
+                    }
+                    tonic::Code::Ok
+                }
+                Err(status) => status.code(),
+            };
+            span.in_scope(|| {
+                tracing::debug!(
+                    latency_ms = latency.as_millis() as u64,
+                    code = ?code,
+                    outcome = ?outcome,
+                    "rpc completed"
+                )
+            });
+        }
 
-        dst = dst.timeout(timeout);
+        if let Some(metrics) = &self.metrics {
+            metrics.record_rpc(op, latency, outcome);
+        }
 
-        let token = match (username, password) {
-            (Some(username), Some(password)) => {
-                let auth_token = format!("{}:{}", username, password);
-                let auth_token = general_purpose::STANDARD.encode(auth_token);
-                Some(auth_token)
+        match result {
+            Ok(response) => {
+                self.request_id.observe(response.metadata());
+                Ok(response.into_inner())
             }
-            _ => None,
+            Err(status) => {
+                self.request_id.observe(status.metadata());
+                Err(status.into())
+            }
+        }
+    }
+
+    /// Negotiate a session with the server through the `Connect` RPC.
+    ///
+    /// This step is optional but recommended right after `new`, or done by `ClientBuilder::build` with `handshake(true)`:
+    /// the identifier returned by the server is attached to every following request, which allows the server to track this
+    /// client, and the returned `ServerInfo`, also kept for `server_info`, tells the server version early.
+    pub async fn handshake(&self) -> Result<ServerInfo> {
+        let local_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs().to_string())
+            .unwrap_or_default();
+
+        let request = milvus::proto::milvus::ConnectRequest {
+            base: Some(self.new_msg(MsgType::Connect)),
+            client_info: Some(milvus::proto::common::ClientInfo {
+                sdk_type: "Rust".to_string(),
+                sdk_version: env!("CARGO_PKG_VERSION").to_string(),
+                local_time,
+                host: std::env::var("HOSTNAME").unwrap_or_default(),
+                ..Default::default()
+            }),
         };
 
-        let auth_interceptor = AuthInterceptor { token };
+        let response = self
+            .call("connect", "", self.client.clone().connect(request))
+            .await?;
+
+        status_to_result_ctx(&response.status, "handshake")?;
 
-        let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+        *self.identifier.write().unwrap() = Some(response.identifier);
 
-        let client = MilvusServiceClient::with_interceptor(conn, auth_interceptor);
+        let server_info: ServerInfo = response.server_info.unwrap_or_default().into();
+        *self.server_info.write().unwrap() = Some(server_info.clone());
+
+        Ok(server_info)
+    }
 
-        Ok(Self { client })
+    /// The server info returned by the last `handshake` of this client or its clones, `None` before the first one.
+    pub fn server_info(&self) -> Option<ServerInfo> {
+        self.server_info.read().unwrap().clone()
     }
 
+    /// Create a collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to create. It must be the name of `schema`, otherwise
+    ///   `Error::InvalidParameter` is returned rather than letting the server pick one of them.
+    ///
+    /// * `schema` - The schema of the collection.
+    ///
+    /// * `shards_num` - The number of shards, 2 by default.
+    ///
+    /// * `level` - The default consistency level of the searches and queries, `Bounded` by default.
     pub async fn create_collection(
         &self,
+        collection_name: &str,
         schema: CollectionSchema,
         shards_num: Option<i32>,
         level: Option<ConsistencyLevel>,
     ) -> Result<()> {
+        let request = self.create_collection_request(collection_name, schema, shards_num, level)?;
+
+        let status = self
+            .call(
+                "create_collection",
+                collection_name,
+                self.client.clone().create_collection(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&Some(status), "create_collection")
+    }
+
+    /// Create a collection unless one with the same name exists, reporting which happened.
+    ///
+    /// An existing collection is left as is, even if its schema differs from `schema`. A collection created concurrently,
+    /// between the check and the creation, is reported as existing rather than failing.
+    /// The arguments are the ones of `create_collection`.
+    pub async fn create_collection_if_not_exists(
+        &self,
+        collection_name: &str,
+        schema: CollectionSchema,
+        shards_num: Option<i32>,
+        level: Option<ConsistencyLevel>,
+    ) -> Result<CreateCollectionOutcome> {
+        if self.has_collection(collection_name).await? {
+            return Ok(CreateCollectionOutcome { created: false });
+        }
+
+        match self
+            .create_collection(collection_name, schema, shards_num, level)
+            .await
+        {
+            Ok(()) => Ok(CreateCollectionOutcome { created: true }),
+            // the server rejects a duplicate collection with a reason varying across versions, check it instead
+            Err(err @ Error::Server(..)) => {
+                if self.has_collection(collection_name).await? {
+                    Ok(CreateCollectionOutcome { created: false })
+                } else {
+                    Err(err)
+                }
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn create_collection_request(
+        &self,
+        collection_name: &str,
+        schema: CollectionSchema,
+        shards_num: Option<i32>,
+        level: Option<ConsistencyLevel>,
+    ) -> Result<milvus::proto::milvus::CreateCollectionRequest> {
+        if collection_name.is_empty() {
+            return Err(Error::InvalidParameter(
+                "collection_name".to_owned(),
+                "expected a non-empty collection name".to_owned(),
+            ));
+        }
+
+        if schema.name != collection_name {
+            return Err(Error::InvalidParameter(
+                "collection_name".to_owned(),
+                format!(
+                    "{} does not match the schema name {}",
+                    collection_name, schema.name
+                ),
+            ));
+        }
+
         let shards_num = shards_num.unwrap_or(2);
 
         let consistency_level = level.unwrap_or(ConsistencyLevel::Bounded);
@@ -82,57 +561,87 @@ impl Client {
         let mut buf = BytesMut::new();
         schema.encode(&mut buf)?;
 
-        let request = milvus::proto::milvus::CreateCollectionRequest {
-            base: Some(new_msg(MsgType::CreateCollection)),
-            collection_name: schema.name.to_string(),
+        Ok(milvus::proto::milvus::CreateCollectionRequest {
+            base: Some(self.new_msg(MsgType::CreateCollection)),
+            db_name: self.db_name.clone(),
+            collection_name: collection_name.to_string(),
             schema: buf.to_vec(),
             shards_num,
             consistency_level: consistency_level.into(),
             ..Default::default()
+        })
+    }
+
+    pub async fn drop_collection(&self, name: &str) -> Result<()> {
+        let request = milvus::proto::milvus::DropCollectionRequest {
+            base: Some(self.new_msg(MsgType::DropCollection)),
+            db_name: self.db_name.clone(),
+            collection_name: name.to_string(),
+            ..Default::default()
         };
 
         let status = self
-            .client
-            .clone()
-            .create_collection(request)
-            .await?
-            .into_inner();
+            .call(
+                "drop_collection",
+                name,
+                self.client.clone().drop_collection(request),
+            )
+            .await;
+        self.invalidate(name);
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status?), "drop_collection")
     }
 
-    pub async fn drop_collection(&self, name: &str) -> Result<()> {
-        let request = milvus::proto::milvus::DropCollectionRequest {
-            base: Some(new_msg(MsgType::DropCollection)),
-            collection_name: name.to_string(),
+    /// Rename a collection of the client database.
+    pub async fn rename_collection(&self, old_name: &str, new_name: &str) -> Result<()> {
+        let request = milvus::proto::milvus::RenameCollectionRequest {
+            base: Some(self.new_msg(MsgType::RenameCollection)),
+            db_name: self.db_name.clone(),
+            old_name: old_name.to_string(),
+            new_name: new_name.to_string(),
             ..Default::default()
         };
 
         let status = self
-            .client
-            .clone()
-            .drop_collection(request)
-            .await?
-            .into_inner();
+            .call(
+                "rename_collection",
+                old_name,
+                self.client.clone().rename_collection(request),
+            )
+            .await;
+        self.invalidate(old_name);
+        self.invalidate(new_name);
+
+        status_to_result_ctx(&Some(status?), "rename_collection")
+    }
 
-        status_to_result(&Some(status))
+    /// Return the id of a collection, described once and then cached until the collection is dropped or renamed through this
+    /// client or its clones.
+    pub async fn collection_id(&self, name: &str) -> Result<i64> {
+        self.collection_ids
+            .get_or_fetch(&self.db_name, name, || async {
+                Ok(self.describe_collection(name).await?.id)
+            })
+            .await
     }
 
     pub async fn has_collection(&self, name: &str) -> Result<bool> {
         let request = milvus::proto::milvus::HasCollectionRequest {
-            base: Some(new_msg(MsgType::HasCollection)),
+            base: Some(self.new_msg(MsgType::HasCollection)),
+            db_name: self.db_name.clone(),
             collection_name: name.to_string(),
             ..Default::default()
         };
 
         let response = self
-            .client
-            .clone()
-            .has_collection(request)
-            .await?
-            .into_inner();
+            .call(
+                "has_collection",
+                name,
+                self.client.clone().has_collection(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "has_collection")?;
 
         Ok(response.value)
     }
@@ -145,139 +654,335 @@ impl Client {
     /// * `replica_num` - replica number to load, default by 1
     ///
     pub async fn load_collection(&self, name: &str, replica_num: Option<i32>) -> Result<()> {
-        let replica_number = replica_num.unwrap_or(1);
+        let mut options = LoadOptions::new();
+        if let Some(replica_number) = replica_num {
+            options = options.replica_number(replica_number);
+        }
 
-        let request = milvus::proto::milvus::LoadCollectionRequest {
-            base: Some(new_msg(MsgType::LoadCollection)),
-            collection_name: name.to_string(),
-            replica_number,
-            ..Default::default()
-        };
+        self.load_collection_with(name, options).await
+    }
+
+    /// Load a collection with options, e.g. to load only some of its fields or on some resource groups.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - collection name
+    ///
+    /// * `options` - the replicas, resource groups and fields to load
+    ///
+    pub async fn load_collection_with(&self, name: &str, options: LoadOptions) -> Result<()> {
+        let request = self.load_collection_request(name, options);
 
         let status = self
-            .client
-            .clone()
-            .load_collection(request)
-            .await?
-            .into_inner();
+            .call(
+                "load_collection",
+                name,
+                self.client.clone().load_collection(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&Some(status), "load_collection")
+    }
 
-        status_to_result(&Some(status))
+    fn load_collection_request(
+        &self,
+        name: &str,
+        options: LoadOptions,
+    ) -> milvus::proto::milvus::LoadCollectionRequest {
+        milvus::proto::milvus::LoadCollectionRequest {
+            base: Some(self.new_msg(MsgType::LoadCollection)),
+            db_name: self.db_name.clone(),
+            collection_name: name.to_string(),
+            replica_number: options.replica_number,
+            resource_groups: options.resource_groups,
+            load_fields: options.load_fields,
+            skip_load_dynamic_field: options.skip_load_dynamic_field,
+            refresh: options.refresh,
+            ..Default::default()
+        }
     }
 
     pub async fn release_collection(&self, name: &str) -> Result<()> {
         let request = milvus::proto::milvus::ReleaseCollectionRequest {
-            base: Some(new_msg(MsgType::ReleaseCollection)),
+            base: Some(self.new_msg(MsgType::ReleaseCollection)),
+            db_name: self.db_name.clone(),
             collection_name: name.to_string(),
             ..Default::default()
         };
 
         let status = self
-            .client
-            .clone()
-            .release_collection(request)
-            .await?
-            .into_inner();
+            .call(
+                "release_collection",
+                name,
+                self.client.clone().release_collection(request),
+            )
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "release_collection")
     }
 
-    /// Get collection meta datas like: schema, collectionID, shards number ...
+    /// Get the loading progress of a collection, or of some of its partitions.
     ///
     /// # Arguments
     ///
-    /// * `name` - collection name
+    /// * `collection_name` - The name of the collection being loaded.
     ///
-    pub async fn describe_collection(&self, name: &str) -> Result<CollectionMetadata> {
-        let request = milvus::proto::milvus::DescribeCollectionRequest {
-            base: Some(new_msg(MsgType::DescribeCollection)),
-            collection_name: name.to_string(),
+    /// * `partition_names` - The partitions to check, all the loaded ones if empty.
+    pub async fn get_loading_progress(
+        &self,
+        collection_name: &str,
+        partition_names: Vec<&str>,
+    ) -> Result<LoadingProgress> {
+        let request = milvus::proto::milvus::GetLoadingProgressRequest {
+            base: Some(self.new_msg(MsgType::LoadCollection)),
+            db_name: self.db_name.clone(),
+            collection_name: collection_name.to_string(),
+            partition_names: partition_names.iter().map(|x| x.to_string()).collect(),
             ..Default::default()
         };
 
         let response = self
-            .client
-            .clone()
-            .describe_collection(request)
-            .await?
-            .into_inner();
-
-        status_to_result(&response.status)?;
-
-        let metadata = CollectionMetadata {
-            name: response.collection_name,
-            id: response.collection_id,
-            schema: response.schema.map(|x| x.into()),
-            created_timestamp: response.created_timestamp,
-            created_utc_timestamp: response.created_utc_timestamp,
-            shards_num: response.shards_num,
-            aliases: response.aliases,
-            consistency_level: crate::common::ConsistencyLevel::from_i32(
-                response.consistency_level,
+            .call(
+                "get_loading_progress",
+                collection_name,
+                self.client.clone().get_loading_progress(request),
             )
-            .unwrap(),
-        };
+            .await?;
 
-        Ok(metadata)
+        status_to_result_ctx(&response.status, "get_loading_progress")?;
+
+        Ok(LoadingProgress {
+            progress: response.progress,
+            refresh_progress: response.refresh_progress,
+        })
     }
 
-    /// Get collection statistics
+    /// Get the load state of a collection, or of some of its partitions.
     ///
     /// # Arguments
     ///
-    /// * `name` - collection name
+    /// * `collection_name` - The name of the collection.
     ///
-    pub async fn get_collection_stats(&self, name: &str) -> Result<HashMap<String, String>> {
-        let request = milvus::proto::milvus::GetCollectionStatisticsRequest {
-            base: Some(new_msg(MsgType::GetCollectionStatistics)),
-            collection_name: name.to_string(),
-            ..Default::default()
-        };
+    /// * `partition_names` - The partitions to check, the whole collection if empty.
+    pub async fn get_load_state(
+        &self,
+        collection_name: &str,
+        partition_names: Vec<&str>,
+    ) -> Result<LoadState> {
+        let request = self.get_load_state_request(collection_name, partition_names);
 
         let response = self
-            .client
-            .clone()
-            .get_collection_statistics(request)
-            .await?
-            .into_inner();
+            .call(
+                "get_load_state",
+                collection_name,
+                self.client.clone().get_load_state(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "get_load_state")?;
 
-        let stats: HashMap<String, String> =
-            HashMap::from_iter(response.stats.into_iter().map(|x| (x.key, x.value)));
+        Ok(response.state.into())
+    }
+
+    fn get_load_state_request(
+        &self,
+        collection_name: &str,
+        partition_names: Vec<&str>,
+    ) -> milvus::proto::milvus::GetLoadStateRequest {
+        milvus::proto::milvus::GetLoadStateRequest {
+            base: Some(self.new_msg(MsgType::GetLoadState)),
+            db_name: self.db_name.clone(),
+            collection_name: collection_name.to_string(),
+            partition_names: partition_names.iter().map(|x| x.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Get the load state of a single partition, e.g. to know which partitions of a selectively loaded collection are ready.
+    ///
+    /// Use `get_loading_progress` with the partition name for the progress of a partition being loaded.
+    pub async fn get_partition_load_state(
+        &self,
+        collection_name: &str,
+        partition_name: &str,
+    ) -> Result<LoadState> {
+        self.get_load_state(collection_name, vec![partition_name])
+            .await
+    }
+
+    /// Wait until the collection is fully loaded, e.g. after `load_collection`.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection being loaded.
+    ///
+    /// * `timeout` - maximum time to wait before returning `Error::Timeout`
+    pub async fn wait_for_loading(&self, collection_name: &str, timeout: Duration) -> Result<()> {
+        poll_until(
+            "wait_for_loading",
+            Duration::from_millis(WAIT_LOAD_DURATION_MS),
+            timeout,
+            || async move {
+                let progress = self.get_loading_progress(collection_name, vec![]).await?;
+                Ok((progress.progress >= 100).then_some(()))
+            },
+        )
+        .await
+    }
+
+    /// Whether a collection can be searched right now: it is fully loaded and all its indexes are built.
+    ///
+    /// Returns `false` for a collection without index, which cannot be loaded.
+    pub async fn is_searchable(&self, collection_name: &str) -> Result<bool> {
+        let load_state = self.get_load_state(collection_name, vec![]).await?;
+        if !load_state.is_loaded() {
+            return Ok(false);
+        }
+
+        let indexes = match self.describe_index(collection_name, "", "").await {
+            Ok(indexes) => indexes,
+            Err(Error::NotFound(_)) => return Ok(false),
+            Err(err) => return Err(err),
+        };
+
+        Ok(is_searchable(&load_state, &indexes))
+    }
+
+    /// Get collection meta datas like: schema, collectionID, shards number ...
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - collection name
+    ///
+    pub async fn describe_collection(&self, name: &str) -> Result<CollectionMetadata> {
+        let request = milvus::proto::milvus::DescribeCollectionRequest {
+            base: Some(self.new_msg(MsgType::DescribeCollection)),
+            db_name: self.db_name.clone(),
+            collection_name: name.to_string(),
+            ..Default::default()
+        };
+
+        let response = self
+            .call(
+                "describe_collection",
+                name,
+                self.client.clone().describe_collection(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&response.status, "describe_collection")?;
+
+        let metadata = CollectionMetadata {
+            name: response.collection_name,
+            id: response.collection_id,
+            schema: response
+                .schema
+                .map(CollectionSchema::try_from)
+                .transpose()?,
+            created_timestamp: response.created_timestamp,
+            created_utc_timestamp: response.created_utc_timestamp,
+            shards_num: response.shards_num,
+            aliases: response.aliases,
+            consistency_level: crate::common::ConsistencyLevel::from_i32(
+                response.consistency_level,
+            )
+            .unwrap(),
+            properties: response
+                .properties
+                .into_iter()
+                .map(|kv| (kv.key, kv.value))
+                .collect(),
+            num_partitions: response.num_partitions,
+            db_name: response.db_name,
+        };
+        self.describe_cache.insert(&self.db_name, name, &metadata);
+
+        Ok(metadata)
+    }
+
+    /// Get a handle on an existing collection.
+    ///
+    /// The collection schema is described once and kept in the handle.
+    pub async fn collection(&self, name: &str) -> Result<CollectionHandle> {
+        let metadata = self.describe_cached(name).await?;
+
+        let schema = metadata
+            .schema
+            .ok_or_else(|| Error::Unexpected(format!("no schema for collection {}", name)))?;
+
+        Ok(CollectionHandle {
+            client: self.clone(),
+            name: metadata.name,
+            schema,
+        })
+    }
+
+    /// Get collection statistics
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - collection name
+    ///
+    pub async fn get_collection_stats(&self, name: &str) -> Result<HashMap<String, String>> {
+        let request = self.get_collection_stats_request(name);
+
+        let response = self
+            .call(
+                "get_collection_statistics",
+                name,
+                self.client.clone().get_collection_statistics(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&response.status, "get_collection_stats")?;
+
+        let stats: HashMap<String, String> =
+            HashMap::from_iter(response.stats.into_iter().map(|x| (x.key, x.value)));
 
         Ok(stats)
     }
 
+    /// Get the number of entities of a collection, from the `row_count` of its statistics.
+    ///
+    /// The statistics only count the flushed entities and include the deleted ones until compaction; use `count` for an
+    /// exact number.
+    pub async fn get_row_count(&self, collection_name: &str) -> Result<i64> {
+        let stats = self.get_collection_stats(collection_name).await?;
+
+        row_count_from_stats(&stats, collection_name)
+    }
+
+    fn get_collection_stats_request(
+        &self,
+        name: &str,
+    ) -> milvus::proto::milvus::GetCollectionStatisticsRequest {
+        milvus::proto::milvus::GetCollectionStatisticsRequest {
+            base: Some(self.new_msg(MsgType::GetCollectionStatistics)),
+            db_name: self.db_name.clone(),
+            collection_name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
     /// Return basic collection infos.
     pub async fn show_collections(&self) -> Result<Vec<CollectionInfo>> {
         let request = milvus::proto::milvus::ShowCollectionsRequest {
-            base: Some(new_msg(MsgType::ShowCollections)),
+            base: Some(self.new_msg(MsgType::ShowCollections)),
+            db_name: self.db_name.clone(),
             ..Default::default()
         };
 
         let response = self
-            .client
-            .clone()
-            .show_collections(request)
-            .await?
-            .into_inner();
+            .call(
+                "show_collections",
+                "",
+                self.client.clone().show_collections(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
-
-        let mut info_vec = vec![];
-        for i in 0..response.collection_names.len() {
-            println!("collection_names: {}", response.collection_names[i]);
-            info_vec.push(CollectionInfo {
-                name: response.collection_names[i].clone(),
-                id: response.collection_ids[i],
-                created_timestamp: response.created_timestamps[i],
-                created_utc_timestamp: response.created_utc_timestamps[i],
-                in_memory_percentage: response.in_memory_percentages[i],
-                query_service_available: response.query_service_available[i],
-            });
-        }
+        status_to_result_ctx(&response.status, "show_collections")?;
 
-        Ok(info_vec)
+        collection_infos(response)
     }
 
     /// Alter collection.
@@ -287,7 +992,8 @@ impl Client {
         properties: Vec<(String, String)>,
     ) -> Result<()> {
         let request = milvus::proto::milvus::AlterCollectionRequest {
-            base: Some(new_msg(MsgType::AlterCollection)),
+            base: Some(self.new_msg(MsgType::AlterCollection)),
+            db_name: self.db_name.clone(),
             collection_name: name.to_string(),
             properties: properties
                 .into_iter()
@@ -297,13 +1003,167 @@ impl Client {
         };
 
         let status = self
-            .client
-            .clone()
-            .alter_collection(request)
-            .await?
-            .into_inner();
+            .call(
+                "alter_collection",
+                name,
+                self.client.clone().alter_collection(request),
+            )
+            .await;
+        self.invalidate(name);
+
+        status_to_result_ctx(&Some(status?), "alter_collection")
+    }
+
+    /// Add a field to an existing collection, e.g. to evolve its schema without recreating it.
+    ///
+    /// The field is always added as nullable, as required by the server. The existing entities get `default_value` for the
+    /// new field, or the default value of `field`, or null without a default value. The field cannot be a primary key.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection.
+    ///
+    /// * `field` - The schema of the new field.
+    ///
+    /// * `default_value` - The value of the field for the entities not providing one.
+    pub async fn add_field(
+        &self,
+        collection_name: &str,
+        field: FieldSchema,
+        default_value: Option<Value>,
+    ) -> Result<()> {
+        let request = self.add_field_request(collection_name, field, default_value)?;
+
+        let status = self
+            .call(
+                "add_collection_field",
+                collection_name,
+                self.client.clone().add_collection_field(request),
+            )
+            .await;
+        self.invalidate(collection_name);
+
+        status_to_result_ctx(&Some(status?), "add_field")
+    }
+
+    fn add_field_request(
+        &self,
+        collection_name: &str,
+        field: FieldSchema,
+        default_value: Option<Value>,
+    ) -> Result<milvus::proto::milvus::AddCollectionFieldRequest> {
+        if field.is_primary() {
+            return Err(Error::InvalidParameter(
+                "field".to_owned(),
+                format!("{} is a primary key", field.name()),
+            ));
+        }
+        let field = match default_value {
+            Some(value) => field.default_value(value),
+            None => field,
+        }
+        .nullable(true);
+        field.check_params()?;
+
+        let field: milvus::proto::schema::FieldSchema = field.into();
+        let mut buf = BytesMut::new();
+        field.encode(&mut buf)?;
+
+        Ok(milvus::proto::milvus::AddCollectionFieldRequest {
+            base: Some(self.new_msg(MsgType::AddCollectionField)),
+            db_name: self.db_name.clone(),
+            collection_name: collection_name.to_string(),
+            schema: buf.to_vec(),
+            ..Default::default()
+        })
+    }
+
+    /// Alter the properties of a field of a collection, e.g. `max_length` of a VarChar field or `mmap.enabled`.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection.
+    ///
+    /// * `field_name` - The name of the field to alter.
+    ///
+    /// * `properties` - The properties to set, by key.
+    pub async fn alter_collection_field(
+        &self,
+        collection_name: &str,
+        field_name: &str,
+        properties: HashMap<String, String>,
+    ) -> Result<()> {
+        let request = self.alter_collection_field_request(collection_name, field_name, properties);
+
+        let status = self
+            .call(
+                "alter_collection_field",
+                collection_name,
+                self.client.clone().alter_collection_field(request),
+            )
+            .await;
+        self.invalidate(collection_name);
+
+        status_to_result_ctx(&Some(status?), "alter_collection_field")
+    }
+
+    fn alter_collection_field_request(
+        &self,
+        collection_name: &str,
+        field_name: &str,
+        properties: HashMap<String, String>,
+    ) -> milvus::proto::milvus::AlterCollectionFieldRequest {
+        let mut properties: Vec<_> = properties
+            .into_iter()
+            .map(|(key, value)| milvus::proto::common::KeyValuePair { key, value })
+            .collect();
+        properties.sort_by(|a, b| a.key.cmp(&b.key));
+
+        milvus::proto::milvus::AlterCollectionFieldRequest {
+            base: Some(self.new_msg(MsgType::AlterCollectionField)),
+            db_name: self.db_name.clone(),
+            collection_name: collection_name.to_string(),
+            field_name: field_name.to_string(),
+            properties,
+            ..Default::default()
+        }
+    }
+
+    /// Change the maximum length of a VarChar field, e.g. to grow it as the data evolves.
+    pub async fn set_field_max_length(
+        &self,
+        collection_name: &str,
+        field_name: &str,
+        max_length: MaxLength,
+    ) -> Result<()> {
+        if max_length <= 0 || max_length > MAX_VARCHAR_LENGTH {
+            return Err(Error::InvalidParameter(
+                "max_length".to_owned(),
+                format!(
+                    "expected a length in [1, {}], got {}",
+                    MAX_VARCHAR_LENGTH, max_length
+                ),
+            ));
+        }
+
+        let properties = HashMap::from([("max_length".to_string(), max_length.to_string())]);
+        self.alter_collection_field(collection_name, field_name, properties)
+            .await
+    }
 
-        status_to_result(&Some(status))
+    /// Set the time to live of the entities of a collection; expired entities are dropped by compactions.
+    ///
+    /// The TTL has a granularity of one second: fractions are rounded up, and a TTL under one second returns
+    /// `Error::InvalidParameter`, except `Duration::ZERO`, which disables the TTL.
+    pub async fn set_collection_ttl(&self, collection_name: &str, ttl: Duration) -> Result<()> {
+        self.alter_collection(collection_name, vec![ttl_property(ttl)?])
+            .await
+    }
+
+    /// Enable or disable memory-mapping the data of a collection. The collection must be released first.
+    pub async fn set_collection_mmap(&self, collection_name: &str, enabled: bool) -> Result<()> {
+        self.alter_collection(collection_name, vec![mmap_property(enabled)])
+            .await
     }
 
     /// Create partition in created collection.
@@ -319,58 +1179,64 @@ impl Client {
         partition_name: &str,
     ) -> Result<()> {
         let request = milvus::proto::milvus::CreatePartitionRequest {
-            base: Some(new_msg(MsgType::CreatePartition)),
+            base: Some(self.new_msg(MsgType::CreatePartition)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             partition_name: partition_name.to_string(),
             ..Default::default()
         };
 
         let status = self
-            .client
-            .clone()
-            .create_partition(request)
-            .await?
-            .into_inner();
+            .call(
+                "create_partition",
+                collection_name,
+                self.client.clone().create_partition(request),
+            )
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "create_partition")
     }
 
     /// Drop partition in created collection.
     pub async fn drop_partition(&self, collection_name: &str, partition_name: &str) -> Result<()> {
         let request = milvus::proto::milvus::DropPartitionRequest {
-            base: Some(new_msg(MsgType::DropPartition)),
+            base: Some(self.new_msg(MsgType::DropPartition)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             partition_name: partition_name.to_string(),
             ..Default::default()
         };
 
         let status = self
-            .client
-            .clone()
-            .drop_partition(request)
-            .await?
-            .into_inner();
+            .call(
+                "drop_partition",
+                collection_name,
+                self.client.clone().drop_partition(request),
+            )
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "drop_partition")
     }
 
     /// Check if partition exist in collection or not.
     pub async fn has_partition(&self, collection_name: &str, partition_name: &str) -> Result<bool> {
         let request = milvus::proto::milvus::HasPartitionRequest {
-            base: Some(new_msg(MsgType::HasPartition)),
+            base: Some(self.new_msg(MsgType::HasPartition)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             partition_name: partition_name.to_string(),
             ..Default::default()
         };
 
         let response = self
-            .client
-            .clone()
-            .has_partition(request)
-            .await?
-            .into_inner();
+            .call(
+                "has_partition",
+                collection_name,
+                self.client.clone().has_partition(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "has_partition")?;
 
         Ok(response.value)
     }
@@ -384,7 +1250,8 @@ impl Client {
         replica_number: i32,
     ) -> Result<()> {
         let request = milvus::proto::milvus::LoadPartitionsRequest {
-            base: Some(new_msg(MsgType::LoadPartitions)),
+            base: Some(self.new_msg(MsgType::LoadPartitions)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             partition_names: partition_names.iter().map(|x| x.to_string()).collect(),
             replica_number,
@@ -392,13 +1259,14 @@ impl Client {
         };
 
         let status = self
-            .client
-            .clone()
-            .load_partitions(request)
-            .await?
-            .into_inner();
+            .call(
+                "load_partitions",
+                collection_name,
+                self.client.clone().load_partitions(request),
+            )
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "load_partitions")
     }
 
     /// Release specific partitions data of one collection from query nodes.
@@ -409,20 +1277,22 @@ impl Client {
         partition_names: Vec<&str>,
     ) -> Result<()> {
         let request = milvus::proto::milvus::ReleasePartitionsRequest {
-            base: Some(new_msg(MsgType::ReleasePartitions)),
+            base: Some(self.new_msg(MsgType::ReleasePartitions)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             partition_names: partition_names.iter().map(|x| x.to_string()).collect(),
             ..Default::default()
         };
 
         let status = self
-            .client
-            .clone()
-            .release_partitions(request)
-            .await?
-            .into_inner();
+            .call(
+                "release_partitions",
+                collection_name,
+                self.client.clone().release_partitions(request),
+            )
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "release_partitions")
     }
 
     /// Get partition statistics.
@@ -432,20 +1302,22 @@ impl Client {
         partition_name: &str,
     ) -> Result<HashMap<String, String>> {
         let request = milvus::proto::milvus::GetPartitionStatisticsRequest {
-            base: Some(new_msg(MsgType::GetPartitionStatistics)),
+            base: Some(self.new_msg(MsgType::GetPartitionStatistics)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             partition_name: partition_name.to_string(),
             ..Default::default()
         };
 
         let response = self
-            .client
-            .clone()
-            .get_partition_statistics(request)
-            .await?
-            .into_inner();
+            .call(
+                "get_partition_statistics",
+                collection_name,
+                self.client.clone().get_partition_statistics(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "get_partition_stats")?;
 
         let stats: HashMap<String, String> =
             HashMap::from_iter(response.stats.into_iter().map(|x| (x.key, x.value)));
@@ -460,7 +1332,8 @@ impl Client {
         partition_names: Option<Vec<&str>>,
     ) -> Result<Vec<PartitionInfo>> {
         let request = milvus::proto::milvus::ShowPartitionsRequest {
-            base: Some(new_msg(MsgType::ShowPartitions)),
+            base: Some(self.new_msg(MsgType::ShowPartitions)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             partition_names: partition_names
                 .unwrap_or_default()
@@ -471,69 +1344,133 @@ impl Client {
         };
 
         let response = self
-            .client
-            .clone()
-            .show_partitions(request)
-            .await?
-            .into_inner();
-
-        status_to_result(&response.status)?;
+            .call(
+                "show_partitions",
+                collection_name,
+                self.client.clone().show_partitions(request),
+            )
+            .await?;
 
-        let mut res = vec![];
-        for i in 0..response.partition_names.len() {
-            res.push(PartitionInfo {
-                name: response.partition_names[i].clone(),
-                id: response.partition_i_ds[i],
-                created_timestamp: response.created_timestamps[i],
-                created_utc_timestamp: response.created_utc_timestamps[i],
-                in_memory_percentage: response.in_memory_percentages[i],
-            });
-        }
+        status_to_result_ctx(&response.status, "show_partitions")?;
 
-        Ok(res)
+        partition_infos(response)
     }
 
     pub async fn create_alias(&self, collection_name: &str, alias: &str) -> Result<()> {
         let request = milvus::proto::milvus::CreateAliasRequest {
-            base: Some(new_msg(MsgType::CreateAlias)),
+            base: Some(self.new_msg(MsgType::CreateAlias)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             alias: alias.to_string(),
             ..Default::default()
         };
 
         let status = self
-            .client
-            .clone()
-            .create_alias(request)
-            .await?
-            .into_inner();
+            .call(
+                "create_alias",
+                collection_name,
+                self.client.clone().create_alias(request),
+            )
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "create_alias")
     }
 
     pub async fn drop_alias(&self, alias: &str) -> Result<()> {
         let request = milvus::proto::milvus::DropAliasRequest {
-            base: Some(new_msg(MsgType::DropAlias)),
+            base: Some(self.new_msg(MsgType::DropAlias)),
+            db_name: self.db_name.clone(),
             alias: alias.to_string(),
             ..Default::default()
         };
 
-        let status = self.client.clone().drop_alias(request).await?.into_inner();
+        let status = self
+            .call("drop_alias", "", self.client.clone().drop_alias(request))
+            .await?;
+        self.invalidate(alias);
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "drop_alias")
     }
 
     pub async fn alter_alias(&self, alias: &str, collection_name: &str) -> Result<()> {
         let request = milvus::proto::milvus::AlterAliasRequest {
-            base: Some(new_msg(MsgType::AlterAlias)),
+            base: Some(self.new_msg(MsgType::AlterAlias)),
+            db_name: self.db_name.clone(),
+            alias: alias.to_string(),
+            collection_name: collection_name.to_string(),
+            ..Default::default()
+        };
+
+        let status = self
+            .call(
+                "alter_alias",
+                collection_name,
+                self.client.clone().alter_alias(request),
+            )
+            .await?;
+        self.invalidate(alias);
+
+        status_to_result_ctx(&Some(status), "alter_alias")
+    }
+
+    /// Return the name of the collection `alias` points to.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_name` - The name of the database of the alias, the client database if `None`.
+    ///
+    /// * `alias` - The alias to describe.
+    pub async fn describe_alias(&self, db_name: Option<&str>, alias: &str) -> Result<String> {
+        let request = milvus::proto::milvus::DescribeAliasRequest {
+            base: Some(self.new_msg(MsgType::DescribeAlias)),
+            db_name: self.resolve_db_name(db_name),
             alias: alias.to_string(),
+            ..Default::default()
+        };
+
+        let response = self
+            .call(
+                "describe_alias",
+                "",
+                self.client.clone().describe_alias(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&response.status, "describe_alias")?;
+
+        Ok(response.collection)
+    }
+
+    /// List the aliases of a collection, or of all the collections of the database if `collection_name` is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_name` - The name of the database of the collection, the client database if `None`.
+    ///
+    /// * `collection_name` - The name of the collection whose aliases to list.
+    pub async fn list_aliases(
+        &self,
+        db_name: Option<&str>,
+        collection_name: &str,
+    ) -> Result<Vec<String>> {
+        let request = milvus::proto::milvus::ListAliasesRequest {
+            base: Some(self.new_msg(MsgType::ListAliases)),
+            db_name: self.resolve_db_name(db_name),
             collection_name: collection_name.to_string(),
             ..Default::default()
         };
 
-        let status = self.client.clone().alter_alias(request).await?.into_inner();
+        let response = self
+            .call(
+                "list_aliases",
+                collection_name,
+                self.client.clone().list_aliases(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&response.status, "list_aliases")?;
 
-        status_to_result(&Some(status))
+        Ok(response.aliases)
     }
 
     /// Create index for vector data
@@ -555,7 +1492,8 @@ impl Client {
         index_name: &str,
     ) -> Result<()> {
         let request = milvus::proto::milvus::CreateIndexRequest {
-            base: Some(new_msg(MsgType::CreateIndex)),
+            base: Some(self.new_msg(MsgType::CreateIndex)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             field_name: field_name.to_string(),
             extra_params: extra_params
@@ -571,13 +1509,14 @@ impl Client {
         };
 
         let status = self
-            .client
-            .clone()
-            .create_index(request)
-            .await?
-            .into_inner();
+            .call(
+                "create_index",
+                collection_name,
+                self.client.clone().create_index(request),
+            )
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "create_index")
     }
 
     pub async fn describe_index(
@@ -587,7 +1526,8 @@ impl Client {
         index_name: &str,
     ) -> Result<Vec<IndexInfo>> {
         let request = milvus::proto::milvus::DescribeIndexRequest {
-            base: Some(new_msg(MsgType::DescribeIndex)),
+            base: Some(self.new_msg(MsgType::DescribeIndex)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             field_name: field_name.to_string(),
             index_name: index_name.to_string(),
@@ -595,37 +1535,103 @@ impl Client {
         };
 
         let response = self
-            .client
-            .clone()
-            .describe_index(request)
-            .await?
-            .into_inner();
+            .call(
+                "describe_index",
+                collection_name,
+                self.client.clone().describe_index(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "describe_index")?;
 
-        let mut res = vec![];
-        for i in 0..response.index_descriptions.len() {
-            res.push(IndexInfo {
-                index_name: response.index_descriptions[i].index_name.clone(),
-                index_id: response.index_descriptions[i].index_id,
-                params: response.index_descriptions[i]
-                    .params
-                    .iter()
-                    .map(|kv| (kv.key.clone(), kv.value.clone()))
-                    .collect(),
-                field_name: response.index_descriptions[i].field_name.clone(),
-                indexed_rows: response.index_descriptions[i].indexed_rows,
-                total_rows: response.index_descriptions[i].total_rows,
-                state: response.index_descriptions[i].state,
-                index_state_fail_reason: response.index_descriptions[i]
-                    .index_state_fail_reason
-                    .clone(),
-            });
-        }
+        let res = response
+            .index_descriptions
+            .into_iter()
+            .map(|description| description.into())
+            .collect();
 
         Ok(res)
     }
 
+    /// Get the number of indexed and total rows of an index, summed over its segments.
+    ///
+    /// Servers without the `GetIndexStatistics` RPC are answered through `describe_index` instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_name` - The name of the database of the collection, the client database if `None`.
+    ///
+    /// * `collection_name` - The name of the collection the index belongs to.
+    ///
+    /// * `index_name` - The name of the index, all the indexes of the collection if empty.
+    pub async fn get_index_statistics(
+        &self,
+        db_name: Option<&str>,
+        collection_name: &str,
+        index_name: &str,
+    ) -> Result<IndexProgress> {
+        let indexes = self
+            .describe_index_statistics(db_name, collection_name, index_name)
+            .await?;
+
+        Ok(IndexProgress {
+            indexed_rows: indexes.iter().map(|index| index.indexed_rows).sum(),
+            total_rows: indexes.iter().map(|index| index.total_rows).sum(),
+        })
+    }
+
+    /// Get the build statistics of each index of a collection: indexed, pending and total rows aggregated over the segments,
+    /// and the build state.
+    ///
+    /// Servers without the `GetIndexStatistics` RPC are answered through `describe_index` instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_name` - The name of the database of the collection, the client database if `None`.
+    ///
+    /// * `collection_name` - The name of the collection the indexes belong to.
+    ///
+    /// * `index_name` - The name of the index, all the indexes of the collection if empty.
+    pub async fn describe_index_statistics(
+        &self,
+        db_name: Option<&str>,
+        collection_name: &str,
+        index_name: &str,
+    ) -> Result<Vec<IndexInfo>> {
+        let request = milvus::proto::milvus::GetIndexStatisticsRequest {
+            base: Some(self.new_msg(MsgType::DescribeIndex)),
+            db_name: self.resolve_db_name(db_name),
+            collection_name: collection_name.to_string(),
+            index_name: index_name.to_string(),
+            ..Default::default()
+        };
+
+        match self
+            .call(
+                "get_index_statistics",
+                collection_name,
+                self.client.clone().get_index_statistics(request),
+            )
+            .await
+        {
+            Ok(response) => {
+                status_to_result_ctx(&response.status, "get_index_statistics")?;
+                Ok(response
+                    .index_descriptions
+                    .into_iter()
+                    .map(|description| description.into())
+                    .collect())
+            }
+            Err(Error::Grpc(status)) if status.code() == tonic::Code::Unimplemented => {
+                self.clone()
+                    .with_database(&self.resolve_db_name(db_name))
+                    .describe_index(collection_name, "", index_name)
+                    .await
+            }
+            Err(err) => Err(err),
+        }
+    }
+
     pub async fn get_index_state(
         &self,
         collection_name: &str,
@@ -633,7 +1639,8 @@ impl Client {
         index_name: &str,
     ) -> Result<IndexState> {
         let request = milvus::proto::milvus::GetIndexStateRequest {
-            base: Some(new_msg(MsgType::GetIndexState)),
+            base: Some(self.new_msg(MsgType::GetIndexState)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             field_name: field_name.to_string(),
             index_name: index_name.to_string(),
@@ -641,20 +1648,59 @@ impl Client {
         };
 
         let response = self
-            .client
-            .clone()
-            .get_index_state(request)
-            .await?
-            .into_inner();
+            .call(
+                "get_index_state",
+                collection_name,
+                self.client.clone().get_index_state(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "get_index_state")?;
 
         Ok(IndexState {
-            state: response.state,
+            state: response.state.into(),
             fail_reason: response.fail_reason,
         })
     }
 
+    /// Wait until the index is built, e.g. after `create_index`. Fails as soon as the server reports the build failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection the index belongs to.
+    ///
+    /// * `field_name` - The name of the indexed field.
+    ///
+    /// * `index_name` - The name of the index.
+    ///
+    /// * `timeout` - maximum time to wait before returning `Error::Timeout`
+    pub async fn wait_for_index(
+        &self,
+        collection_name: &str,
+        field_name: &str,
+        index_name: &str,
+        timeout: Duration,
+    ) -> Result<()> {
+        poll_until(
+            "wait_for_index",
+            Duration::from_millis(WAIT_LOAD_DURATION_MS),
+            timeout,
+            || async move {
+                let state = self
+                    .get_index_state(collection_name, field_name, index_name)
+                    .await?;
+                if state.state.is_failed() {
+                    return Err(Error::Unexpected(format!(
+                        "wait_for_index: index {} failed: {}",
+                        index_name, state.fail_reason
+                    )));
+                }
+                Ok(state.state.is_finished().then_some(()))
+            },
+        )
+        .await
+    }
+
     pub async fn get_index_build_progress(
         &self,
         collection_name: &str,
@@ -662,7 +1708,8 @@ impl Client {
         index_name: &str,
     ) -> Result<IndexProgress> {
         let request = milvus::proto::milvus::GetIndexBuildProgressRequest {
-            base: Some(new_msg(MsgType::GetIndexBuildProgress)),
+            base: Some(self.new_msg(MsgType::GetIndexBuildProgress)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             field_name: field_name.to_string(),
             index_name: index_name.to_string(),
@@ -670,13 +1717,14 @@ impl Client {
         };
 
         let response = self
-            .client
-            .clone()
-            .get_index_build_progress(request)
-            .await?
-            .into_inner();
+            .call(
+                "get_index_build_progress",
+                collection_name,
+                self.client.clone().get_index_build_progress(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "get_index_build_progress")?;
 
         Ok(IndexProgress {
             total_rows: response.total_rows,
@@ -691,39 +1739,96 @@ impl Client {
         index_name: &str,
     ) -> Result<()> {
         let request = milvus::proto::milvus::DropIndexRequest {
-            base: Some(new_msg(MsgType::DropIndex)),
+            base: Some(self.new_msg(MsgType::DropIndex)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             field_name: field_name.to_string(),
             index_name: index_name.to_string(),
             ..Default::default()
         };
 
-        let status = self.client.clone().drop_index(request).await?.into_inner();
+        let status = self
+            .call(
+                "drop_index",
+                collection_name,
+                self.client.clone().drop_index(request),
+            )
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "drop_index")
     }
 
-    pub async fn insert(
+    /// Alter the properties of an existing index, e.g. `mmap.enabled`, without rebuilding it.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_name` - The name of the database the collection belongs to, the client database if `None`.
+    ///
+    /// * `collection_name` - The name of the collection the index belongs to.
+    ///
+    /// * `index_name` - The name of the index to alter.
+    ///
+    /// * `params` - The index properties to set.
+    pub async fn alter_index(
         &self,
+        db_name: Option<&str>,
         collection_name: &str,
-        partition_name: &str,
-        fields_data: Vec<FieldData>,
-    ) -> Result<MutationResult> {
-        let request = milvus::proto::milvus::InsertRequest {
-            base: Some(new_msg(MsgType::Insert)),
+        index_name: &str,
+        params: HashMap<String, String>,
+    ) -> Result<()> {
+        let request = milvus::proto::milvus::AlterIndexRequest {
+            base: Some(self.new_msg(MsgType::AlterIndex)),
+            db_name: self.resolve_db_name(db_name),
             collection_name: collection_name.to_string(),
-            partition_name: partition_name.to_string(),
-            num_rows: fields_data.len() as u32,
-            fields_data: fields_data
+            index_name: index_name.to_string(),
+            extra_params: params
                 .into_iter()
-                .map(|field_data| field_data.into())
+                .map(|(key, value)| KeyValuePair { key, value })
                 .collect(),
             ..Default::default()
         };
 
-        let response = self.client.clone().insert(request).await?.into_inner();
+        let status = self
+            .call(
+                "alter_index",
+                collection_name,
+                self.client.clone().alter_index(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&Some(status), "alter_index")
+    }
+
+    pub async fn insert(
+        &self,
+        collection_name: &str,
+        partition_name: &str,
+        fields_data: Vec<FieldData>,
+    ) -> Result<MutationResult> {
+        let request = self.insert_request(collection_name, partition_name, fields_data)?;
+
+        let response = self
+            .call(
+                "insert",
+                collection_name,
+                self.client.clone().insert(request),
+            )
+            .await?;
+
+        self.mutation_result(collection_name, "insert", response)
+    }
+
+    /// Convert the reply of a mutation, recording its timestamp for the `Session` consistency of the following searches and
+    /// queries.
+    fn mutation_result(
+        &self,
+        collection_name: &str,
+        op: &'static str,
+        response: milvus::proto::milvus::MutationResult,
+    ) -> Result<MutationResult> {
+        status_to_result_ctx(&response.status, op)?;
 
-        status_to_result(&response.status)?;
+        self.record_write(collection_name, response.timestamp);
 
         let res = MutationResult {
             id: response.i_ds.map(|ids| ids.into()),
@@ -739,42 +1844,367 @@ impl Client {
         Ok(res)
     }
 
-    pub async fn delete(
+    fn insert_request(
+        &self,
+        collection_name: &str,
+        partition_name: &str,
+        fields_data: Vec<FieldData>,
+    ) -> Result<milvus::proto::milvus::InsertRequest> {
+        for field_data in fields_data.iter() {
+            field_data.check_int_range()?;
+        }
+
+        Ok(milvus::proto::milvus::InsertRequest {
+            base: Some(self.new_msg(MsgType::Insert)),
+            db_name: self.db_name.clone(),
+            collection_name: collection_name.to_string(),
+            partition_name: partition_name.to_string(),
+            num_rows: fields_data
+                .first()
+                .map(|field_data| field_data.num_rows())
+                .unwrap_or_default() as u32,
+            fields_data: fields_data
+                .into_iter()
+                .map(|field_data| field_data.into())
+                .collect(),
+            ..Default::default()
+        })
+    }
+
+    /// Insert `fields_data` in chunks of at most `chunk_size` rows, sending one request per chunk.
+    ///
+    /// This keeps each request under the gRPC message size limit when inserting large batches. All the columns are split at the
+    /// same rows, so they must hold the same number of rows. The results are returned in chunk order; the chunks inserted before a
+    /// failing one are not rolled back.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to insert into.
+    ///
+    /// * `partition_name` - The name of the partition to insert into.
+    ///
+    /// * `fields_data` - The columns to insert.
+    ///
+    /// * `chunk_size` - The maximum number of rows of a request.
+    pub async fn insert_batched(
+        &self,
+        collection_name: &str,
+        partition_name: &str,
+        fields_data: Vec<FieldData>,
+        chunk_size: usize,
+    ) -> Result<Vec<MutationResult>> {
+        if chunk_size == 0 {
+            return Err(Error::InvalidParameter(
+                "chunk_size".to_owned(),
+                chunk_size.to_string(),
+            ));
+        }
+
+        let num_rows = aligned_num_rows(&fields_data)?;
+
+        let mut results = Vec::with_capacity(num_rows.div_ceil(chunk_size));
+        for start in (0..num_rows).step_by(chunk_size) {
+            let range = start..num_rows.min(start + chunk_size);
+            let chunk = fields_data
+                .iter()
+                .map(|field_data| field_data.slice_rows(range.clone()))
+                .collect();
+
+            results.push(self.insert(collection_name, partition_name, chunk).await?);
+        }
+
+        Ok(results)
+    }
+
+    /// Insert the column chunks of `stream` one request at a time, e.g. read from a large file without loading it all in
+    /// memory, see `insert_stream_with_progress`.
+    pub async fn insert_stream<S>(
+        &self,
+        collection_name: &str,
+        partition_name: &str,
+        stream: S,
+    ) -> Result<MutationResult>
+    where
+        S: Stream<Item = Vec<FieldData>>,
+    {
+        self.insert_stream_with_progress(collection_name, partition_name, stream, |_| {})
+            .await
+    }
+
+    /// Insert the column chunks of `stream` one request at a time, calling `on_progress` after each chunk.
+    ///
+    /// The results of the chunks are aggregated into one, whose row indexes count from the first row of the stream. The
+    /// chunks inserted before a failing one are not rolled back.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to insert into.
+    ///
+    /// * `partition_name` - The name of the partition to insert into.
+    ///
+    /// * `stream` - The chunks to insert, each holding the same number of rows in all its columns.
+    ///
+    /// * `on_progress` - Called with the progress so far after each inserted chunk.
+    pub async fn insert_stream_with_progress<S, F>(
+        &self,
+        collection_name: &str,
+        partition_name: &str,
+        stream: S,
+        mut on_progress: F,
+    ) -> Result<MutationResult>
+    where
+        S: Stream<Item = Vec<FieldData>>,
+        F: FnMut(InsertProgress),
+    {
+        futures_util::pin_mut!(stream);
+
+        let mut result = MutationResult::empty();
+        let mut progress = InsertProgress { chunks: 0, rows: 0 };
+        while let Some(chunk) = stream.next().await {
+            let num_rows = aligned_num_rows(&chunk)?;
+            if num_rows == 0 {
+                continue;
+            }
+
+            let chunk_result = self.insert(collection_name, partition_name, chunk).await?;
+            result.append(chunk_result, progress.rows as u32);
+
+            progress.chunks += 1;
+            progress.rows += num_rows as u64;
+            on_progress(progress);
+        }
+
+        Ok(result)
+    }
+
+    /// Insert row-oriented data.
+    ///
+    /// Each row maps field names to values. The rows are transposed into one column per field of `schema`, so every row must
+    /// provide a value for each field, except for an auto-id primary key which is generated by the server, and for the
+    /// nullable fields and the fields with a default value.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to insert into.
+    ///
+    /// * `partition_name` - The name of the partition to insert into.
+    ///
+    /// * `rows` - The rows to insert.
+    ///
+    /// * `schema` - The schema of the collection.
+    pub async fn insert_rows(
         &self,
         collection_name: &str,
         partition_name: &str,
+        rows: Vec<HashMap<String, Value>>,
+        schema: &CollectionSchema,
+    ) -> Result<MutationResult> {
+        let fields_data = rows_to_fields_data(schema, &rows)?;
+
+        self.insert(collection_name, partition_name, fields_data)
+            .await
+    }
+
+    /// Insert column-oriented data, converted to the data types declared by the collection schema.
+    ///
+    /// Each column maps a field name to its values, e.g. `ColumnData::Int` for an `Int16` field, so the data type of each
+    /// field does not have to be repeated. All the fields need a column, except for an auto-id primary key, and for the
+    /// nullable fields and the fields with a default value, which are then null or default for all the entities.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to insert into.
+    ///
+    /// * `columns` - The values to insert, by field name.
+    pub async fn insert_columns(
+        &self,
+        collection_name: &str,
+        columns: HashMap<String, ColumnData>,
+    ) -> Result<MutationResult> {
+        let schema = self
+            .describe_cached(collection_name)
+            .await?
+            .schema
+            .ok_or_else(|| {
+                Error::Unexpected(format!("no schema for collection {}", collection_name))
+            })?;
+        let fields_data = columns_to_fields_data(&schema, columns)?;
+
+        self.insert(collection_name, "", fields_data).await
+    }
+
+    /// Delete the entities matching `expr`.
+    ///
+    /// `partition_name` is the partition to delete from, all of them if `None`. An empty name returns `Error::InvalidParameter`.
+    pub async fn delete(
+        &self,
+        collection_name: &str,
+        partition_name: Option<&str>,
         expr: &str,
     ) -> Result<MutationResult> {
-        let request = milvus::proto::milvus::DeleteRequest {
-            base: Some(new_msg(MsgType::Delete)),
+        let request = self.delete_request(collection_name, partition_name, expr)?;
+
+        let response = self
+            .call(
+                "delete",
+                collection_name,
+                self.client.clone().delete(request),
+            )
+            .await?;
+
+        self.mutation_result(collection_name, "delete", response)
+    }
+
+    fn delete_request(
+        &self,
+        collection_name: &str,
+        partition_name: Option<&str>,
+        expr: &str,
+    ) -> Result<milvus::proto::milvus::DeleteRequest> {
+        Ok(milvus::proto::milvus::DeleteRequest {
+            base: Some(self.new_msg(MsgType::Delete)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
-            partition_name: partition_name.to_string(),
+            partition_name: partition_name_param(partition_name)?,
             expr: expr.to_string(),
             ..Default::default()
-        };
+        })
+    }
 
-        let response = self.client.clone().delete(request).await?.into_inner();
+    /// Delete entities by primary key.
+    ///
+    /// The `in [...]` expression is built from `ids`, quoting and escaping string keys, so they may hold any character. The name
+    /// of the primary key field is resolved by describing the collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to delete from.
+    ///
+    /// * `partition_name` - The name of the partition to delete from, all the partitions if `None`.
+    ///
+    /// * `ids` - The primary keys of the entities to delete.
+    pub async fn delete_by_ids(
+        &self,
+        collection_name: &str,
+        partition_name: Option<&str>,
+        ids: IdField,
+    ) -> Result<MutationResult> {
+        let schema = self
+            .describe_cached(collection_name)
+            .await?
+            .schema
+            .ok_or_else(|| {
+                Error::Unexpected(format!("no schema for collection {}", collection_name))
+            })?;
+        let primary_field = schema
+            .primary_field()
+            .ok_or_else(|| Error::Schema(SchemaError::NoPrimaryKey))?;
 
-        status_to_result(&response.status)?;
+        let expr = ids_expr(primary_field.name(), &ids)?;
 
-        let res = MutationResult {
-            id: response.i_ds.map(|ids| ids.into()),
-            succ_index: response.succ_index,
-            err_index: response.err_index,
-            acknowledged: response.acknowledged,
-            insert_cnt: response.insert_cnt,
-            delete_cnt: response.delete_cnt,
-            upsert_cnt: response.upsert_cnt,
-            timestamp: response.timestamp,
+        self.delete(collection_name, partition_name, &expr).await
+    }
+
+    /// Delete the entities matching `expr` and report their primary keys in the `id` of the result, e.g. for an audit log.
+    ///
+    /// The server does not return the keys of the deleted entities, so the matching keys are queried first with the `Strong`
+    /// consistency level, then deleted by key: an entity written between the two steps is neither deleted nor reported.
+    /// The keys are queried and deleted by pages of `DELETE_REPORT_BATCH_SIZE`, in primary key order, so this costs a
+    /// describe, then a query and a delete per page.
+    pub async fn delete_and_report(
+        &self,
+        collection_name: &str,
+        partition_name: Option<&str>,
+        expr: &str,
+    ) -> Result<MutationResult> {
+        partition_name_param(partition_name)?;
+        let schema = self
+            .describe_cached(collection_name)
+            .await?
+            .schema
+            .ok_or_else(|| {
+                Error::Unexpected(format!("no schema for collection {}", collection_name))
+            })?;
+        let primary_field = schema
+            .primary_field()
+            .ok_or_else(|| Error::Schema(SchemaError::NoPrimaryKey))?;
+
+        let mut result = MutationResult::empty();
+        let mut deleted = 0;
+        let mut last_pk = None;
+        loop {
+            let request = self.delete_and_report_page_request(
+                collection_name,
+                partition_name,
+                expr,
+                primary_field.name(),
+                last_pk.as_ref(),
+            )?;
+            let page = self.send_query(request).await?;
+            let ids = primary_ids(&page, primary_field.name())?;
+            if ids.is_empty() {
+                break;
+            }
+
+            let num_ids = ids.len();
+            last_pk = ids.get(num_ids - 1).map(Value::from);
+            let ids_expr = ids_expr(primary_field.name(), &ids)?;
+            let mut page_result = self
+                .delete(collection_name, partition_name, &ids_expr)
+                .await?;
+            page_result.id = Some(ids.into());
+            result.append(page_result, deleted);
+            deleted += num_ids as u32;
+
+            if (num_ids as i64) < DELETE_REPORT_BATCH_SIZE {
+                break;
+            }
+        }
+        if result.id.is_none() {
+            result.id = Some(IdField::IntId(vec![]).into());
+        }
+
+        Ok(result)
+    }
+
+    /// The query of the page of primary keys following `last_pk` of `delete_and_report`.
+    fn delete_and_report_page_request(
+        &self,
+        collection_name: &str,
+        partition_name: Option<&str>,
+        expr: &str,
+        primary_field_name: &str,
+        last_pk: Option<&Value>,
+    ) -> Result<milvus::proto::milvus::QueryRequest> {
+        let expr = match last_pk {
+            Some(pk) => resume_after_pk_expr(expr, primary_field_name, pk)?,
+            None => expr.to_string(),
         };
 
-        Ok(res)
+        let mut options = QueryOptions::new()
+            .output_fields(vec![primary_field_name])
+            .consistency_level(crate::common::ConsistencyLevel::Strong)
+            .limit(DELETE_REPORT_BATCH_SIZE);
+        if let Some(partition_name) = partition_name {
+            options = options.partition_names(vec![partition_name]);
+        }
+        let mut request = self.query_with_request(collection_name, &expr, options)?;
+        // sort the page by primary key
+        request.query_params.push(KeyValuePair {
+            key: "iterator".to_string(),
+            value: "True".to_string(),
+        });
+
+        Ok(request)
     }
 
+    /// Search with a raw `dsl` and `placeholder_group`; prefer `search_with`.
+    ///
+    /// `partition_names` are the partitions to search, all of them if `None`. An empty list returns `Error::InvalidParameter`.
     pub async fn search(
         &self,
         collection_name: &str,
-        partition_names: Vec<&str>,
+        partition_names: Option<Vec<&str>>,
         dsl: &str,
         placeholder_group: Vec<u8>,
         dsl_type: i32,
@@ -785,9 +2215,10 @@ impl Client {
         nq: i64,
     ) -> Result<SearchResult> {
         let request = milvus::proto::milvus::SearchRequest {
-            base: Some(new_msg(MsgType::Search)),
+            base: Some(self.new_msg(MsgType::Search)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
-            partition_names: partition_names.into_iter().map(|s| s.to_string()).collect(),
+            partition_names: partition_names_param(partition_names.as_deref())?,
             dsl: dsl.to_string(),
             placeholder_group,
             dsl_type,
@@ -805,13 +2236,326 @@ impl Client {
             ..Default::default()
         };
 
-        let response = self.client.clone().search(request).await?.into_inner();
-
-        status_to_result(&response.status)?;
+        self.send_search(request).await
+    }
+
+    /// Search the float vectors of the field `field_name` closest to `vectors`.
+    ///
+    /// The guarantee timestamp is derived from the consistency level of `options`; without one, the server applies the
+    /// consistency level of the collection. With the `Session` level, the search waits for the last insert or delete made
+    /// to the collection through this client or its clones, so it reads its own writes.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to search.
+    ///
+    /// * `vectors` - The query vectors, all of the dimension of the field.
+    ///
+    /// * `field_name` - The name of the vector field to search.
+    ///
+    /// * `options` - The search options.
+    pub async fn search_with(
+        &self,
+        collection_name: &str,
+        vectors: Vec<Vec<f32>>,
+        field_name: &str,
+        options: SearchOptions,
+    ) -> Result<SearchResult> {
+        let request = self.search_with_request(collection_name, vectors, field_name, options)?;
+
+        self.send_search(request).await
+    }
+
+    fn search_with_request(
+        &self,
+        collection_name: &str,
+        vectors: Vec<Vec<f32>>,
+        field_name: &str,
+        options: SearchOptions,
+    ) -> Result<milvus::proto::milvus::SearchRequest> {
+        check_metric_type(options.metric_type, false)?;
+        let placeholder_group = float_vectors_placeholder(&vectors)?;
+
+        self.vectors_search_request(
+            collection_name,
+            placeholder_group,
+            vectors.len() as i64,
+            field_name,
+            options,
+        )
+    }
+
+    /// Search the binary vectors of the field `field_name` closest to `vectors`, see `search_with`.
+    ///
+    /// The vectors are bit-packed, 8 dimensions per byte. A binary vector field is searched with the `Hamming` or the
+    /// `Jaccard` metric, any other metric of `options` returns `Error::InvalidParameter`.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to search.
+    ///
+    /// * `vectors` - The query vectors, all of the dimension of the field divided by 8 bytes.
+    ///
+    /// * `field_name` - The name of the binary vector field to search.
+    ///
+    /// * `options` - The search options.
+    pub async fn search_binary_vectors(
+        &self,
+        collection_name: &str,
+        vectors: Vec<Vec<u8>>,
+        field_name: &str,
+        options: SearchOptions,
+    ) -> Result<SearchResult> {
+        let request =
+            self.search_binary_vectors_request(collection_name, vectors, field_name, options)?;
+
+        self.send_search(request).await
+    }
+
+    fn search_binary_vectors_request(
+        &self,
+        collection_name: &str,
+        vectors: Vec<Vec<u8>>,
+        field_name: &str,
+        options: SearchOptions,
+    ) -> Result<milvus::proto::milvus::SearchRequest> {
+        check_metric_type(options.metric_type, true)?;
+        let placeholder_group = binary_vectors_placeholder(&vectors)?;
+
+        self.vectors_search_request(
+            collection_name,
+            placeholder_group,
+            vectors.len() as i64,
+            field_name,
+            options,
+        )
+    }
+
+    /// The search request of the `nq` query vectors encoded in `placeholder_group`.
+    fn vectors_search_request(
+        &self,
+        collection_name: &str,
+        placeholder_group: Vec<u8>,
+        nq: i64,
+        field_name: &str,
+        options: SearchOptions,
+    ) -> Result<milvus::proto::milvus::SearchRequest> {
+        let (consistency_level, guarantee_timestamp) = self.consistency(
+            collection_name,
+            options.consistency_level,
+            options.guarantee_timestamp,
+        );
+
+        Ok(milvus::proto::milvus::SearchRequest {
+            base: Some(self.new_msg(MsgType::Search)),
+            db_name: self.db_name.clone(),
+            collection_name: collection_name.to_string(),
+            partition_names: partition_names_param(options.partition_names.as_deref())?,
+            dsl: options.expr.clone(),
+            placeholder_group,
+            dsl_type: DslType::BoolExprV1 as i32,
+            output_fields: options.output_fields.clone(),
+            search_params: options.search_params(field_name),
+            guarantee_timestamp,
+            nq,
+            consistency_level: ConsistencyLevel::from(consistency_level) as i32,
+            use_default_consistency: options.consistency_level.is_none(),
+            ..Default::default()
+        })
+    }
+
+    /// The consistency level and guarantee timestamp of a search or query of `collection_name` at `level`.
+    ///
+    /// `ts` is the guarantee timestamp of the `Customized` level. Without a level, the server applies the one of the collection.
+    fn consistency(
+        &self,
+        collection_name: &str,
+        level: Option<crate::common::ConsistencyLevel>,
+        ts: u64,
+    ) -> (crate::common::ConsistencyLevel, u64) {
+        match level {
+            Some(level) => {
+                let ts = match level {
+                    // no write yet, 0 waits for all the writes so far
+                    crate::common::ConsistencyLevel::Session => {
+                        self.last_write_ts(collection_name).unwrap_or(0)
+                    }
+                    _ => ts,
+                };
+                (level, get_gts(level, ts))
+            }
+            // the level is ignored by the server in favor of the collection one
+            None => (crate::common::ConsistencyLevel::Bounded, 0),
+        }
+    }
+
+    /// Page through the `total` hits closest to `vector`, `batch_size` hits at a time, e.g. to retrieve thousands of candidates
+    /// without hitting the top-k limit of a single search.
+    ///
+    /// Pages are searched with an increasing offset and deduplicated by id, so a hit is returned once even if the ranking
+    /// shifts between two pages. The server caps the offset plus the page size, 16384 by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to search.
+    ///
+    /// * `vector` - The query vector.
+    ///
+    /// * `field_name` - The name of the float vector field to search.
+    ///
+    /// * `batch_size` - The maximum number of hits of a page.
+    ///
+    /// * `total` - The maximum number of hits over all the pages.
+    pub fn search_iterator(
+        &self,
+        collection_name: &str,
+        vector: Vec<f32>,
+        field_name: &str,
+        batch_size: i64,
+        total: i64,
+    ) -> Result<SearchIterator> {
+        for (name, value) in [("batch_size", batch_size), ("total", total)] {
+            if value <= 0 {
+                return Err(Error::InvalidParameter(
+                    name.to_owned(),
+                    format!("expected a positive size, got {}", value),
+                ));
+            }
+        }
+
+        Ok(SearchIterator {
+            client: self.clone(),
+            collection_name: collection_name.to_string(),
+            vector,
+            field_name: field_name.to_string(),
+            batch_size,
+            total,
+            offset: 0,
+            returned: 0,
+            seen: HashSet::new(),
+            exhausted: false,
+        })
+    }
+
+    /// Search several vector fields at once and fuse their hits with `rerank`, e.g. to search both the image and the text
+    /// embeddings of the entities.
+    ///
+    /// The searches must have the same number of query vectors, the result has one group of hits per query.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to search.
+    ///
+    /// * `reqs` - The searches, one per vector field.
+    ///
+    /// * `rerank` - How the hits of the searches are ranked together.
+    ///
+    /// * `top_k` - The number of hits per query after reranking.
+    ///
+    /// * `output_fields` - The fields to return along with the hits.
+    pub async fn hybrid_search(
+        &self,
+        collection_name: &str,
+        reqs: Vec<AnnSearchRequest>,
+        rerank: RerankStrategy,
+        top_k: i64,
+        output_fields: Vec<&str>,
+    ) -> Result<SearchResult> {
+        let request =
+            self.hybrid_search_request(collection_name, reqs, rerank, top_k, output_fields)?;
+        let nq = request.requests.first().map_or(0, |r| r.nq);
+
+        let response = self
+            .call(
+                "hybrid_search",
+                collection_name,
+                self.client.clone().hybrid_search(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&response.status, "hybrid_search")?;
+
+        Ok(SearchResult {
+            results: response.results.map(|x| x.into()),
+            collection_name: response.collection_name,
+            nq,
+        })
+    }
+
+    fn hybrid_search_request(
+        &self,
+        collection_name: &str,
+        reqs: Vec<AnnSearchRequest>,
+        rerank: RerankStrategy,
+        top_k: i64,
+        output_fields: Vec<&str>,
+    ) -> Result<milvus::proto::milvus::HybridSearchRequest> {
+        let rank_params = rerank.rank_params(reqs.len(), top_k)?;
+        let nq = match reqs.first() {
+            Some(req) => req.vectors.len(),
+            None => {
+                return Err(Error::InvalidParameter(
+                    "reqs".to_owned(),
+                    "expected at least one search".to_owned(),
+                ))
+            }
+        };
+
+        let mut requests = Vec::with_capacity(reqs.len());
+        for (i, req) in reqs.iter().enumerate() {
+            if req.vectors.len() != nq {
+                return Err(Error::InvalidParameter(
+                    format!("reqs[{}]", i),
+                    format!("{} query vectors do not match {}", req.vectors.len(), nq),
+                ));
+            }
+            check_metric_type(req.options.metric_type, false)?;
+            requests.push(milvus::proto::milvus::SearchRequest {
+                base: Some(self.new_msg(MsgType::Search)),
+                db_name: self.db_name.clone(),
+                collection_name: collection_name.to_string(),
+                dsl: req.options.expr.clone(),
+                placeholder_group: float_vectors_placeholder(&req.vectors)?,
+                dsl_type: DslType::BoolExprV1 as i32,
+                search_params: req.options.search_params(&req.anns_field),
+                nq: nq as i64,
+                ..Default::default()
+            });
+        }
+
+        Ok(milvus::proto::milvus::HybridSearchRequest {
+            base: Some(self.new_msg(MsgType::Search)),
+            db_name: self.db_name.clone(),
+            collection_name: collection_name.to_string(),
+            requests,
+            rank_params,
+            output_fields: output_fields.into_iter().map(|s| s.to_string()).collect(),
+            use_default_consistency: true,
+            ..Default::default()
+        })
+    }
+
+    async fn send_search(
+        &self,
+        request: milvus::proto::milvus::SearchRequest,
+    ) -> Result<SearchResult> {
+        let nq = request.nq;
+        let collection_name = request.collection_name.clone();
+
+        let response = self
+            .call(
+                "search",
+                &collection_name,
+                self.client.clone().search(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&response.status, "search")?;
 
         let res = SearchResult {
             results: response.results.map(|x| x.into()),
             collection_name: response.collection_name,
+            nq,
         };
 
         Ok(res)
@@ -819,7 +2563,8 @@ impl Client {
 
     pub async fn flush(&self, collection_names: Vec<&str>) -> Result<FlushResult> {
         let request = milvus::proto::milvus::FlushRequest {
-            base: Some(new_msg(MsgType::Flush)),
+            base: Some(self.new_msg(MsgType::Flush)),
+            db_name: self.db_name.clone(),
             collection_names: collection_names
                 .into_iter()
                 .map(|s| s.to_string())
@@ -827,9 +2572,11 @@ impl Client {
             ..Default::default()
         };
 
-        let response = self.client.clone().flush(request).await?.into_inner();
+        let response = self
+            .call("flush", "", self.client.clone().flush(request))
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "flush")?;
 
         let res = FlushResult {
             db_name: response.db_name,
@@ -844,27 +2591,83 @@ impl Client {
                 .map(|(key, value)| (key, value.data))
                 .collect(),
             collection_seal_times: response.coll_seal_times,
+            collection_flush_ts: response.coll_flush_ts,
         };
 
         Ok(res)
     }
 
+    /// Flush every collection of a database at once, returning the flush timestamp.
+    ///
+    /// Pass the returned timestamp to `get_flush_all_state` to poll for completion.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_name` - name of the database, the client database if `None`
+    pub async fn flush_all(&self, db_name: Option<&str>) -> Result<u64> {
+        let request = milvus::proto::milvus::FlushAllRequest {
+            base: Some(self.new_msg(MsgType::FlushAll)),
+            db_name: self.resolve_db_name(db_name),
+            ..Default::default()
+        };
+
+        let response = self
+            .call("flush_all", "", self.client.clone().flush_all(request))
+            .await?;
+
+        status_to_result_ctx(&response.status, "flush_all")?;
+
+        Ok(response.flush_all_ts)
+    }
+
+    /// Check whether a `flush_all` of the database has completed.
+    ///
+    /// # Arguments
+    ///
+    /// * `flush_ts` - the timestamp returned by `flush_all`
+    ///
+    /// * `db_name` - name of the database, the client database if `None`
+    pub async fn get_flush_all_state(&self, flush_ts: u64, db_name: Option<&str>) -> Result<bool> {
+        let request = milvus::proto::milvus::GetFlushAllStateRequest {
+            base: Some(self.new_msg(MsgType::FlushAll)),
+            flush_all_ts: flush_ts,
+            db_name: self.resolve_db_name(db_name),
+            ..Default::default()
+        };
+
+        let response = self
+            .call(
+                "get_flush_all_state",
+                "",
+                self.client.clone().get_flush_all_state(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&response.status, "get_flush_all_state")?;
+
+        Ok(response.flushed)
+    }
+
+    /// Query with raw parameters; prefer `query_with`.
+    ///
+    /// `partition_names` are the partitions to query, all of them if `None`. An empty list returns `Error::InvalidParameter`.
     pub async fn query(
         &self,
         collection_name: &str,
         expr: &str,
         output_fields: Vec<&str>,
-        partition_names: Vec<&str>,
+        partition_names: Option<Vec<&str>>,
         travel_timestamp: u64,
         guarantee_timestamp: u64,
         query_params: Option<HashMap<String, String>>,
     ) -> Result<QueryResult> {
         let request = milvus::proto::milvus::QueryRequest {
-            base: Some(new_msg(MsgType::Retrieve)),
+            base: Some(self.new_msg(MsgType::Retrieve)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             expr: expr.to_string(),
             output_fields: output_fields.into_iter().map(|s| s.to_string()).collect(),
-            partition_names: partition_names.into_iter().map(|s| s.to_string()).collect(),
+            partition_names: partition_names_param(partition_names.as_deref())?,
             travel_timestamp,
             guarantee_timestamp,
             query_params: query_params
@@ -880,9 +2683,141 @@ impl Client {
             ..Default::default()
         };
 
-        let response = self.client.clone().query(request).await?.into_inner();
+        self.send_query(request).await
+    }
+
+    /// Query the entities matching `expr`, e.g. a page of them with `QueryOptions::offset` and `QueryOptions::limit`.
+    ///
+    /// The guarantee timestamp is derived from the consistency level of `options` as for `search_with`.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to query.
+    ///
+    /// * `expr` - The boolean expression the entities must match.
+    ///
+    /// * `options` - The query options.
+    pub async fn query_with(
+        &self,
+        collection_name: &str,
+        expr: &str,
+        options: QueryOptions,
+    ) -> Result<QueryResult> {
+        let request = self.query_with_request(collection_name, expr, options)?;
+
+        self.send_query(request).await
+    }
+
+    fn query_with_request(
+        &self,
+        collection_name: &str,
+        expr: &str,
+        options: QueryOptions,
+    ) -> Result<milvus::proto::milvus::QueryRequest> {
+        let (consistency_level, guarantee_timestamp) = self.consistency(
+            collection_name,
+            options.consistency_level,
+            options.guarantee_timestamp,
+        );
+
+        Ok(milvus::proto::milvus::QueryRequest {
+            base: Some(self.new_msg(MsgType::Retrieve)),
+            db_name: self.db_name.clone(),
+            collection_name: collection_name.to_string(),
+            expr: expr.to_string(),
+            query_params: options.query_params()?,
+            partition_names: partition_names_param(options.partition_names.as_deref())?,
+            output_fields: options.output_fields,
+            guarantee_timestamp,
+            consistency_level: ConsistencyLevel::from(consistency_level) as i32,
+            use_default_consistency: options.consistency_level.is_none(),
+            ..Default::default()
+        })
+    }
+
+    /// Count the entities of a collection matching `expr`, all of them if `None`.
+    ///
+    /// The count is computed by the server on the loaded data, with the consistency level of the collection.
+    pub async fn count(&self, collection_name: &str, expr: Option<&str>) -> Result<i64> {
+        let options = QueryOptions::new().output_fields(vec![COUNT_FIELD]);
+        let result = self
+            .query_with(collection_name, expr.unwrap_or_default(), options)
+            .await?;
+
+        count_from_result(&result)
+    }
+
+    /// Page through the entities matching `expr` in primary key order, `batch_size` entities at a time, e.g. to export a
+    /// collection without holding it in memory at once.
+    ///
+    /// Each page resumes after the last primary key of the previous one, so entities inserted during the iteration with a
+    /// greater key are returned too.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to query.
+    ///
+    /// * `expr` - The boolean expression the entities must match, all the entities if empty.
+    ///
+    /// * `output_fields` - The fields to return, the primary key is always returned.
+    ///
+    /// * `batch_size` - The maximum number of entities of a page.
+    pub async fn query_iterator(
+        &self,
+        collection_name: &str,
+        expr: &str,
+        output_fields: Vec<&str>,
+        batch_size: i64,
+    ) -> Result<QueryIterator> {
+        if batch_size <= 0 {
+            return Err(Error::InvalidParameter(
+                "batch_size".to_owned(),
+                format!("expected a positive size, got {}", batch_size),
+            ));
+        }
+
+        let schema = self
+            .describe_cached(collection_name)
+            .await?
+            .schema
+            .ok_or_else(|| {
+                Error::Unexpected(format!("no schema for collection {}", collection_name))
+            })?;
+        let primary_field = schema
+            .primary_field()
+            .ok_or_else(|| Error::Schema(SchemaError::NoPrimaryKey))?
+            .clone();
+
+        let mut output_fields: Vec<String> = output_fields.iter().map(|s| s.to_string()).collect();
+        output_fields.retain(|name| name != primary_field.name());
+        output_fields.push(primary_field.name().to_string());
+
+        Ok(QueryIterator {
+            client: self.clone(),
+            collection_name: collection_name.to_string(),
+            expr: expr.to_string(),
+            output_fields,
+            batch_size,
+            primary_field,
+            last_pk: None,
+            exhausted: false,
+        })
+    }
+
+    async fn send_query(
+        &self,
+        request: milvus::proto::milvus::QueryRequest,
+    ) -> Result<QueryResult> {
+        let collection_name = request.collection_name.clone();
+        let response = self
+            .call(
+                "query",
+                &collection_name,
+                self.client.clone().query(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "query")?;
 
         let res = QueryResult {
             fields_data: response.fields_data.into_iter().map(|x| x.into()).collect(),
@@ -895,39 +2830,106 @@ impl Client {
     pub async fn get_flush_state(&self, segment_ids: Vec<i64>) -> Result<bool> {
         let request = milvus::proto::milvus::GetFlushStateRequest {
             segment_i_ds: segment_ids,
+            db_name: self.db_name.clone(),
+            ..Default::default()
         };
 
         let response = self
-            .client
-            .clone()
-            .get_flush_state(request)
-            .await?
-            .into_inner();
+            .call(
+                "get_flush_state",
+                "",
+                self.client.clone().get_flush_state(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&response.status, "get_flush_state")?;
+
+        Ok(response.flushed)
+    }
+
+    /// Check whether the data of a collection written up to a flush is persisted, without collecting its segment ids.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the flushed collection.
+    ///
+    /// * `flush_ts` - The flush timestamp of the collection, from `FlushResult::collection_flush_ts`.
+    pub async fn get_flush_state_for_collection(
+        &self,
+        collection_name: &str,
+        flush_ts: u64,
+    ) -> Result<bool> {
+        let request = self.get_flush_state_for_collection_request(collection_name, flush_ts);
+
+        let response = self
+            .call(
+                "get_flush_state",
+                collection_name,
+                self.client.clone().get_flush_state(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "get_flush_state")?;
 
         Ok(response.flushed)
     }
 
+    fn get_flush_state_for_collection_request(
+        &self,
+        collection_name: &str,
+        flush_ts: u64,
+    ) -> milvus::proto::milvus::GetFlushStateRequest {
+        milvus::proto::milvus::GetFlushStateRequest {
+            db_name: self.db_name.clone(),
+            collection_name: collection_name.to_string(),
+            flush_ts,
+            ..Default::default()
+        }
+    }
+
+    /// Wait until all the given segments are flushed.
+    ///
+    /// Duplicated segment ids are only checked once, and the polling stops as soon as the server reports them flushed.
+    ///
+    /// # Arguments
+    ///
+    /// * `segment_ids` - ids of the segments to wait for, e.g. the ones returned by `flush`
+    ///
+    /// * `timeout` - maximum time to wait before returning `Error::Timeout`
+    pub async fn wait_for_segments_flushed(
+        &self,
+        segment_ids: Vec<i64>,
+        timeout: Duration,
+    ) -> Result<()> {
+        wait_for_segments(
+            segment_ids,
+            Duration::from_millis(WAIT_LOAD_DURATION_MS),
+            timeout,
+            |segment_ids| self.get_flush_state(segment_ids),
+        )
+        .await
+    }
+
     pub async fn get_persistent_segment_info(
         &self,
-        db_name: &str,
+        db_name: Option<&str>,
         collection_name: &str,
     ) -> Result<Vec<PersistentSegmentInfo>> {
         let request = milvus::proto::milvus::GetPersistentSegmentInfoRequest {
-            base: Some(new_msg(MsgType::ShowSegments)),
-            db_name: db_name.to_string(),
+            base: Some(self.new_msg(MsgType::ShowSegments)),
+            db_name: self.resolve_db_name(db_name),
             collection_name: collection_name.to_string(),
         };
 
         let response = self
-            .client
-            .clone()
-            .get_persistent_segment_info(request)
-            .await?
-            .into_inner();
+            .call(
+                "get_persistent_segment_info",
+                collection_name,
+                self.client.clone().get_persistent_segment_info(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "get_persistent_segment_info")?;
 
         let res = response
             .infos
@@ -946,23 +2948,24 @@ impl Client {
 
     pub async fn get_query_segment_info(
         &self,
-        db_name: &str,
+        db_name: Option<&str>,
         collection_name: &str,
     ) -> Result<Vec<QuerySegmentInfo>> {
         let request = milvus::proto::milvus::GetQuerySegmentInfoRequest {
-            base: Some(new_msg(MsgType::SegmentInfo)),
-            db_name: db_name.to_string(),
+            base: Some(self.new_msg(MsgType::SegmentInfo)),
+            db_name: self.resolve_db_name(db_name),
             collection_name: collection_name.to_string(),
         };
 
         let response = self
-            .client
-            .clone()
-            .get_query_segment_info(request)
-            .await?
-            .into_inner();
+            .call(
+                "get_query_segment_info",
+                collection_name,
+                self.client.clone().get_query_segment_info(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "get_query_segment_info")?;
 
         let res = response
             .infos
@@ -990,31 +2993,49 @@ impl Client {
         with_shard_nodes: bool,
     ) -> Result<Vec<ReplicaInfo>> {
         let request = milvus::proto::milvus::GetReplicasRequest {
-            base: Some(new_msg(MsgType::GetReplicas)),
+            base: Some(self.new_msg(MsgType::GetReplicas)),
+            db_name: self.db_name.clone(),
             collection_id,
             with_shard_nodes,
+            ..Default::default()
         };
 
         let response = self
-            .client
-            .clone()
-            .get_replicas(request)
-            .await?
-            .into_inner();
+            .call(
+                "get_replicas",
+                "",
+                self.client.clone().get_replicas(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "get_replicas")?;
 
         let res = response.replicas.into_iter().map(|x| x.into()).collect();
 
         Ok(res)
     }
 
+    /// Get the replicas of a loaded collection by name, see `get_replicas`.
+    ///
+    /// The collection id is resolved with `collection_id`.
+    pub async fn get_replicas_by_name(
+        &self,
+        collection_name: &str,
+        with_shard_nodes: bool,
+    ) -> Result<Vec<ReplicaInfo>> {
+        let collection_id = self.collection_id(collection_name).await?;
+
+        self.get_replicas(collection_id, with_shard_nodes).await
+    }
+
     pub async fn dummy(&self, request_type: &str) -> Result<String> {
         let request = milvus::proto::milvus::DummyRequest {
             request_type: request_type.to_string(),
         };
 
-        let response = self.client.clone().dummy(request).await?.into_inner();
+        let response = self
+            .call("dummy", "", self.client.clone().dummy(request))
+            .await?;
 
         Ok(response.response)
     }
@@ -1023,27 +3044,30 @@ impl Client {
         let request = milvus::proto::milvus::RegisterLinkRequest {};
 
         let response = self
-            .client
-            .clone()
-            .register_link(request)
-            .await?
-            .into_inner();
+            .call(
+                "register_link",
+                "",
+                self.client.clone().register_link(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "register_link")?;
 
         Ok(response.address.unwrap_or_default().into())
     }
 
-    /// `request` is of jsonic format
+    /// `request` is of jsonic format, e.g. `MetricsRequest::system_info().into()`.
     pub async fn get_metrics(&self, request: String) -> Result<Metrics> {
         let request = milvus::proto::milvus::GetMetricsRequest {
             request,
             ..Default::default()
         };
 
-        let response = self.client.clone().get_metrics(request).await?.into_inner();
+        let response = self
+            .call("get_metrics", "", self.client.clone().get_metrics(request))
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "get_metrics")?;
 
         Ok(Metrics {
             response: response.response,
@@ -1055,13 +3079,14 @@ impl Client {
         let request = milvus::proto::milvus::GetComponentStatesRequest {};
 
         let response = self
-            .client
-            .clone()
-            .get_component_states(request)
-            .await?
-            .into_inner();
+            .call(
+                "get_component_states",
+                "",
+                self.client.clone().get_component_states(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "get_component_states")?;
 
         let res = ComponentState {
             state: response.state.map(|x| x.into()),
@@ -1075,6 +3100,16 @@ impl Client {
         Ok(res)
     }
 
+    /// Get the version of the server along with the build details it reports, e.g. its git commit and deploy mode.
+    ///
+    /// The details are the `extra_info` of the state of the server, the version is under the `version` key.
+    pub async fn get_build_info(&self) -> Result<HashMap<String, String>> {
+        let version = self.get_version().await?;
+        let states = self.get_component_states().await?;
+
+        Ok(build_info(version, states))
+    }
+
     pub async fn load_balance(
         &self,
         collection_name: &str,
@@ -1083,36 +3118,205 @@ impl Client {
         sealed_segment_ids: Vec<i64>,
     ) -> Result<()> {
         let request = milvus::proto::milvus::LoadBalanceRequest {
-            base: Some(new_msg(MsgType::LoadBalanceSegments)),
+            base: Some(self.new_msg(MsgType::LoadBalanceSegments)),
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             src_node_id,
             dst_node_i_ds: dst_node_ids,
             sealed_segment_i_ds: sealed_segment_ids,
+            ..Default::default()
         };
 
         let response = self
-            .client
-            .clone()
-            .load_balance(request)
-            .await?
-            .into_inner();
+            .call(
+                "load_balance",
+                collection_name,
+                self.client.clone().load_balance(request),
+            )
+            .await?;
 
-        status_to_result(&Some(response))?;
+        status_to_result_ctx(&Some(response), "load_balance")?;
 
         Ok(())
     }
 
+    /// Create a resource group, to which query nodes and replicas can then be transferred.
+    pub async fn create_resource_group(&self, name: &str) -> Result<()> {
+        let request = milvus::proto::milvus::CreateResourceGroupRequest {
+            base: Some(self.new_msg(MsgType::CreateResourceGroup)),
+            resource_group: name.to_string(),
+            ..Default::default()
+        };
+
+        let status = self
+            .call(
+                "create_resource_group",
+                "",
+                self.client.clone().create_resource_group(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&Some(status), "create_resource_group")
+    }
+
+    /// Drop a resource group. The group must hold no query node anymore.
+    pub async fn drop_resource_group(&self, name: &str) -> Result<()> {
+        let request = milvus::proto::milvus::DropResourceGroupRequest {
+            base: Some(self.new_msg(MsgType::DropResourceGroup)),
+            resource_group: name.to_string(),
+            ..Default::default()
+        };
+
+        let status = self
+            .call(
+                "drop_resource_group",
+                "",
+                self.client.clone().drop_resource_group(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&Some(status), "drop_resource_group")
+    }
+
+    /// List the names of all the resource groups, including the default one.
+    pub async fn list_resource_groups(&self) -> Result<Vec<String>> {
+        let request = milvus::proto::milvus::ListResourceGroupsRequest {
+            base: Some(self.new_msg(MsgType::ListResourceGroups)),
+            ..Default::default()
+        };
+
+        let response = self
+            .call(
+                "list_resource_groups",
+                "",
+                self.client.clone().list_resource_groups(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&response.status, "list_resource_groups")?;
+
+        Ok(response.resource_groups)
+    }
+
+    /// Get the capacity, nodes and loaded replicas of a resource group.
+    pub async fn describe_resource_group(&self, name: &str) -> Result<ResourceGroupInfo> {
+        let request = milvus::proto::milvus::DescribeResourceGroupRequest {
+            base: Some(self.new_msg(MsgType::DescribeResourceGroup)),
+            resource_group: name.to_string(),
+            ..Default::default()
+        };
+
+        let response = self
+            .call(
+                "describe_resource_group",
+                "",
+                self.client.clone().describe_resource_group(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&response.status, "describe_resource_group")?;
+
+        response
+            .resource_group
+            .map(|group| group.into())
+            .ok_or_else(|| Error::Unexpected(format!("no resource group {} in response", name)))
+    }
+
+    /// Move query nodes from a resource group to another.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_group` - The name of the resource group to take the nodes from.
+    ///
+    /// * `target_group` - The name of the resource group to move the nodes to.
+    ///
+    /// * `num_nodes` - The number of nodes to move.
+    ///
+    /// Returns `Error::InvalidParameter` without moving anything if the source group has fewer available nodes.
+    pub async fn transfer_node(
+        &self,
+        source_group: &str,
+        target_group: &str,
+        num_nodes: i32,
+    ) -> Result<()> {
+        let source = self.describe_resource_group(source_group).await?;
+        check_transfer_node(&source, num_nodes)?;
+
+        let request = milvus::proto::milvus::TransferNodeRequest {
+            base: Some(self.new_msg(MsgType::TransferNode)),
+            source_resource_group: source_group.to_string(),
+            target_resource_group: target_group.to_string(),
+            num_node: num_nodes,
+            ..Default::default()
+        };
+
+        let status = self
+            .call(
+                "transfer_node",
+                "",
+                self.client.clone().transfer_node(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&Some(status), "transfer_node")
+    }
+
+    /// Move replicas of a loaded collection from a resource group to another, e.g. to pin them to dedicated nodes.
+    ///
+    /// # Arguments
+    ///
+    /// * `source_group` - The name of the resource group to take the replicas from.
+    ///
+    /// * `target_group` - The name of the resource group to move the replicas to.
+    ///
+    /// * `collection_name` - The name of the collection whose replicas are moved.
+    ///
+    /// * `num_replicas` - The number of replicas to move.
+    ///
+    /// Returns `Error::InvalidParameter` without moving anything if the source group holds fewer replicas of the collection.
+    pub async fn transfer_replica(
+        &self,
+        source_group: &str,
+        target_group: &str,
+        collection_name: &str,
+        num_replicas: i64,
+    ) -> Result<()> {
+        let source = self.describe_resource_group(source_group).await?;
+        check_transfer_replica(&source, collection_name, num_replicas)?;
+
+        let request = milvus::proto::milvus::TransferReplicaRequest {
+            base: Some(self.new_msg(MsgType::TransferReplica)),
+            source_resource_group: source_group.to_string(),
+            target_resource_group: target_group.to_string(),
+            collection_name: collection_name.to_string(),
+            num_replica: num_replicas,
+            db_name: self.db_name.clone(),
+            ..Default::default()
+        };
+
+        let status = self
+            .call(
+                "transfer_replica",
+                collection_name,
+                self.client.clone().transfer_replica(request),
+            )
+            .await?;
+
+        status_to_result_ctx(&Some(status), "transfer_replica")
+    }
+
     pub async fn get_compaction_state(&self, compaction_id: i64) -> Result<CompactionStateResult> {
         let request = milvus::proto::milvus::GetCompactionStateRequest { compaction_id };
 
         let response = self
-            .client
-            .clone()
-            .get_compaction_state(request)
-            .await?
-            .into_inner();
+            .call(
+                "get_compaction_state",
+                "",
+                self.client.clone().get_compaction_state(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "get_compaction_state")?;
 
         let res = CompactionStateResult {
             state: CompactionState::from_i32(response.state).unwrap(),
@@ -1125,24 +3329,63 @@ impl Client {
         Ok(res)
     }
 
+    /// Wait until the compaction `compaction_id` has completed, returning its final state.
+    ///
+    /// # Arguments
+    ///
+    /// * `compaction_id` - id of the compaction, as returned by `manual_compaction`
+    ///
+    /// * `poll_interval` - time to sleep between two state checks, `WAIT_LOAD_DURATION_MS` if `None`
+    ///
+    /// * `timeout` - maximum time to wait before returning `Error::Timeout`
+    pub async fn wait_for_compaction(
+        &self,
+        compaction_id: i64,
+        poll_interval: Option<Duration>,
+        timeout: Duration,
+    ) -> Result<CompactionStateResult> {
+        poll_until(
+            "wait_for_compaction",
+            poll_interval.unwrap_or(Duration::from_millis(WAIT_LOAD_DURATION_MS)),
+            timeout,
+            || async move {
+                let state = self.get_compaction_state(compaction_id).await?;
+                Ok((state.state == CompactionState::Completed).then_some(state))
+            },
+        )
+        .await
+    }
+
     pub async fn manual_compaction(&self, collection_id: i64, time_travel: u64) -> Result<i64> {
         let request = milvus::proto::milvus::ManualCompactionRequest {
             collection_id,
             timetravel: time_travel,
+            db_name: self.db_name.clone(),
+            ..Default::default()
         };
 
         let response = self
-            .client
-            .clone()
-            .manual_compaction(request)
-            .await?
-            .into_inner();
+            .call(
+                "manual_compaction",
+                "",
+                self.client.clone().manual_compaction(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "manual_compaction")?;
 
         Ok(response.compaction_id)
     }
 
+    /// Trigger the compaction of a collection by name, returning the compaction id to pass to `wait_for_compaction`.
+    ///
+    /// The collection id is resolved with `collection_id`.
+    pub async fn compact(&self, collection_name: &str) -> Result<i64> {
+        let collection_id = self.collection_id(collection_name).await?;
+
+        self.manual_compaction(collection_id, 0).await
+    }
+
     pub async fn get_compaction_state_with_plans(
         &self,
         compaction_id: i64,
@@ -1150,13 +3393,14 @@ impl Client {
         let request = milvus::proto::milvus::GetCompactionPlansRequest { compaction_id };
 
         let response = self
-            .client
-            .clone()
-            .get_compaction_state_with_plans(request)
-            .await?
-            .into_inner();
+            .call(
+                "get_compaction_state_with_plans",
+                "",
+                self.client.clone().get_compaction_state_with_plans(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "get_compaction_state_with_plans")?;
 
         let res = CompactionPlan {
             state: CompactionState::from_i32(response.state).unwrap(),
@@ -1173,6 +3417,24 @@ impl Client {
         Ok(res)
     }
 
+    /// Import files of the object storage of the server into a collection, returning the tasks with the files each imports.
+    ///
+    /// Row-based imports read one `.json` file per task, column-based ones read all their `.npy` files, one per field, in a
+    /// single task. Backups are directories of insert logs, whatever `row_based`. Poll the tasks with `get_import_state`.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - The name of the collection to import into.
+    ///
+    /// * `partition_name` - The name of the partition to import into, the default one if empty.
+    ///
+    /// * `channel_names` - The channels of the collection to import into, all of them if empty.
+    ///
+    /// * `row_based` - Whether the files are JSON rows rather than NumPy columns.
+    ///
+    /// * `files` - The paths of the files in the bucket.
+    ///
+    /// * `options` - The import options.
     pub async fn import(
         &self,
         collection_name: &str,
@@ -1180,38 +3442,46 @@ impl Client {
         channel_names: Vec<&str>,
         row_based: bool,
         files: Vec<&str>,
-        options: HashMap<String, String>,
-    ) -> Result<Vec<i64>> {
+        options: ImportOptions,
+    ) -> Result<Vec<ImportTask>> {
+        check_import_files(&files, row_based, options.backup)?;
+
         let request = milvus::proto::milvus::ImportRequest {
+            db_name: self.db_name.clone(),
             collection_name: collection_name.to_string(),
             partition_name: partition_name.to_string(),
             channel_names: channel_names.iter().map(|x| x.to_string()).collect(),
             row_based,
             files: files.iter().map(|x| x.to_string()).collect(),
-            options: options
-                .into_iter()
-                .map(|(key, value)| KeyValuePair { key, value })
-                .collect(),
+            options: options.import_options(),
+            ..Default::default()
         };
 
-        let response = self.client.clone().import(request).await?.into_inner();
+        let response = self
+            .call(
+                "import",
+                collection_name,
+                self.client.clone().import(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "import")?;
 
-        Ok(response.tasks)
+        Ok(import_tasks(response.tasks, &files, row_based))
     }
 
     pub async fn get_import_state(&self, task: i64) -> Result<ImportStateResult> {
         let request = milvus::proto::milvus::GetImportStateRequest { task };
 
         let response = self
-            .client
-            .clone()
-            .get_import_state(request)
-            .await?
-            .into_inner();
+            .call(
+                "get_import_state",
+                "",
+                self.client.clone().get_import_state(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "get_import_state")?;
 
         let res = ImportStateResult {
             state: ImportState::from_i32(response.state).unwrap(),
@@ -1239,16 +3509,18 @@ impl Client {
         let request = milvus::proto::milvus::ListImportTasksRequest {
             collection_name: collection_name.to_string(),
             limit,
+            db_name: self.db_name.clone(),
         };
 
         let response = self
-            .client
-            .clone()
-            .list_import_tasks(request)
-            .await?
-            .into_inner();
+            .call(
+                "list_import_tasks",
+                collection_name,
+                self.client.clone().list_import_tasks(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "list_import_tasks")?;
 
         let res = response.tasks.into_iter().map(|task| task.into()).collect();
 
@@ -1263,7 +3535,7 @@ impl Client {
         modified_utc_timestamps: u64,
     ) -> Result<()> {
         let request = milvus::proto::milvus::CreateCredentialRequest {
-            base: Some(new_msg(MsgType::CreateCredential)),
+            base: Some(self.new_msg(MsgType::CreateCredential)),
             username: username.to_string(),
             password: password.to_string(),
             created_utc_timestamps,
@@ -1271,13 +3543,14 @@ impl Client {
         };
 
         let status = self
-            .client
-            .clone()
-            .create_credential(request)
-            .await?
-            .into_inner();
+            .call(
+                "create_credential",
+                "",
+                self.client.clone().create_credential(request),
+            )
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "create_credential")
     }
 
     pub async fn update_credential(
@@ -1289,7 +3562,7 @@ impl Client {
         modified_utc_timestamps: u64,
     ) -> Result<()> {
         let request = milvus::proto::milvus::UpdateCredentialRequest {
-            base: Some(new_msg(MsgType::UpdateCredential)),
+            base: Some(self.new_msg(MsgType::UpdateCredential)),
             username: username.to_string(),
             old_password: old_password.to_string(),
             new_password: new_password.to_string(),
@@ -1298,68 +3571,75 @@ impl Client {
         };
 
         let status = self
-            .client
-            .clone()
-            .update_credential(request)
-            .await?
-            .into_inner();
+            .call(
+                "update_credential",
+                "",
+                self.client.clone().update_credential(request),
+            )
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "update_credential")
     }
 
     pub async fn delete_credential(&self, username: &str) -> Result<()> {
         let request = milvus::proto::milvus::DeleteCredentialRequest {
-            base: Some(new_msg(MsgType::DeleteCredential)),
+            base: Some(self.new_msg(MsgType::DeleteCredential)),
             username: username.to_string(),
         };
 
         let status = self
-            .client
-            .clone()
-            .delete_credential(request)
-            .await?
-            .into_inner();
+            .call(
+                "delete_credential",
+                "",
+                self.client.clone().delete_credential(request),
+            )
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "delete_credential")
     }
 
     pub async fn list_credential_usernames(&self) -> Result<Vec<String>> {
         let request = milvus::proto::milvus::ListCredUsersRequest {
-            base: Some(new_msg(MsgType::ListCredUsernames)),
+            base: Some(self.new_msg(MsgType::ListCredUsernames)),
         };
 
         let response = self
-            .client
-            .clone()
-            .list_cred_users(request)
-            .await?
-            .into_inner();
+            .call(
+                "list_cred_users",
+                "",
+                self.client.clone().list_cred_users(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "list_credential_usernames")?;
 
         Ok(response.usernames)
     }
 
     pub async fn create_role(&self, role: Option<RoleEntity>) -> Result<()> {
         let request = milvus::proto::milvus::CreateRoleRequest {
-            base: Some(new_msg(MsgType::CreateRole)),
+            base: Some(self.new_msg(MsgType::CreateRole)),
             entity: role.map(|x| x.into()),
         };
 
-        let status = self.client.clone().create_role(request).await?.into_inner();
+        let status = self
+            .call("create_role", "", self.client.clone().create_role(request))
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "create_role")
     }
 
     pub async fn drop_role(&self, role_name: &str) -> Result<()> {
         let request = milvus::proto::milvus::DropRoleRequest {
-            base: Some(new_msg(MsgType::DropRole)),
+            base: Some(self.new_msg(MsgType::DropRole)),
             role_name: role_name.to_string(),
         };
 
-        let status = self.client.clone().drop_role(request).await?.into_inner();
+        let status = self
+            .call("drop_role", "", self.client.clone().drop_role(request))
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "drop_role")
     }
 
     pub async fn operate_user_role(
@@ -1369,20 +3649,21 @@ impl Client {
         ty: OperateUserRoleType,
     ) -> Result<()> {
         let request = milvus::proto::milvus::OperateUserRoleRequest {
-            base: Some(new_msg(MsgType::OperateUserRole)),
+            base: Some(self.new_msg(MsgType::OperateUserRole)),
             username: username.to_string(),
             role_name: role_name.to_string(),
             r#type: ty as i32,
         };
 
         let status = self
-            .client
-            .clone()
-            .operate_user_role(request)
-            .await?
-            .into_inner();
+            .call(
+                "operate_user_role",
+                "",
+                self.client.clone().operate_user_role(request),
+            )
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "operate_user_role")
     }
 
     pub async fn select_role(
@@ -1391,14 +3672,16 @@ impl Client {
         include_user_info: bool,
     ) -> Result<Vec<RoleResult>> {
         let request = milvus::proto::milvus::SelectRoleRequest {
-            base: Some(new_msg(MsgType::SelectRole)),
+            base: Some(self.new_msg(MsgType::SelectRole)),
             role: role.map(|role| role.into()),
             include_user_info,
         };
 
-        let response = self.client.clone().select_role(request).await?.into_inner();
+        let response = self
+            .call("select_role", "", self.client.clone().select_role(request))
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "select_role")?;
 
         let res = response
             .results
@@ -1418,14 +3701,16 @@ impl Client {
         include_role_info: bool,
     ) -> Result<Vec<User>> {
         let request = milvus::proto::milvus::SelectUserRequest {
-            base: Some(new_msg(MsgType::SelectUser)),
+            base: Some(self.new_msg(MsgType::SelectUser)),
             user: user.map(|user| user.into()),
             include_role_info,
         };
 
-        let response = self.client.clone().select_user(request).await?.into_inner();
+        let response = self
+            .call("select_user", "", self.client.clone().select_user(request))
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "select_user")?;
 
         let res = response
             .results
@@ -1445,49 +3730,47 @@ impl Client {
         ty: OperatePrivilegeType,
     ) -> Result<()> {
         let request = milvus::proto::milvus::OperatePrivilegeRequest {
-            base: Some(new_msg(MsgType::OperatePrivilege)),
+            base: Some(self.new_msg(MsgType::OperatePrivilege)),
             entity: Some(entity.into()),
             r#type: ty as i32,
         };
 
         let status = self
-            .client
-            .clone()
-            .operate_privilege(request)
-            .await?
-            .into_inner();
+            .call(
+                "operate_privilege",
+                "",
+                self.client.clone().operate_privilege(request),
+            )
+            .await?;
 
-        status_to_result(&Some(status))
+        status_to_result_ctx(&Some(status), "operate_privilege")
     }
 
     pub async fn select_grant(&self, object_name: &str) -> Result<Vec<GrantEntity>> {
         let entity = GrantEntity {
             object_name: object_name.to_string(),
+            db_name: self.db_name.clone(),
             ..Default::default()
         };
         let request = milvus::proto::milvus::SelectGrantRequest {
-            base: Some(new_msg(MsgType::SelectGrant)),
+            base: Some(self.new_msg(MsgType::SelectGrant)),
             entity: Some(entity.into()),
         };
 
         let response = self
-            .client
-            .clone()
-            .select_grant(request)
-            .await?
-            .into_inner();
+            .call(
+                "select_grant",
+                "",
+                self.client.clone().select_grant(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "select_grant")?;
 
         let res = response
             .entities
             .into_iter()
-            .map(|grant| GrantEntity {
-                role: grant.role.map(|x| x.into()),
-                object: grant.object.map(|x| x.into()),
-                object_name: grant.object_name,
-                grantor: grant.grantor.map(|x| x.into()),
-            })
+            .map(|grant| grant.into())
             .collect();
 
         Ok(res)
@@ -1496,9 +3779,11 @@ impl Client {
     pub async fn get_version(&self) -> Result<String> {
         let request = milvus::proto::milvus::GetVersionRequest {};
 
-        let response = self.client.clone().get_version(request).await?.into_inner();
+        let response = self
+            .call("get_version", "", self.client.clone().get_version(request))
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "get_version")?;
 
         Ok(response.version)
     }
@@ -1507,47 +3792,3809 @@ impl Client {
         let request = milvus::proto::milvus::CheckHealthRequest {};
 
         let response = self
-            .client
-            .clone()
-            .check_health(request)
-            .await?
-            .into_inner();
+            .call(
+                "check_health",
+                "",
+                self.client.clone().check_health(request),
+            )
+            .await?;
 
-        status_to_result(&response.status)?;
+        status_to_result_ctx(&response.status, "check_health")?;
 
-        Ok(Health {
-            is_healthy: response.is_healthy,
-            reasons: response.reasons,
-        })
+        Ok(response.into())
     }
 }
 
+/// A builder of `Client`, created by `Client::builder`.
+///
+/// Every setting is optional: by default the client connects anonymously to the server default database, with the
+/// defaults of `ConnectOptions` and a `DEFAULT_MAX_MESSAGE_SIZE` message size limit.
 #[derive(Clone)]
-pub struct AuthInterceptor {
-    token: Option<String>,
+pub struct ClientBuilder {
+    host: String,
+    port: u16,
+    username: Option<String>,
+    password: Option<String>,
+    api_key: Option<String>,
+    options: ConnectOptions,
+    db_name: String,
+    source_id: i64,
+    max_message_size: usize,
+    describe_cache_ttl: Duration,
+    metrics: Option<Arc<dyn MetricsRecorder>>,
+    handshake: bool,
 }
-
-impl Interceptor for AuthInterceptor {
-    fn call(
-        &mut self,
-        mut req: Request<()>,
-    ) -> std::result::Result<tonic::Request<()>, tonic::Status> {
-        if let Some(ref token) = self.token {
-            let header_value = format!("{}", token);
-            req.metadata_mut()
-                .insert("authorization", header_value.parse().unwrap());
+impl std::fmt::Debug for ClientBuilder {
+    // the password and the API key are redacted
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let redacted = |secret: &Option<String>| secret.as_ref().map(|_| "<redacted>");
+        f.debug_struct("ClientBuilder")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.username)
+            .field("password", &redacted(&self.password))
+            .field("api_key", &redacted(&self.api_key))
+            .field("options", &self.options)
+            .field("db_name", &self.db_name)
+            .field("source_id", &self.source_id)
+            .field("max_message_size", &self.max_message_size)
+            .field("describe_cache_ttl", &self.describe_cache_ttl)
+            .field("metrics", &self.metrics)
+            .field("handshake", &self.handshake)
+            .finish()
+    }
+}
+impl ClientBuilder {
+    fn new(host: &str, port: u16) -> Self {
+        Self {
+            host: host.to_string(),
+            port,
+            username: None,
+            password: None,
+            api_key: None,
+            options: ConnectOptions::new(),
+            db_name: String::new(),
+            source_id: 0,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            describe_cache_ttl: Duration::ZERO,
+            metrics: None,
+            handshake: false,
         }
+    }
 
-        Ok(req)
+    /// Authenticate with a username and a password, see `Client::new`.
+    pub fn credentials(mut self, username: &str, password: &str) -> Self {
+        self.username = Some(username.to_string());
+        self.password = Some(password.to_string());
+        self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Authenticate with an API key, see `Client::connect_with_api_key`. Exclusive with `credentials`.
+    pub fn api_key(mut self, api_key: &str) -> Self {
+        self.api_key = Some(api_key.to_string());
+        self
+    }
 
-    fn get_vdb_host_address() -> String {
-        std::env::var("VDB_HOST").expect("VDB_HOST is not set")
+    /// The timeout of each request, 10 seconds by default; a shortcut for the `ConnectOptions::timeout` setting.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options = self.options.timeout(timeout);
+        self
+    }
+
+    /// The connection settings, replacing the timeout set so far.
+    pub fn connect_options(mut self, options: ConnectOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// See `Client::with_database`.
+    pub fn database(mut self, db_name: &str) -> Self {
+        self.db_name = db_name.to_string();
+        self
+    }
+
+    /// See `Client::with_source_id`.
+    pub fn source_id(mut self, source_id: i64) -> Self {
+        self.source_id = source_id;
+        self
+    }
+
+    /// The limit of the size of a gRPC message sent or received, in bytes.
+    pub fn max_message_size(mut self, size: usize) -> Self {
+        self.max_message_size = size;
+        self
+    }
+
+    /// See `Client::with_describe_cache_ttl`.
+    pub fn describe_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.describe_cache_ttl = ttl;
+        self
+    }
+
+    /// See `Client::with_metrics`.
+    pub fn metrics(mut self, recorder: impl MetricsRecorder + 'static) -> Self {
+        self.metrics = Some(Arc::new(recorder));
+        self
+    }
+
+    /// Run `Client::handshake` once connected, so `build` fails early on an unreachable or rejecting server and the
+    /// negotiated `ServerInfo` is available from `Client::server_info`. Disabled by default.
+    pub fn handshake(mut self, enabled: bool) -> Self {
+        self.handshake = enabled;
+        self
+    }
+
+    /// Connect to the server, and negotiate a session if `handshake` is enabled.
+    pub async fn build(self) -> Result<Client> {
+        let token = self.token()?;
+
+        let url = format!("{}:{}", self.host, self.port);
+        let dst: tonic::transport::Endpoint = url.try_into().map_err(|err| {
+            Error::InvalidParameter("url".to_owned(), format!("to parse {:?}", err))
+        })?;
+        let dst = self.options.apply(dst);
+
+        let auth_interceptor = AuthInterceptor {
+            token: Arc::new(RwLock::new(token)),
+            identifier: Arc::new(RwLock::new(None)),
+        };
+
+        let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+
+        let handshake = self.handshake;
+        let client = self.configure(Client::with_channel(conn, auth_interceptor));
+        if handshake {
+            client.handshake().await?;
+        }
+
+        Ok(client)
+    }
+
+    /// The `authorization` token of the credentials or the API key.
+    fn token(&self) -> Result<Option<String>> {
+        let token = auth_token(self.username.clone(), self.password.clone())?;
+        match (&self.api_key, token) {
+            (None, token) => Ok(token),
+            (Some(api_key), None) if !api_key.is_empty() => {
+                Ok(Some(general_purpose::STANDARD.encode(api_key)))
+            }
+            (Some(_), None) => Err(Error::InvalidParameter(
+                "api_key".to_owned(),
+                "expected a non-empty key".to_owned(),
+            )),
+            (Some(_), Some(_)) => Err(Error::InvalidParameter(
+                "api_key".to_owned(),
+                "given along with a username and a password".to_owned(),
+            )),
+        }
+    }
+
+    /// Apply the settings not needed to connect to `client`.
+    fn configure(self, mut client: Client) -> Client {
+        client.client = client
+            .client
+            .max_decoding_message_size(self.max_message_size)
+            .max_encoding_message_size(self.max_message_size);
+        client.metrics = self.metrics;
+
+        client
+            .with_database(&self.db_name)
+            .with_source_id(self.source_id)
+            .with_describe_cache_ttl(self.describe_cache_ttl)
+    }
+}
+
+/// A sink of the metrics of the RPCs of `Client`, e.g. an adapter to a Prometheus registry.
+pub trait MetricsRecorder: std::fmt::Debug + Send + Sync {
+    /// Record a completed RPC: its name, e.g. `search`, its latency and its outcome.
+    fn record_rpc(&self, method: &'static str, latency: Duration, outcome: RpcOutcome);
+}
+
+/// The outcome of an RPC, as recorded by a `MetricsRecorder`.
+///
+/// The server reports most failures, e.g. a missing collection or a rate limit, in the status of a successful gRPC reply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RpcOutcome {
+    /// The server replied with a success status
+    Ok,
+    /// The RPC failed with this gRPC code, e.g. `Unavailable` if the server could not be reached
+    Grpc(tonic::Code),
+    /// The server replied with a failure status: its legacy error code and its code
+    Server(ErrorCode, i32),
+}
+impl RpcOutcome {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, RpcOutcome::Ok)
+    }
+
+    /// The outcome of a reply with `status`, `Ok` for a reply without status.
+    fn of_status(status: Option<&milvus::proto::common::Status>) -> Self {
+        match status {
+            Some(status) if status.error_code != ErrorCode::Success as i32 || status.code != 0 => {
+                RpcOutcome::Server(
+                    ErrorCode::from_i32(status.error_code).unwrap_or(ErrorCode::UnexpectedError),
+                    status.code,
+                )
+            }
+            _ => RpcOutcome::Ok,
+        }
+    }
+}
+
+/// The responses of the RPCs, to check their status in `Client::call`.
+trait RpcStatus {
+    fn rpc_status(&self) -> Option<&milvus::proto::common::Status>;
+}
+impl RpcStatus for milvus::proto::common::Status {
+    fn rpc_status(&self) -> Option<&milvus::proto::common::Status> {
+        Some(self)
+    }
+}
+impl RpcStatus for milvus::proto::milvus::DummyResponse {
+    fn rpc_status(&self) -> Option<&milvus::proto::common::Status> {
+        None
+    }
+}
+macro_rules! impl_rpc_status {
+    ($($response:ident),* $(,)?) => {
+        $(
+            impl RpcStatus for milvus::proto::milvus::$response {
+                fn rpc_status(&self) -> Option<&milvus::proto::common::Status> {
+                    self.status.as_ref()
+                }
+            }
+        )*
+    };
+}
+impl_rpc_status!(
+    BoolResponse,
+    CheckHealthResponse,
+    ComponentStates,
+    ConnectResponse,
+    DescribeAliasResponse,
+    DescribeCollectionResponse,
+    DescribeIndexResponse,
+    DescribeResourceGroupResponse,
+    FlushAllResponse,
+    FlushResponse,
+    GetCollectionStatisticsResponse,
+    GetCompactionPlansResponse,
+    GetCompactionStateResponse,
+    GetFlushAllStateResponse,
+    GetFlushStateResponse,
+    GetImportStateResponse,
+    GetIndexBuildProgressResponse,
+    GetIndexStateResponse,
+    GetIndexStatisticsResponse,
+    GetLoadStateResponse,
+    GetLoadingProgressResponse,
+    GetMetricsResponse,
+    GetPartitionStatisticsResponse,
+    GetPersistentSegmentInfoResponse,
+    GetQuerySegmentInfoResponse,
+    GetReplicasResponse,
+    GetVersionResponse,
+    ImportResponse,
+    ListAliasesResponse,
+    ListCredUsersResponse,
+    ListImportTasksResponse,
+    ListResourceGroupsResponse,
+    ManualCompactionResponse,
+    MutationResult,
+    QueryResults,
+    RegisterLinkResponse,
+    SearchResults,
+    SelectGrantResponse,
+    SelectRoleResponse,
+    SelectUserResponse,
+    ShowCollectionsResponse,
+    ShowPartitionsResponse,
+);
+
+/// Keeps the request id the server attached to the last response.
+#[derive(Debug, Default)]
+struct RequestIdTracker {
+    enabled: AtomicBool,
+    last: Mutex<Option<String>>,
+}
+impl RequestIdTracker {
+    const KEYS: [&'static str; 2] = ["request-id", "trace-id"];
+
+    fn observe(&self, metadata: &tonic::metadata::MetadataMap) {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let request_id = Self::KEYS
+            .iter()
+            .find_map(|key| metadata.get(*key))
+            .and_then(|value| value.to_str().ok());
+        // a reply without id clears the one of the previous RPC
+        *self.last.lock().unwrap() = request_id.map(str::to_string);
+    }
+}
+
+/// Collection ids by database and collection name.
+#[derive(Debug, Default)]
+struct CollectionIdCache {
+    ids: RwLock<HashMap<(String, String), i64>>,
+}
+impl CollectionIdCache {
+    /// Return the cached id of a collection, or fetch and cache it.
+    async fn get_or_fetch<F, Fut>(&self, db_name: &str, name: &str, fetch: F) -> Result<i64>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<i64>>,
+    {
+        let key = (db_name.to_string(), name.to_string());
+        let cached = self.ids.read().unwrap().get(&key).copied();
+        if let Some(id) = cached {
+            return Ok(id);
+        }
+
+        let id = fetch().await?;
+        self.ids.write().unwrap().insert(key, id);
+
+        Ok(id)
+    }
+
+    fn invalidate(&self, db_name: &str, name: &str) {
+        self.ids
+            .write()
+            .unwrap()
+            .remove(&(db_name.to_string(), name.to_string()));
+    }
+}
+
+/// Collection descriptions by database and collection name, with the time they were described.
+#[derive(Debug)]
+struct DescribeCache {
+    /// Caching is disabled if zero
+    ttl: Duration,
+    entries: RwLock<HashMap<(String, String), (Instant, CollectionMetadata)>>,
+}
+impl DescribeCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Return the description of a collection, if described less than `ttl` ago.
+    fn get(&self, db_name: &str, name: &str) -> Option<CollectionMetadata> {
+        let entries = self.entries.read().unwrap();
+        let (described_at, metadata) = entries.get(&(db_name.to_string(), name.to_string()))?;
+        (described_at.elapsed() < self.ttl).then(|| metadata.clone())
+    }
+
+    fn insert(&self, db_name: &str, name: &str, metadata: &CollectionMetadata) {
+        if self.ttl.is_zero() {
+            return;
+        }
+
+        self.entries.write().unwrap().insert(
+            (db_name.to_string(), name.to_string()),
+            (Instant::now(), metadata.clone()),
+        );
+    }
+
+    /// Forget the collection or alias `name`, and the entries of the same collection described by another name, e.g. an
+    /// alias of a dropped collection.
+    fn invalidate(&self, db_name: &str, name: &str) {
+        self.entries
+            .write()
+            .unwrap()
+            .retain(|(db, key), (_, metadata)| {
+                db != db_name
+                    || (key != name
+                        && metadata.name != name
+                        && !metadata.aliases.iter().any(|a| a == name))
+            });
+    }
+}
+
+/// Pages of the entities matching an expression, in primary key order, created by `Client::query_iterator`.
+#[derive(Debug, Clone)]
+pub struct QueryIterator {
+    client: Client,
+    collection_name: String,
+    expr: String,
+    output_fields: Vec<String>,
+    batch_size: i64,
+    primary_field: FieldSchema,
+    /// The primary key of the last entity returned
+    last_pk: Option<Value>,
+    exhausted: bool,
+}
+impl QueryIterator {
+    /// Return the next page of entities, or `None` once all the matching entities have been returned.
+    pub async fn next_batch(&mut self) -> Result<Option<QueryResult>> {
+        if self.exhausted {
+            return Ok(None);
+        }
+
+        let request = self.next_request()?;
+        let result = self.client.send_query(request).await?;
+
+        if self.advance(&result)? == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(result))
+    }
+
+    /// The query of the page following the last primary key.
+    fn next_request(&self) -> Result<milvus::proto::milvus::QueryRequest> {
+        let expr = match &self.last_pk {
+            Some(pk) => resume_after_pk_expr(&self.expr, self.primary_field.name(), pk)?,
+            None => self.expr.clone(),
+        };
+
+        let options = QueryOptions::new()
+            .output_fields(self.output_fields.iter().map(|s| s.as_str()).collect())
+            .limit(self.batch_size);
+        let mut request = self
+            .client
+            .query_with_request(&self.collection_name, &expr, options)?;
+        // sort the page by primary key
+        request.query_params.push(KeyValuePair {
+            key: "iterator".to_string(),
+            value: "True".to_string(),
+        });
+
+        Ok(request)
+    }
+
+    /// Move past the page `result`, returning its number of entities.
+    fn advance(&mut self, result: &QueryResult) -> Result<usize> {
+        let pks = result
+            .fields_data
+            .iter()
+            .find(|field| field.field_name == self.primary_field.name())
+            .ok_or_else(|| {
+                Error::Unexpected(format!(
+                    "primary key {} missing from the query result",
+                    self.primary_field.name()
+                ))
+            })?;
+
+        let num_rows = pks.num_rows();
+        if (num_rows as i64) < self.batch_size {
+            self.exhausted = true;
+        }
+        if num_rows > 0 {
+            self.last_pk = pks.value(num_rows - 1);
+        }
+
+        Ok(num_rows)
+    }
+}
+
+/// A fixed set of clients, each with its own connection, handed out in turn to spread the load of concurrent requests.
+///
+/// A single client multiplexes all its requests over one HTTP/2 connection, which heavy parallel searches can saturate. The
+/// clients of a pool share their session state, so `Session` consistency holds whichever client served a write.
+///
+/// Each call to `get` hands out the next client, e.g. `pool.get().search_with(...)`. Keep the client returned to make several
+/// calls through the same connection.
+#[derive(Debug, Clone)]
+pub struct ClientPool {
+    clients: Arc<Vec<Client>>,
+    next: Arc<AtomicUsize>,
+}
+impl ClientPool {
+    /// Open `size` connections to the server at `host:port`, see `Client::new` for the other arguments.
+    pub async fn new(
+        host: &str,
+        port: u16,
+        username: Option<String>,
+        password: Option<String>,
+        timeout: Option<std::time::Duration>,
+        size: usize,
+    ) -> Result<Self> {
+        if size == 0 {
+            return Err(Error::InvalidParameter(
+                "size".to_owned(),
+                "expected at least one connection".to_owned(),
+            ));
+        }
+
+        let mut clients = Vec::with_capacity(size);
+        for _ in 0..size {
+            clients
+                .push(Client::new(host, port, username.clone(), password.clone(), timeout).await?);
+        }
+
+        Ok(Self::from_clients(clients))
+    }
+
+    /// Pool `clients`, sharing the session state and the `msg_id` sequence of the first one, and its `source_id`.
+    fn from_clients(clients: Vec<Client>) -> Self {
+        let first = clients[0].clone();
+        let clients = clients
+            .into_iter()
+            .map(|client| Client {
+                msg_id: first.msg_id.clone(),
+                source_id: first.source_id,
+                request_id: first.request_id.clone(),
+                session_ts: first.session_ts.clone(),
+                collection_ids: first.collection_ids.clone(),
+                describe_cache: first.describe_cache.clone(),
+                ..client
+            })
+            .collect();
+
+        ClientPool {
+            clients: Arc::new(clients),
+            next: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Send the requests of all the clients to the database `db_name`, see `Client::with_database`.
+    pub fn with_database(self, db_name: &str) -> Self {
+        ClientPool {
+            clients: Arc::new(
+                self.clients
+                    .iter()
+                    .map(|client| client.clone().with_database(db_name))
+                    .collect(),
+            ),
+            next: self.next,
+        }
+    }
+
+    /// The next client, in turn.
+    pub fn get(&self) -> &Client {
+        let i = self.next.fetch_add(1, Ordering::Relaxed);
+        &self.clients[i % self.clients.len()]
+    }
+
+    /// The number of connections of the pool.
+    pub fn size(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// Replace the token of all the clients, see `Client::set_token`.
+    pub fn set_token(&self, token: Option<&str>) -> Result<()> {
+        self.clients
+            .iter()
+            .try_for_each(|client| client.set_token(token))
+    }
+
+    /// Replace the API key of all the clients, see `Client::set_api_key`.
+    pub fn set_api_key(&self, api_key: &str) -> Result<()> {
+        self.clients
+            .iter()
+            .try_for_each(|client| client.set_api_key(api_key))
+    }
+}
+
+/// Pages of the hits closest to a vector, created by `Client::search_iterator`.
+#[derive(Debug, Clone)]
+pub struct SearchIterator {
+    client: Client,
+    collection_name: String,
+    vector: Vec<f32>,
+    field_name: String,
+    batch_size: i64,
+    total: i64,
+    /// The number of hits searched so far, duplicates included
+    offset: i64,
+    /// The number of hits returned so far
+    returned: i64,
+    seen: HashSet<Value>,
+    exhausted: bool,
+}
+impl SearchIterator {
+    /// Return the next page of hits, or `None` once `total` hits, or all the hits, have been returned.
+    pub async fn next_batch(&mut self) -> Result<Option<QueryHits>> {
+        while !self.exhausted && self.returned < self.total {
+            let limit = self.batch_size.min(self.total - self.returned);
+            let request = self.next_request(limit)?;
+            let hits = self
+                .client
+                .send_search(request)
+                .await?
+                .per_query()
+                .into_iter()
+                .next()
+                .unwrap_or_default();
+
+            let page = self.advance(hits, limit);
+            // a page of duplicates only, search further
+            if !page.hits.is_empty() {
+                return Ok(Some(page));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// The search of the `limit` hits following the ones searched so far.
+    fn next_request(&self, limit: i64) -> Result<milvus::proto::milvus::SearchRequest> {
+        let options = SearchOptions::new().top_k(limit).offset(self.offset);
+
+        self.client.search_with_request(
+            &self.collection_name,
+            vec![self.vector.clone()],
+            &self.field_name,
+            options,
+        )
+    }
+
+    /// Move past the page `hits` of a search of `limit` hits, returning the hits not returned yet.
+    fn advance(&mut self, hits: QueryHits, limit: i64) -> QueryHits {
+        self.offset += hits.hits.len() as i64;
+        if (hits.hits.len() as i64) < limit {
+            self.exhausted = true;
+        }
+
+        let mut page = QueryHits::default();
+        for hit in hits.hits {
+            if self.returned >= self.total {
+                break;
+            }
+            if let Some(id) = &hit.id {
+                if !self.seen.insert(id.clone()) {
+                    continue;
+                }
+            }
+            self.returned += 1;
+            page.hits.push(hit);
+        }
+
+        page
+    }
+}
+
+/// A handle on an existing collection, created by `Client::collection`.
+///
+/// The handle keeps the collection schema and the database of the client it was created from, so its methods take neither
+/// the collection name nor the database.
+#[derive(Debug, Clone)]
+pub struct CollectionHandle {
+    client: Client,
+    name: String,
+    schema: CollectionSchema,
+}
+impl CollectionHandle {
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The database of the collection, empty for the server default database.
+    pub fn db_name(&self) -> &str {
+        self.client.database()
+    }
+
+    pub fn schema(&self) -> &CollectionSchema {
+        &self.schema
+    }
+
+    /// Insert columns into the collection, see `Client::insert`.
+    pub async fn insert(
+        &self,
+        partition_name: &str,
+        fields_data: Vec<FieldData>,
+    ) -> Result<MutationResult> {
+        self.client
+            .insert(&self.name, partition_name, fields_data)
+            .await
+    }
+
+    /// Insert rows into the collection, transposed with the collection schema, see `Client::insert_rows`.
+    pub async fn insert_rows(
+        &self,
+        partition_name: &str,
+        rows: Vec<HashMap<String, Value>>,
+    ) -> Result<MutationResult> {
+        self.client
+            .insert_rows(&self.name, partition_name, rows, &self.schema)
+            .await
+    }
+
+    /// Search the float vector field `field_name`, see `Client::search_with`.
+    pub async fn search(
+        &self,
+        vectors: Vec<Vec<f32>>,
+        field_name: &str,
+        options: SearchOptions,
+    ) -> Result<SearchResult> {
+        self.client
+            .search_with(&self.name, vectors, field_name, options)
+            .await
+    }
+
+    /// Search the `top_k` entities closest to each of the text embeddings `vectors`, returning the hits of each query, in the
+    /// order of `vectors`, with their score and the scalar fields `output_fields`.
+    ///
+    /// No metric is sent, so the search uses the metric the index of `anns_field` was built with.
+    ///
+    /// # Arguments
+    ///
+    /// * `vectors` - The embeddings of the query texts, all of the dimension of the field.
+    ///
+    /// * `anns_field` - The name of the float vector field to search.
+    ///
+    /// * `top_k` - The maximum number of hits of each query.
+    ///
+    /// * `output_fields` - The scalar fields to return with every hit.
+    pub async fn search_text_vectors(
+        &self,
+        vectors: Vec<Vec<f32>>,
+        anns_field: &str,
+        top_k: i64,
+        output_fields: Vec<&str>,
+    ) -> Result<Vec<QueryHits>> {
+        let options = SearchOptions::new()
+            .top_k(top_k)
+            .output_fields(output_fields);
+
+        Ok(self.search(vectors, anns_field, options).await?.per_query())
+    }
+
+    /// Delete the entities matching `expr`, see `Client::delete`.
+    pub async fn delete(&self, partition_name: Option<&str>, expr: &str) -> Result<MutationResult> {
+        self.client.delete(&self.name, partition_name, expr).await
+    }
+
+    /// Delete entities by primary key, see `Client::delete_by_ids`.
+    ///
+    /// The primary key field is taken from the kept schema, so the collection is not described again.
+    pub async fn delete_by_ids(
+        &self,
+        partition_name: Option<&str>,
+        ids: IdField,
+    ) -> Result<MutationResult> {
+        let primary_field = self
+            .schema
+            .primary_field()
+            .ok_or_else(|| Error::Schema(SchemaError::NoPrimaryKey))?;
+
+        let expr = ids_expr(primary_field.name(), &ids)?;
+
+        self.delete(partition_name, &expr).await
+    }
+
+    /// Restrict the filter `expr` to the rows whose partition key equals `value`.
+    ///
+    /// Filtering on the partition key lets the server only scan the matching partition.
+    /// An empty `expr` results in the partition key filter alone.
+    pub fn partition_key_expr(&self, value: impl Into<Value>, expr: &str) -> Result<String> {
+        merge_partition_key_expr(&self.schema, &value.into(), expr)
+    }
+
+    /// Query the entities of the collection matching `expr`.
+    ///
+    /// The primary key is always returned, even when missing from `output_fields`, so that every row can be told apart.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - The boolean expression the entities must match.
+    ///
+    /// * `output_fields` - The fields to return.
+    ///
+    /// * `partition_names` - The partitions to query, all of them if `None`. An empty list returns `Error::InvalidParameter`.
+    pub async fn query(
+        &self,
+        expr: &str,
+        output_fields: Vec<&str>,
+        partition_names: Option<Vec<&str>>,
+    ) -> Result<QueryResult> {
+        let request = self.query_request(expr, output_fields, partition_names)?;
+
+        self.client.send_query(request).await
+    }
+
+    fn query_request(
+        &self,
+        expr: &str,
+        output_fields: Vec<&str>,
+        partition_names: Option<Vec<&str>>,
+    ) -> Result<milvus::proto::milvus::QueryRequest> {
+        let mut output_fields: Vec<String> = output_fields.iter().map(|s| s.to_string()).collect();
+        if let Some(primary_field) = self.schema.primary_field() {
+            output_fields.retain(|name| name != primary_field.name());
+            output_fields.push(primary_field.name().to_string());
+        }
+
+        Ok(milvus::proto::milvus::QueryRequest {
+            base: Some(self.client.new_msg(MsgType::Retrieve)),
+            db_name: self.client.db_name.clone(),
+            collection_name: self.name.clone(),
+            expr: expr.to_string(),
+            output_fields,
+            partition_names: partition_names_param(partition_names.as_deref())?,
+            ..Default::default()
+        })
+    }
+}
+
+/// The `partition_name` of a delete request: empty for all the partitions, otherwise a non-empty name.
+fn partition_name_param(partition_name: Option<&str>) -> Result<String> {
+    match partition_name {
+        None => Ok(String::new()),
+        Some("") => Err(Error::InvalidParameter(
+            "partition_name".to_owned(),
+            "expected a partition name, `None` for all of them".to_owned(),
+        )),
+        Some(name) => Ok(name.to_string()),
+    }
+}
+
+/// The `partition_names` of a search or query request: none for all the partitions, otherwise at least one.
+fn partition_names_param<S: AsRef<str>>(partition_names: Option<&[S]>) -> Result<Vec<String>> {
+    match partition_names {
+        None => Ok(vec![]),
+        Some([]) => Err(Error::InvalidParameter(
+            "partition_names".to_owned(),
+            "expected at least one partition, `None` for all of them".to_owned(),
+        )),
+        Some(names) => Ok(names.iter().map(|s| s.as_ref().to_string()).collect()),
+    }
+}
+
+/// Return the number of rows shared by all the columns of `fields_data`.
+fn aligned_num_rows(fields_data: &[FieldData]) -> Result<usize> {
+    let num_rows = fields_data
+        .first()
+        .map(|field_data| field_data.num_rows())
+        .unwrap_or_default();
+
+    match fields_data.iter().find(|f| f.num_rows() != num_rows) {
+        Some(field_data) => Err(Error::InvalidParameter(
+            field_data.field_name.clone(),
+            format!("{} rows, expected {}", field_data.num_rows(), num_rows),
+        )),
+        None => Ok(num_rows),
+    }
+}
+
+/// Transpose `rows` into one `FieldData` per field of `schema`, skipping the auto-id primary key.
+///
+/// With the dynamic field enabled, the fields not declared in `schema` are stored as JSON in the `DYNAMIC_FIELD_NAME` field,
+/// otherwise they are an error.
+fn rows_to_fields_data(
+    schema: &CollectionSchema,
+    rows: &[HashMap<String, Value>],
+) -> Result<Vec<FieldData>> {
+    let is_declared = |name: &str| schema.fields.iter().any(|f| f.name() == name);
+    if !schema.enable_dynamic_field {
+        for row in rows {
+            if let Some(name) = row.keys().find(|name| !is_declared(name)) {
+                return Err(Error::from(SchemaError::FieldDoesNotExists(name.clone())));
+            }
+        }
+    }
+
+    let mut fields_data = vec![];
+    for field in schema.fields.iter().filter(|f| !f.is_auto_id()) {
+        let mut column = Vec::with_capacity(rows.len());
+        let mut valid_data = Vec::with_capacity(rows.len());
+        for (i, row) in rows.iter().enumerate() {
+            match row.get(field.name()) {
+                Some(value) => column.push(value),
+                // null, or the default value filled in by the server
+                None if field.is_optional() => {}
+                None => {
+                    return Err(Error::InvalidParameter(
+                        format!("rows[{}]", i),
+                        format!("missing field {}", field.name()),
+                    ))
+                }
+            }
+            valid_data.push(row.contains_key(field.name()));
+        }
+
+        let mut field_data = column_to_field_data(field, &column)?;
+        if field.is_optional() {
+            field_data.valid_data = valid_data;
+        }
+        fields_data.push(field_data);
+    }
+
+    if schema.enable_dynamic_field {
+        let mut documents = Vec::with_capacity(rows.len());
+        for (i, row) in rows.iter().enumerate() {
+            let mut document = serde_json::Map::new();
+            for (name, value) in row.iter().filter(|(name, _)| !is_declared(name)) {
+                let value = value_to_json(value).ok_or_else(|| {
+                    Error::InvalidParameter(
+                        format!("rows[{}]", i),
+                        format!("{} can not be stored in the dynamic field", name),
+                    )
+                })?;
+                document.insert(name.clone(), value);
+            }
+            documents.push(serde_json::to_vec(&document)?);
+        }
+
+        fields_data.push(FieldData {
+            data_type: DataType::Json as i32,
+            field_name: DYNAMIC_FIELD_NAME.to_owned(),
+            field_id: 0,
+            valid_data: vec![],
+            field: Some(Field::Scalars(ScalarField {
+                data: Some(ScalarFieldData::JsonData(documents)),
+            })),
+        });
+    }
+
+    Ok(fields_data)
+}
+
+/// Convert the columns of `Client::insert_columns` to the data of the fields of `schema`.
+fn columns_to_fields_data(
+    schema: &CollectionSchema,
+    mut columns: HashMap<String, ColumnData>,
+) -> Result<Vec<FieldData>> {
+    if let Some(name) = columns
+        .keys()
+        .find(|name| !schema.fields.iter().any(|f| f.name() == name.as_str()))
+    {
+        return Err(Error::from(SchemaError::FieldDoesNotExists(name.clone())));
+    }
+
+    let mut fields_data = vec![];
+    for field in schema.fields.iter().filter(|f| !f.is_auto_id()) {
+        let column = match columns.remove(field.name()) {
+            Some(column) => column,
+            None if field.is_optional() => continue,
+            None => {
+                return Err(Error::InvalidParameter(
+                    "columns".to_owned(),
+                    format!("missing field {}", field.name()),
+                ))
+            }
+        };
+
+        let data_type: DataType = field.field_type().clone().into();
+        let values = column.into_values(field.name(), data_type)?;
+        let field_data = column_to_field_data(field, &values.iter().collect::<Vec<_>>())?;
+        field_data.check_int_range()?;
+        fields_data.push(field_data);
+    }
+    aligned_num_rows(&fields_data)?;
+
+    Ok(fields_data)
+}
+
+/// The JSON value of a dynamic field, `None` for bytes, non-finite floats and vectors other than float ones.
+fn value_to_json(value: &Value) -> Option<serde_json::Value> {
+    let float = |v: f64| serde_json::Number::from_f64(v).map(serde_json::Value::Number);
+    match value {
+        Value::Bool(v) => Some((*v).into()),
+        Value::Int(v) => Some((*v).into()),
+        Value::Long(v) => Some((*v).into()),
+        Value::Float(v) => float(*v as f64),
+        Value::Double(v) => float(*v),
+        Value::String(v) => Some(v.as_str().into()),
+        Value::FloatVector(v) => v.iter().map(|v| float(*v as f64)).collect(),
+        Value::Bytes(_)
+        | Value::BinaryVector(_)
+        | Value::Int8Vector(_)
+        | Value::SparseFloatVector(_) => None,
+    }
+}
+
+/// Build the `FieldData` of `field` from one value per row.
+fn column_to_field_data(field: &FieldSchema, column: &[&Value]) -> Result<FieldData> {
+    let data_type: DataType = field.field_type().clone().into();
+    let wrong_type = |v: &Value| {
+        Error::from(SchemaError::FieldWrongType(
+            field.name().to_owned(),
+            data_type,
+            v.data_type(),
+        ))
+    };
+    let scalars = |data: ScalarFieldData| Field::Scalars(ScalarField { data: Some(data) });
+
+    let field_data = match field.field_type() {
+        FieldType::None => {
+            return Err(Error::InvalidParameter(
+                field.name().to_owned(),
+                "no data type".to_owned(),
+            ))
+        }
+        FieldType::Bool => scalars(ScalarFieldData::BoolData(collect_column(
+            column,
+            |v| match v {
+                Value::Bool(v) => Some(*v),
+                _ => None,
+            },
+            &wrong_type,
+        )?)),
+        FieldType::Int8 | FieldType::Int16 | FieldType::Int32 => {
+            scalars(ScalarFieldData::IntData(collect_column(
+                column,
+                |v| match v {
+                    Value::Int(v) => Some(*v),
+                    _ => None,
+                },
+                &wrong_type,
+            )?))
+        }
+        FieldType::Int64(..) => scalars(ScalarFieldData::LongData(collect_column(
+            column,
+            |v| match v {
+                Value::Int(v) => Some(*v as i64),
+                Value::Long(v) => Some(*v),
+                _ => None,
+            },
+            &wrong_type,
+        )?)),
+        FieldType::Float => scalars(ScalarFieldData::FloatData(collect_column(
+            column,
+            |v| match v {
+                Value::Float(v) => Some(*v),
+                _ => None,
+            },
+            &wrong_type,
+        )?)),
+        FieldType::Double => scalars(ScalarFieldData::DoubleData(collect_column(
+            column,
+            |v| match v {
+                Value::Float(v) => Some(*v as f64),
+                Value::Double(v) => Some(*v),
+                _ => None,
+            },
+            &wrong_type,
+        )?)),
+        FieldType::String | FieldType::VarChar(..) => {
+            scalars(ScalarFieldData::StringData(collect_column(
+                column,
+                |v| match v {
+                    Value::String(v) => Some(v.clone()),
+                    _ => None,
+                },
+                &wrong_type,
+            )?))
+        }
+        FieldType::FloatVector(dim) => {
+            let mut data = Vec::with_capacity(column.len() * *dim as usize);
+            for v in column {
+                match v {
+                    Value::FloatVector(row) if row.len() as i64 == *dim => {
+                        data.extend_from_slice(row)
+                    }
+                    Value::FloatVector(row) => {
+                        return Err(Error::from(SchemaError::DimensionMismatch(
+                            field.name().to_owned(),
+                            *dim as i32,
+                            row.len() as i32,
+                        )))
+                    }
+                    v => return Err(wrong_type(v)),
+                }
+            }
+
+            Field::Vectors(VectorField {
+                dim: *dim,
+                data: Some(VectorFieldData::FloatVec(data)),
+            })
+        }
+        FieldType::BinaryVector(dim) => {
+            let mut data = Vec::with_capacity(column.len() * *dim as usize / 8);
+            for v in column {
+                match v {
+                    Value::BinaryVector(row) if row.len() as i64 * 8 == *dim => {
+                        data.extend_from_slice(row)
+                    }
+                    Value::BinaryVector(row) => {
+                        return Err(Error::from(SchemaError::DimensionMismatch(
+                            field.name().to_owned(),
+                            *dim as i32,
+                            row.len() as i32 * 8,
+                        )))
+                    }
+                    v => return Err(wrong_type(v)),
+                }
+            }
+
+            Field::Vectors(VectorField {
+                dim: *dim,
+                data: Some(VectorFieldData::BinaryVec(data)),
+            })
+        }
+        FieldType::Int8Vector(dim) => {
+            let mut data = Vec::with_capacity(column.len() * *dim as usize);
+            for v in column {
+                match v {
+                    Value::Int8Vector(row) if row.len() as i64 == *dim => {
+                        data.extend_from_slice(row)
+                    }
+                    Value::Int8Vector(row) => {
+                        return Err(Error::from(SchemaError::DimensionMismatch(
+                            field.name().to_owned(),
+                            *dim as i32,
+                            row.len() as i32,
+                        )))
+                    }
+                    v => return Err(wrong_type(v)),
+                }
+            }
+
+            Field::Vectors(VectorField {
+                dim: *dim,
+                data: Some(VectorFieldData::Int8Vec(data)),
+            })
+        }
+        FieldType::Json => scalars(ScalarFieldData::JsonData(collect_column(
+            column,
+            |v| match v {
+                // a serialized document, as returned by queries
+                Value::String(v) if serde_json::from_str::<serde_json::Value>(v).is_ok() => {
+                    Some(v.clone().into_bytes())
+                }
+                _ => None,
+            },
+            &wrong_type,
+        )?)),
+        FieldType::SparseFloatVector => {
+            let data = collect_column(
+                column,
+                |v| match v {
+                    Value::SparseFloatVector(v) => Some(v.clone()),
+                    _ => None,
+                },
+                &wrong_type,
+            )?;
+
+            Field::Vectors(VectorField {
+                dim: data.iter().map(|row| row.dim()).max().unwrap_or(0),
+                data: Some(VectorFieldData::SparseFloatVec(data)),
+            })
+        }
+    };
+
+    Ok(FieldData {
+        data_type: data_type as i32,
+        field_name: field.name().to_owned(),
+        field_id: 0,
+        valid_data: vec![],
+        field: Some(field_data),
+    })
+}
+
+fn collect_column<T>(
+    column: &[&Value],
+    convert: impl Fn(&Value) -> Option<T>,
+    wrong_type: impl Fn(&Value) -> Error,
+) -> Result<Vec<T>> {
+    column
+        .iter()
+        .map(|v| convert(v).ok_or_else(|| wrong_type(v)))
+        .collect()
+}
+
+fn merge_partition_key_expr(
+    schema: &CollectionSchema,
+    value: &Value,
+    expr: &str,
+) -> Result<String> {
+    let field = schema.partition_key_field().ok_or_else(|| {
+        Error::InvalidParameter(
+            "partition key".to_owned(),
+            format!("collection {} has no partition key field", schema.name),
+        )
+    })?;
+
+    let literal = match (field.field_type(), value) {
+        (FieldType::Int64(..), Value::Long(v)) => v.to_string(),
+        (FieldType::Int64(..), Value::Int(v)) => v.to_string(),
+        (FieldType::VarChar(..), Value::String(v)) => quote_str(v),
+        _ => {
+            return Err(Error::InvalidParameter(
+                field.name().to_owned(),
+                format!("{:?}", value),
+            ))
+        }
+    };
+
+    let key_expr = format!("{} == {}", field.name(), literal);
+    if expr.trim().is_empty() {
+        Ok(key_expr)
+    } else {
+        Ok(format!("{} and ({})", key_expr, expr))
+    }
+}
+
+/// Poll `get_flush_state` with the sorted and deduplicated `segment_ids` until it returns true, see
+/// `Client::wait_for_segments_flushed`.
+async fn wait_for_segments<F, Fut>(
+    mut segment_ids: Vec<i64>,
+    interval: Duration,
+    timeout: Duration,
+    get_flush_state: F,
+) -> Result<()>
+where
+    F: Fn(Vec<i64>) -> Fut,
+    Fut: Future<Output = Result<bool>>,
+{
+    segment_ids.sort_unstable();
+    segment_ids.dedup();
+    if segment_ids.is_empty() {
+        return Ok(());
+    }
+
+    let segment_ids = &segment_ids;
+    let get_flush_state = &get_flush_state;
+    poll_until(
+        "wait_for_segments_flushed",
+        interval,
+        timeout,
+        || async move {
+            let flushed = get_flush_state(segment_ids.clone()).await?;
+            Ok(flushed.then_some(()))
+        },
+    )
+    .await
+}
+
+fn ttl_property(ttl: Duration) -> Result<(String, String)> {
+    if !ttl.is_zero() && ttl < Duration::from_secs(1) {
+        return Err(Error::InvalidParameter(
+            "ttl".to_owned(),
+            format!("{:?}", ttl),
+        ));
+    }
+
+    let secs = ttl.as_secs() + u64::from(ttl.subsec_nanos() > 0);
+    Ok((COLLECTION_TTL_SECONDS.to_string(), secs.to_string()))
+}
+
+fn mmap_property(enabled: bool) -> (String, String) {
+    (MMAP_ENABLED.to_string(), enabled.to_string())
+}
+
+/// Build the token of the `authorization` metadata from the credentials, `None` for anonymous access.
+fn auth_token(username: Option<String>, password: Option<String>) -> Result<Option<String>> {
+    let username = username.filter(|s| !s.is_empty());
+    let password = password.filter(|s| !s.is_empty());
+
+    match (username, password) {
+        (Some(username), Some(password)) => {
+            let auth_token = format!("{}:{}", username, password);
+            Ok(Some(general_purpose::STANDARD.encode(auth_token)))
+        }
+        (None, None) => Ok(None),
+        (Some(_), None) => Err(Error::InvalidParameter(
+            "password".to_owned(),
+            "missing while a username is given".to_owned(),
+        )),
+        (None, Some(_)) => Err(Error::InvalidParameter(
+            "username".to_owned(),
+            "missing while a password is given".to_owned(),
+        )),
+    }
+}
+
+/// The output field of a query returning the number of matching entities.
+const COUNT_FIELD: &str = "count(*)";
+
+/// The number of entities returned by a `COUNT_FIELD` query.
+fn count_from_result(result: &QueryResult) -> Result<i64> {
+    let count = result
+        .fields_data
+        .iter()
+        .find(|field| field.field_name == COUNT_FIELD)
+        .and_then(|field| field.value(0));
+
+    match count {
+        Some(Value::Long(count)) => Ok(count),
+        _ => Err(Error::Unexpected(format!(
+            "no {} in the response of {}",
+            COUNT_FIELD, result.collection_name
+        ))),
+    }
+}
+
+/// Whether a collection in `load_state` with `indexes` can be searched.
+fn is_searchable(load_state: &LoadState, indexes: &[IndexInfo]) -> bool {
+    load_state.is_loaded()
+        && !indexes.is_empty()
+        && indexes.iter().all(|index| index.state.is_finished())
+}
+
+/// The statistics entry holding the number of entities.
+const ROW_COUNT_STAT: &str = "row_count";
+
+/// The number of entities of the statistics of `collection_name`.
+fn row_count_from_stats(stats: &HashMap<String, String>, collection_name: &str) -> Result<i64> {
+    let row_count = stats.get(ROW_COUNT_STAT).ok_or_else(|| {
+        Error::Unexpected(format!(
+            "no {} in the statistics of {}",
+            ROW_COUNT_STAT, collection_name
+        ))
+    })?;
+
+    row_count.parse().map_err(|_| {
+        Error::Unexpected(format!(
+            "invalid {} {:?} in the statistics of {}",
+            ROW_COUNT_STAT, row_count, collection_name
+        ))
+    })
+}
+
+/// The build details of the server state, with the server version under `version`.
+fn build_info(version: String, states: ComponentState) -> HashMap<String, String> {
+    let mut info = states
+        .state
+        .map(|state| state.extra_info)
+        .unwrap_or_default();
+    info.insert("version".to_string(), version);
+
+    info
+}
+
+/// Check `source` has the `num_nodes` available nodes to transfer.
+fn check_transfer_node(source: &ResourceGroupInfo, num_nodes: i32) -> Result<()> {
+    if num_nodes <= 0 {
+        return Err(Error::InvalidParameter(
+            "num_nodes".to_owned(),
+            format!("expected a positive number of nodes, got {}", num_nodes),
+        ));
+    }
+    if num_nodes > source.num_available_node {
+        return Err(Error::InvalidParameter(
+            "num_nodes".to_owned(),
+            format!(
+                "resource group {} has {} available nodes, can not transfer {}",
+                source.name, source.num_available_node, num_nodes
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check `source` holds the `num_replicas` replicas of `collection_name` to transfer.
+fn check_transfer_replica(
+    source: &ResourceGroupInfo,
+    collection_name: &str,
+    num_replicas: i64,
+) -> Result<()> {
+    if num_replicas <= 0 {
+        return Err(Error::InvalidParameter(
+            "num_replicas".to_owned(),
+            format!(
+                "expected a positive number of replicas, got {}",
+                num_replicas
+            ),
+        ));
+    }
+    let loaded = source
+        .num_loaded_replica
+        .get(collection_name)
+        .copied()
+        .unwrap_or(0);
+    if num_replicas > loaded as i64 {
+        return Err(Error::InvalidParameter(
+            "num_replicas".to_owned(),
+            format!(
+                "resource group {} has {} replicas of {}, can not transfer {}",
+                source.name, loaded, collection_name, num_replicas
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check the parallel arrays of the `op` response, given with their lengths, are as long as its `len` names.
+fn check_parallel_lengths(op: &str, len: usize, arrays: &[(&str, usize)]) -> Result<()> {
+    match arrays.iter().find(|(_, array_len)| *array_len != len) {
+        Some((name, array_len)) => Err(Error::Unexpected(format!(
+            "{} returned {} {} for {} names",
+            op, array_len, name, len
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// The collections of a `show_collections` response.
+///
+/// The loading state is deprecated and left empty by recent servers, so it is only set for the collections it is reported for.
+fn collection_infos(
+    response: milvus::proto::milvus::ShowCollectionsResponse,
+) -> Result<Vec<CollectionInfo>> {
+    check_parallel_lengths(
+        "show_collections",
+        response.collection_names.len(),
+        &[
+            ("collection_ids", response.collection_ids.len()),
+            ("created_timestamps", response.created_timestamps.len()),
+            (
+                "created_utc_timestamps",
+                response.created_utc_timestamps.len(),
+            ),
+        ],
+    )?;
+    Ok((0..response.collection_names.len())
+        .map(|i| CollectionInfo {
+            name: response.collection_names[i].clone(),
+            id: response.collection_ids[i],
+            created_timestamp: response.created_timestamps[i],
+            created_utc_timestamp: response.created_utc_timestamps[i],
+            in_memory_percentage: response.in_memory_percentages.get(i).copied(),
+            query_service_available: response.query_service_available.get(i).copied(),
+        })
+        .collect())
+}
+
+/// The partitions of a `show_partitions` response, see `collection_infos`.
+fn partition_infos(
+    response: milvus::proto::milvus::ShowPartitionsResponse,
+) -> Result<Vec<PartitionInfo>> {
+    check_parallel_lengths(
+        "show_partitions",
+        response.partition_names.len(),
+        &[
+            ("partition_ids", response.partition_i_ds.len()),
+            ("created_timestamps", response.created_timestamps.len()),
+            (
+                "created_utc_timestamps",
+                response.created_utc_timestamps.len(),
+            ),
+        ],
+    )?;
+    Ok((0..response.partition_names.len())
+        .map(|i| PartitionInfo {
+            name: response.partition_names[i].clone(),
+            id: response.partition_i_ds[i],
+            created_timestamp: response.created_timestamps[i],
+            created_utc_timestamp: response.created_utc_timestamps[i],
+            in_memory_percentage: response.in_memory_percentages.get(i).copied(),
+        })
+        .collect())
+}
+
+/// Check the files of an import are given and, unless importing a backup, named after their format.
+fn check_import_files(files: &[&str], row_based: bool, backup: bool) -> Result<()> {
+    if files.is_empty() {
+        return Err(Error::InvalidParameter(
+            "files".to_owned(),
+            "expected at least one file".to_owned(),
+        ));
+    }
+    if backup {
+        return Ok(());
+    }
+
+    let extension = if row_based { ".json" } else { ".npy" };
+    match files
+        .iter()
+        .find(|file| !file.to_lowercase().ends_with(extension))
+    {
+        Some(file) => Err(Error::InvalidParameter(
+            "files".to_owned(),
+            format!(
+                "{} is not a {} file, as expected when row_based is {}",
+                file, extension, row_based
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Pair the tasks of an import with their files: a task per file for a row-based import, a single task otherwise.
+fn import_tasks(task_ids: Vec<i64>, files: &[&str], row_based: bool) -> Vec<ImportTask> {
+    let files: Vec<String> = files.iter().map(|file| file.to_string()).collect();
+    if row_based && task_ids.len() == files.len() {
+        return task_ids
+            .into_iter()
+            .zip(files)
+            .map(|(id, file)| ImportTask {
+                id,
+                files: vec![file],
+            })
+            .collect();
+    }
+
+    // no way to tell which file went to which task
+    task_ids
+        .into_iter()
+        .map(|id| ImportTask {
+            id,
+            files: files.clone(),
+        })
+        .collect()
+}
+
+/// Check that `vectors` are non-empty query vectors of the same length.
+fn check_query_vectors<T>(vectors: &[Vec<T>]) -> Result<()> {
+    let dim = match vectors.first() {
+        Some(vector) if !vector.is_empty() => vector.len(),
+        _ => {
+            return Err(Error::InvalidParameter(
+                "vectors".to_owned(),
+                "expected at least one non-empty vector".to_owned(),
+            ))
+        }
+    };
+    if let Some(i) = vectors.iter().position(|v| v.len() != dim) {
+        return Err(Error::InvalidParameter(
+            format!("vectors[{}]", i),
+            format!("dimension {} does not match {}", vectors[i].len(), dim),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Check that `metric_type`, if any, is a binary metric exactly when the searched vectors are `binary`.
+///
+/// The server does not always reject a mismatch, e.g. an `L2` search of binary vectors, but returns meaningless scores.
+fn check_metric_type(metric_type: Option<MetricType>, binary: bool) -> Result<()> {
+    match metric_type {
+        Some(metric_type) if metric_type.is_binary() != binary => Err(Error::InvalidParameter(
+            "metric_type".to_owned(),
+            format!(
+                "{} cannot search {} vectors",
+                metric_type,
+                if binary { "binary" } else { "float" }
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Encode float query vectors as the placeholder group of a search request.
+fn float_vectors_placeholder(vectors: &[Vec<f32>]) -> Result<Vec<u8>> {
+    check_query_vectors(vectors)?;
+
+    let group = PlaceholderGroup {
+        placeholders: vec![PlaceholderValue {
+            tag: "$0".to_string(),
+            r#type: PlaceholderType::FloatVector as i32,
+            values: vectors
+                .iter()
+                .map(|v| v.iter().flat_map(|x| x.to_le_bytes()).collect())
+                .collect(),
+        }],
+    };
+
+    Ok(group.encode_to_vec())
+}
+
+/// Encode bit-packed binary query vectors as the placeholder group of a search request.
+fn binary_vectors_placeholder(vectors: &[Vec<u8>]) -> Result<Vec<u8>> {
+    check_query_vectors(vectors)?;
+
+    let group = PlaceholderGroup {
+        placeholders: vec![PlaceholderValue {
+            tag: "$0".to_string(),
+            r#type: PlaceholderType::BinaryVector as i32,
+            values: vectors.to_vec(),
+        }],
+    };
+
+    Ok(group.encode_to_vec())
+}
+
+/// The primary keys `field_name` of the entities of `result`.
+fn primary_ids(result: &QueryResult, field_name: &str) -> Result<IdField> {
+    let field = result
+        .fields_data
+        .iter()
+        .find(|field| field.field_name == field_name)
+        .ok_or_else(|| {
+            Error::Unexpected(format!(
+                "primary key {} missing from the query result",
+                field_name
+            ))
+        })?;
+
+    match &field.field {
+        Some(Field::Scalars(ScalarField {
+            data: Some(ScalarFieldData::LongData(ids)),
+        })) => Ok(IdField::IntId(ids.clone())),
+        Some(Field::Scalars(ScalarField {
+            data: Some(ScalarFieldData::StringData(ids)),
+        })) => Ok(IdField::StrId(ids.clone())),
+        // no entity
+        None | Some(Field::Scalars(ScalarField { data: None })) => Ok(IdField::IntId(vec![])),
+        _ => Err(Error::Unexpected(format!(
+            "unsupported primary key {} in the query result",
+            field_name
+        ))),
+    }
+}
+
+/// Restrict `expr` to the entities whose primary key `field_name` follows `last_pk`, to resume a query sorted by primary key.
+fn resume_after_pk_expr(expr: &str, field_name: &str, last_pk: &Value) -> Result<String> {
+    let literal = match last_pk {
+        Value::Long(v) => v.to_string(),
+        Value::String(v) => quote_str(v),
+        v => {
+            return Err(Error::Unexpected(format!(
+                "unsupported primary key {:?}",
+                v
+            )))
+        }
+    };
+
+    let pk_expr = format!("{} > {}", field_name, literal);
+    if expr.trim().is_empty() {
+        Ok(pk_expr)
+    } else {
+        Ok(format!("({}) and {}", expr, pk_expr))
+    }
+}
+
+/// Build the expression matching the entities whose primary key `field_name` is in `ids`.
+fn ids_expr(field_name: &str, ids: &IdField) -> Result<String> {
+    let literals: Vec<String> = match ids {
+        IdField::IntId(ids) => ids.iter().map(|id| id.to_string()).collect(),
+        IdField::StrId(ids) => ids.iter().map(|id| quote_str(id)).collect(),
+    };
+
+    if literals.is_empty() {
+        return Err(Error::InvalidParameter(
+            "ids".to_owned(),
+            "expected at least one id".to_owned(),
+        ));
+    }
+
+    Ok(format!("{} in [{}]", field_name, literals.join(", ")))
+}
+
+#[derive(Clone)]
+pub struct AuthInterceptor {
+    token: Arc<RwLock<Option<String>>>,
+    identifier: Arc<RwLock<Option<i64>>>,
+}
+
+impl Interceptor for AuthInterceptor {
+    fn call(
+        &mut self,
+        mut req: Request<()>,
+    ) -> std::result::Result<tonic::Request<()>, tonic::Status> {
+        if let Some(ref token) = *self.token.read().unwrap() {
+            req.metadata_mut()
+                .insert("authorization", token.parse().unwrap());
+        }
+
+        if let Some(identifier) = *self.identifier.read().unwrap() {
+            req.metadata_mut()
+                .insert("identifier", identifier.to_string().parse().unwrap());
+        }
+
+        Ok(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::my_collection::Hit;
+    use milvus::proto::schema::DataType;
+
+    fn get_vdb_host_address() -> String {
+        std::env::var("VDB_HOST").expect("VDB_HOST is not set")
+    }
+
+    fn partition_key_schema() -> CollectionSchema {
+        let field = |name: &str, data_type: DataType, is_primary_key, is_partition_key| {
+            milvus::proto::schema::FieldSchema {
+                name: name.to_string(),
+                data_type: data_type as i32,
+                is_primary_key,
+                is_partition_key,
+                type_params: vec![KeyValuePair {
+                    key: "max_length".to_string(),
+                    value: "64".to_string(),
+                }],
+                ..Default::default()
+            }
+        };
+
+        milvus::proto::schema::CollectionSchema {
+            name: "docs".to_string(),
+            fields: vec![
+                field("id", DataType::Int64, true, false),
+                field("tenant", DataType::VarChar, false, true),
+            ],
+            ..Default::default()
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_request_id_tracker() {
+        let tracker = RequestIdTracker::default();
+
+        let mut response = tonic::Response::new(());
+        response
+            .metadata_mut()
+            .insert("request-id", "req-1".parse().unwrap());
+
+        // disabled by default
+        tracker.observe(response.metadata());
+        assert_eq!(*tracker.last.lock().unwrap(), None);
+
+        tracker.enabled.store(true, Ordering::Relaxed);
+        tracker.observe(response.metadata());
+        assert_eq!(tracker.last.lock().unwrap().as_deref(), Some("req-1"));
+
+        // failed RPCs carry the id in the status metadata
+        let mut metadata = tonic::metadata::MetadataMap::new();
+        metadata.insert("trace-id", "trace-2".parse().unwrap());
+        let status =
+            tonic::Status::with_metadata(tonic::Code::Unavailable, "unavailable", metadata);
+        tracker.observe(status.metadata());
+        assert_eq!(tracker.last.lock().unwrap().as_deref(), Some("trace-2"));
+
+        // a response without id following one with an id is reported without id
+        tracker.observe(tonic::Response::new(()).metadata());
+        assert_eq!(*tracker.last.lock().unwrap(), None);
+    }
+
+    #[test]
+    fn test_aligned_num_rows() {
+        let long_data = |name: &str, data: Vec<i64>| FieldData {
+            data_type: DataType::Int64 as i32,
+            field_name: name.to_string(),
+            field_id: 0,
+            valid_data: vec![],
+            field: Some(Field::Scalars(ScalarField {
+                data: Some(ScalarFieldData::LongData(data)),
+            })),
+        };
+        let vectors = FieldData {
+            data_type: DataType::FloatVector as i32,
+            field_name: "embedding".to_string(),
+            field_id: 0,
+            valid_data: vec![],
+            field: Some(Field::Vectors(VectorField {
+                dim: 2,
+                data: Some(VectorFieldData::FloatVec(vec![0.0; 6])),
+            })),
+        };
+
+        let fields_data = vec![long_data("id", vec![1, 2, 3]), vectors.clone()];
+        assert_eq!(aligned_num_rows(&fields_data).unwrap(), 3);
+
+        let fields_data = vec![long_data("id", vec![1, 2]), vectors];
+        assert!(matches!(
+            aligned_num_rows(&fields_data),
+            Err(Error::InvalidParameter(..))
+        ));
+    }
+
+    fn rows_schema() -> CollectionSchema {
+        CollectionSchema::new(
+            "books",
+            vec![
+                FieldSchema::new("id", FieldType::Int64(true, true), None),
+                FieldSchema::new("title", FieldType::VarChar(64, false, false), None),
+                FieldSchema::new("embedding", FieldType::FloatVector(2), None),
+            ],
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_rows_to_fields_data() {
+        let schema = rows_schema();
+        let rows = vec![
+            HashMap::from([
+                ("title".to_string(), Value::from("a")),
+                ("embedding".to_string(), Value::from(vec![0.1f32, 0.2])),
+            ]),
+            HashMap::from([
+                ("title".to_string(), Value::from("b")),
+                ("embedding".to_string(), Value::from(vec![0.3f32, 0.4])),
+            ]),
+        ];
+
+        let fields_data = rows_to_fields_data(&schema, &rows).unwrap();
+
+        // the auto-id primary key is skipped
+        assert_eq!(fields_data.len(), 2);
+        assert_eq!(fields_data[0].field_name, "title");
+        assert_eq!(fields_data[0].data_type, DataType::VarChar as i32);
+        assert_eq!(fields_data[0].num_rows(), 2);
+        match &fields_data[0].field {
+            Some(Field::Scalars(field)) => assert_eq!(
+                field.data,
+                Some(ScalarFieldData::StringData(vec![
+                    "a".to_string(),
+                    "b".to_string()
+                ]))
+            ),
+            _ => panic!("expected a scalar field"),
+        }
+        assert_eq!(fields_data[1].field_name, "embedding");
+        assert_eq!(fields_data[1].num_rows(), 2);
+        match &fields_data[1].field {
+            Some(Field::Vectors(field)) => assert_eq!(
+                field.data,
+                Some(VectorFieldData::FloatVec(vec![0.1, 0.2, 0.3, 0.4]))
+            ),
+            _ => panic!("expected a vector field"),
+        }
+    }
+
+    #[test]
+    fn test_rows_to_fields_data_optional_fields() {
+        let schema = CollectionSchema::new(
+            "books",
+            vec![
+                FieldSchema::new("id", FieldType::Int64(true, true), None),
+                FieldSchema::new("title", FieldType::VarChar(64, false, false), None)
+                    .nullable(true),
+                FieldSchema::new("year", FieldType::Int64(false, false), None)
+                    .default_value(Value::Long(2000)),
+                FieldSchema::new("embedding", FieldType::FloatVector(2), None),
+            ],
+            None,
+        )
+        .unwrap();
+        let rows = vec![
+            HashMap::from([
+                ("title".to_string(), Value::from("a")),
+                ("embedding".to_string(), Value::from(vec![0.1f32, 0.2])),
+            ]),
+            HashMap::from([
+                ("year".to_string(), Value::Long(1965)),
+                ("embedding".to_string(), Value::from(vec![0.3f32, 0.4])),
+            ]),
+        ];
+
+        let fields_data = rows_to_fields_data(&schema, &rows).unwrap();
+
+        // only the values of the rows providing them are sent
+        assert_eq!(fields_data[0].valid_data, vec![true, false]);
+        assert_eq!(fields_data[0].num_rows(), 2);
+        assert_eq!(fields_data[0].value(0), Some(Value::from("a")));
+        assert!(fields_data[0].is_null(1));
+        assert_eq!(fields_data[1].valid_data, vec![false, true]);
+        assert_eq!(fields_data[1].value(1), Some(Value::Long(1965)));
+        assert!(fields_data[2].valid_data.is_empty());
+
+        // a required field is still required
+        let rows = vec![HashMap::from([("title".to_string(), Value::from("a"))])];
+        let result = rows_to_fields_data(&schema, &rows);
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "rows[0]"));
+    }
+
+    #[test]
+    fn test_columns_to_fields_data() {
+        let schema = CollectionSchema::new(
+            "books",
+            vec![
+                FieldSchema::new("id", FieldType::Int64(true, true), None),
+                FieldSchema::new("pages", FieldType::Int16, None),
+                FieldSchema::new("rating", FieldType::Float, None),
+                FieldSchema::new("title", FieldType::VarChar(64, false, false), None)
+                    .nullable(true),
+                FieldSchema::new("embedding", FieldType::FloatVector(2), None),
+            ],
+            None,
+        )
+        .unwrap();
+        let columns = |pages: Vec<i64>| {
+            HashMap::from([
+                ("pages".to_string(), ColumnData::Int(pages)),
+                ("rating".to_string(), ColumnData::Float(vec![4.5, 3.0])),
+                (
+                    "embedding".to_string(),
+                    ColumnData::FloatVector(vec![vec![0.1, 0.2], vec![0.3, 0.4]]),
+                ),
+            ])
+        };
+
+        // the auto-id primary key and the nullable field are skipped
+        let fields_data = columns_to_fields_data(&schema, columns(vec![120, 300])).unwrap();
+        let names: Vec<_> = fields_data.iter().map(|f| f.field_name.as_str()).collect();
+        assert_eq!(names, vec!["pages", "rating", "embedding"]);
+        assert_eq!(fields_data[0].data_type, DataType::Int16 as i32);
+        assert_eq!(fields_data[0].value(1), Some(Value::Int(300)));
+        assert_eq!(fields_data[1].data_type, DataType::Float as i32);
+        assert_eq!(fields_data[1].value(0), Some(Value::Float(4.5)));
+
+        let result = columns_to_fields_data(&schema, columns(vec![120, 40000]));
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::ValueOutOfRange(ref f, 40000, DataType::Int16))) if f == "pages"
+        ));
+
+        let result = columns_to_fields_data(&schema, columns(vec![120]));
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "rating"));
+
+        let mut missing = columns(vec![120, 300]);
+        missing.remove("rating");
+        let result = columns_to_fields_data(&schema, missing);
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "columns"));
+
+        let mut unknown = columns(vec![120, 300]);
+        unknown.insert("year".to_string(), ColumnData::Int(vec![1965, 1966]));
+        let result = columns_to_fields_data(&schema, unknown);
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::FieldDoesNotExists(ref f))) if f == "year"
+        ));
+    }
+
+    #[test]
+    fn test_rows_to_fields_data_dynamic_field() {
+        let mut schema = rows_schema();
+        schema.enable_dynamic_field = true;
+        let rows = vec![
+            HashMap::from([
+                ("title".to_string(), Value::from("a")),
+                ("embedding".to_string(), Value::from(vec![0.1f32, 0.2])),
+                ("year".to_string(), Value::Long(1965)),
+            ]),
+            HashMap::from([
+                ("title".to_string(), Value::from("b")),
+                ("embedding".to_string(), Value::from(vec![0.3f32, 0.4])),
+            ]),
+        ];
+
+        let fields_data = rows_to_fields_data(&schema, &rows).unwrap();
+
+        // the undeclared fields are stored in the dynamic field
+        assert_eq!(fields_data.len(), 3);
+        assert_eq!(fields_data[2].field_name, DYNAMIC_FIELD_NAME);
+        assert_eq!(fields_data[2].data_type, DataType::Json as i32);
+        match &fields_data[2].field {
+            Some(Field::Scalars(field)) => assert_eq!(
+                field.data,
+                Some(ScalarFieldData::JsonData(vec![
+                    br#"{"year":1965}"#.to_vec(),
+                    b"{}".to_vec()
+                ]))
+            ),
+            _ => panic!("expected a scalar field"),
+        }
+        let proto: milvus::proto::schema::FieldData = fields_data[2].clone().into();
+        assert!(proto.is_dynamic);
+
+        // bytes have no JSON value
+        let rows = vec![HashMap::from([
+            ("title".to_string(), Value::from("a")),
+            ("embedding".to_string(), Value::from(vec![0.1f32, 0.2])),
+            ("blob".to_string(), Value::Bytes(vec![1])),
+        ])];
+        let result = rows_to_fields_data(&schema, &rows);
+        assert!(matches!(result, Err(Error::InvalidParameter(..))));
+    }
+
+    #[test]
+    fn test_rows_to_fields_data_json_field() {
+        let schema = CollectionSchema::new(
+            "books",
+            vec![
+                FieldSchema::new("id", FieldType::Int64(true, true), None),
+                FieldSchema::new("extra", FieldType::Json, None),
+                FieldSchema::new("embedding", FieldType::FloatVector(2), None),
+            ],
+            None,
+        )
+        .unwrap();
+        let row = |extra: &str| {
+            HashMap::from([
+                ("extra".to_string(), Value::from(extra)),
+                ("embedding".to_string(), Value::from(vec![0.1f32, 0.2])),
+            ])
+        };
+
+        let fields_data = rows_to_fields_data(&schema, &[row(r#"{"pages":412}"#)]).unwrap();
+        assert_eq!(fields_data[0].data_type, DataType::Json as i32);
+        assert_eq!(
+            fields_data[0].value(0),
+            Some(Value::from(r#"{"pages":412}"#))
+        );
+
+        // not a JSON document
+        let result = rows_to_fields_data(&schema, &[row("pages: 412")]);
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::FieldWrongType(ref f, DataType::Json, _))) if f == "extra"
+        ));
+    }
+
+    #[test]
+    fn test_rows_to_fields_data_invalid_rows() {
+        let schema = rows_schema();
+
+        // missing field
+        let rows = vec![HashMap::from([("title".to_string(), Value::from("a"))])];
+        let result = rows_to_fields_data(&schema, &rows);
+        assert!(matches!(result, Err(Error::InvalidParameter(..))));
+
+        // undeclared field
+        let rows = vec![HashMap::from([
+            ("title".to_string(), Value::from("a")),
+            ("embedding".to_string(), Value::from(vec![0.1f32, 0.2])),
+            ("author".to_string(), Value::from("x")),
+        ])];
+        let result = rows_to_fields_data(&schema, &rows);
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::FieldDoesNotExists(_)))
+        ));
+
+        // wrong dimension
+        let rows = vec![HashMap::from([
+            ("title".to_string(), Value::from("a")),
+            ("embedding".to_string(), Value::from(vec![0.1f32])),
+        ])];
+        let result = rows_to_fields_data(&schema, &rows);
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::DimensionMismatch(..)))
+        ));
+    }
+
+    #[test]
+    fn test_merge_partition_key_expr() {
+        let schema = partition_key_schema();
+
+        let expr = merge_partition_key_expr(&schema, &"t\"1".into(), "id > 10").unwrap();
+        assert_eq!(expr, r#"tenant == "t\"1" and (id > 10)"#);
+
+        let expr = merge_partition_key_expr(&schema, &"t1".into(), "").unwrap();
+        assert_eq!(expr, r#"tenant == "t1""#);
+
+        // the partition key is a VarChar
+        let result = merge_partition_key_expr(&schema, &Value::Long(1), "");
+        assert!(matches!(result, Err(Error::InvalidParameter(..))));
+    }
+
+    #[test]
+    fn test_merge_partition_key_expr_without_partition_key() {
+        let schema: CollectionSchema = milvus::proto::schema::CollectionSchema {
+            name: "docs".to_string(),
+            fields: vec![milvus::proto::schema::FieldSchema {
+                name: "id".to_string(),
+                data_type: DataType::Int64 as i32,
+                is_primary_key: true,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+        .try_into()
+        .unwrap();
+
+        let result = merge_partition_key_expr(&schema, &Value::Long(1), "id > 10");
+        assert!(matches!(result, Err(Error::InvalidParameter(..))));
+    }
+
+    #[test]
+    fn test_collection_infos() {
+        let response = milvus::proto::milvus::ShowCollectionsResponse {
+            collection_names: vec!["books".to_string(), "movies".to_string()],
+            collection_ids: vec![1, 2],
+            created_timestamps: vec![10, 20],
+            created_utc_timestamps: vec![100, 200],
+            in_memory_percentages: vec![100, 40],
+            query_service_available: vec![true, false],
+            ..Default::default()
+        };
+        let infos = collection_infos(response.clone()).unwrap();
+        assert_eq!(infos.len(), 2);
+        assert_eq!(infos[1].name, "movies");
+        assert_eq!(infos[1].id, 2);
+        assert_eq!(infos[1].in_memory_percentage, Some(40));
+        assert_eq!(infos[1].query_service_available, Some(false));
+
+        // not reported
+        let infos = collection_infos(milvus::proto::milvus::ShowCollectionsResponse {
+            in_memory_percentages: vec![],
+            query_service_available: vec![],
+            ..response.clone()
+        })
+        .unwrap();
+        assert_eq!(infos[0].in_memory_percentage, None);
+        assert_eq!(infos[0].query_service_available, None);
+
+        // uneven arrays are an error rather than a panic
+        let result = collection_infos(milvus::proto::milvus::ShowCollectionsResponse {
+            created_timestamps: vec![10],
+            ..response
+        });
+        assert!(
+            matches!(result, Err(Error::Unexpected(msg)) if msg.contains("created_timestamps"))
+        );
+    }
+
+    #[test]
+    fn test_count_from_result() {
+        let result = QueryResult {
+            fields_data: vec![FieldData {
+                data_type: DataType::Int64 as i32,
+                field_name: "count(*)".to_string(),
+                field_id: 0,
+                valid_data: vec![],
+                field: Some(Field::Scalars(ScalarField {
+                    data: Some(ScalarFieldData::LongData(vec![42])),
+                })),
+            }],
+            collection_name: "books".to_string(),
+        };
+        assert_eq!(count_from_result(&result).unwrap(), 42);
+
+        let result = QueryResult {
+            fields_data: vec![],
+            collection_name: "books".to_string(),
+        };
+        assert!(matches!(
+            count_from_result(&result),
+            Err(Error::Unexpected(..))
+        ));
+    }
+
+    #[test]
+    fn test_is_searchable() {
+        let index = |state: i32| {
+            IndexInfo::from(milvus::proto::milvus::IndexDescription {
+                index_name: "embedding_index".to_string(),
+                state,
+                ..Default::default()
+            })
+        };
+        let finished = index(crate::common::IndexBuildState::Finished as i32);
+        let in_progress = index(crate::common::IndexBuildState::InProgress as i32);
+
+        assert!(is_searchable(&LoadState::Loaded, &[finished.clone()]));
+        assert!(!is_searchable(
+            &LoadState::Loaded,
+            &[finished.clone(), in_progress]
+        ));
+        assert!(!is_searchable(&LoadState::Loading, &[finished]));
+        assert!(!is_searchable(&LoadState::Loaded, &[]));
+    }
+
+    #[test]
+    fn test_row_count_from_stats() {
+        let stats = HashMap::from([("row_count".to_string(), "1024".to_string())]);
+        assert_eq!(row_count_from_stats(&stats, "books").unwrap(), 1024);
+
+        let stats = HashMap::from([("row_count".to_string(), "many".to_string())]);
+        let result = row_count_from_stats(&stats, "books");
+        assert!(matches!(result, Err(Error::Unexpected(ref msg)) if msg.contains("\"many\"")));
+
+        let result = row_count_from_stats(&HashMap::new(), "books");
+        assert!(matches!(result, Err(Error::Unexpected(..))));
+    }
+
+    #[test]
+    fn test_build_info() {
+        let states = ComponentState {
+            state: Some(crate::my_collection::ComponentInfo {
+                node_id: 1,
+                role: "proxy".to_string(),
+                state_code: crate::my_collection::StateCode::Healthy,
+                extra_info: HashMap::from([
+                    ("git_commit".to_string(), "a1b2c3d".to_string()),
+                    ("deploy_mode".to_string(), "STANDALONE".to_string()),
+                ]),
+            }),
+            subcomponent_states: vec![],
+        };
+        let info = build_info("v2.4.1".to_string(), states);
+        assert_eq!(info["version"], "v2.4.1");
+        assert_eq!(info["git_commit"], "a1b2c3d");
+        assert_eq!(info["deploy_mode"], "STANDALONE");
+
+        let states = ComponentState {
+            state: None,
+            subcomponent_states: vec![],
+        };
+        let info = build_info("v2.4.1".to_string(), states);
+        assert_eq!(info.len(), 1);
+    }
+
+    #[test]
+    fn test_check_transfers() {
+        let source = ResourceGroupInfo {
+            name: "rg1".to_string(),
+            capacity: 3,
+            num_available_node: 2,
+            num_loaded_replica: HashMap::from([("books".to_string(), 1)]),
+            num_outgoing_node: HashMap::new(),
+            num_incoming_node: HashMap::new(),
+        };
+
+        assert!(check_transfer_node(&source, 2).is_ok());
+        let result = check_transfer_node(&source, 3);
+        assert!(
+            matches!(result, Err(Error::InvalidParameter(ref p, ref msg))
+            if p == "num_nodes" && msg.contains("2 available nodes"))
+        );
+        assert!(check_transfer_node(&source, 0).is_err());
+
+        assert!(check_transfer_replica(&source, "books", 1).is_ok());
+        assert!(check_transfer_replica(&source, "books", 2).is_err());
+        let result = check_transfer_replica(&source, "movies", 1);
+        assert!(
+            matches!(result, Err(Error::InvalidParameter(ref p, ref msg))
+            if p == "num_replicas" && msg.contains("0 replicas of movies"))
+        );
+    }
+
+    #[test]
+    fn test_partition_infos() {
+        let response = milvus::proto::milvus::ShowPartitionsResponse {
+            partition_names: vec!["_default".to_string(), "novels".to_string()],
+            partition_i_ds: vec![1, 2],
+            created_timestamps: vec![10, 20],
+            created_utc_timestamps: vec![100, 200],
+            ..Default::default()
+        };
+        let infos = partition_infos(response.clone()).unwrap();
+        assert_eq!(infos[1].name, "novels");
+        assert_eq!(infos[1].id, 2);
+        assert_eq!(infos[1].in_memory_percentage, None);
+
+        let result = partition_infos(milvus::proto::milvus::ShowPartitionsResponse {
+            partition_i_ds: vec![1],
+            ..response
+        });
+        assert!(matches!(result, Err(Error::Unexpected(msg)) if msg.contains("partition_ids")));
+    }
+
+    #[test]
+    fn test_check_import_files() {
+        assert!(check_import_files(&["rows/a.json", "rows/B.JSON"], true, false).is_ok());
+        assert!(check_import_files(&["cols/id.npy", "cols/vector.npy"], false, false).is_ok());
+        assert!(check_import_files(&[], true, false).is_err());
+        assert!(check_import_files(&["cols/id.npy"], true, false).is_err());
+        assert!(check_import_files(&["rows/a.json"], false, false).is_err());
+        // backups are directories
+        assert!(check_import_files(&["backup/insert_log/1"], true, true).is_ok());
+    }
+
+    #[test]
+    fn test_import_tasks() {
+        let tasks = import_tasks(vec![10, 11], &["a.json", "b.json"], true);
+        assert_eq!(
+            tasks,
+            vec![
+                ImportTask {
+                    id: 10,
+                    files: vec!["a.json".to_string()],
+                },
+                ImportTask {
+                    id: 11,
+                    files: vec!["b.json".to_string()],
+                },
+            ]
+        );
+
+        let tasks = import_tasks(vec![12], &["id.npy", "vector.npy"], false);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].files, vec!["id.npy", "vector.npy"]);
+
+        let options = ImportOptions::new()
+            .backup(true)
+            .end_ts(42)
+            .import_options();
+        let options: Vec<(&str, &str)> = options
+            .iter()
+            .map(|kv| (kv.key.as_str(), kv.value.as_str()))
+            .collect();
+        assert_eq!(options, vec![("backup", "true"), ("end_ts", "42")]);
+    }
+
+    #[tokio::test]
+    async fn test_add_field_request() {
+        let client = lazy_client();
+        let field = FieldSchema::new("rating", FieldType::Int32, None);
+        let request = client
+            .add_field_request("books", field.clone(), Some(Value::Int(3)))
+            .unwrap();
+        assert_eq!(request.collection_name, "books");
+        let schema = milvus::proto::schema::FieldSchema::decode(request.schema.as_slice()).unwrap();
+        assert_eq!(schema.name, "rating");
+        assert!(schema.nullable);
+        assert_eq!(
+            schema.default_value,
+            Some(milvus::proto::schema::ValueField {
+                data: Some(milvus::proto::schema::value_field::Data::IntData(3))
+            })
+        );
+
+        // nullable without a default value
+        let request = client
+            .add_field_request("books", field.clone(), None)
+            .unwrap();
+        let schema = milvus::proto::schema::FieldSchema::decode(request.schema.as_slice()).unwrap();
+        assert!(schema.nullable);
+        assert_eq!(schema.default_value, None);
+
+        let result = client.add_field_request("books", field, Some(Value::from("three")));
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::FieldWrongType(..)))
+        ));
+        let result = client.add_field_request(
+            "books",
+            FieldSchema::new("id", FieldType::Int64(true, false), None),
+            None,
+        );
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "field"));
+    }
+
+    #[tokio::test]
+    async fn test_alter_collection_field_request() {
+        let client = lazy_client().with_database("library");
+        let properties = HashMap::from([
+            ("mmap.enabled".to_string(), "true".to_string()),
+            ("max_length".to_string(), "512".to_string()),
+        ]);
+        let request = client.alter_collection_field_request("books", "title", properties);
+        assert_eq!(request.db_name, "library");
+        assert_eq!(request.collection_name, "books");
+        assert_eq!(request.field_name, "title");
+        let properties: Vec<(&str, &str)> = request
+            .properties
+            .iter()
+            .map(|kv| (kv.key.as_str(), kv.value.as_str()))
+            .collect();
+        assert_eq!(
+            properties,
+            vec![("max_length", "512"), ("mmap.enabled", "true")]
+        );
+
+        let result = client
+            .set_field_max_length("books", "title", MAX_VARCHAR_LENGTH + 1)
+            .await;
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "max_length"));
+    }
+
+    #[tokio::test]
+    async fn test_load_collection_request() {
+        let client = lazy_client();
+        let request = client.load_collection_request("books", LoadOptions::new());
+        assert_eq!(request.collection_name, "books");
+        assert_eq!(request.replica_number, 1);
+        assert!(request.resource_groups.is_empty());
+        assert!(request.load_fields.is_empty());
+        assert!(!request.skip_load_dynamic_field);
+        assert!(!request.refresh);
+
+        let options = LoadOptions::new()
+            .replica_number(2)
+            .resource_groups(vec!["rg1"])
+            .load_fields(vec!["id", "embedding"]);
+        let request = client.load_collection_request("books", options);
+        assert_eq!(request.replica_number, 2);
+        assert_eq!(request.resource_groups, vec!["rg1"]);
+        assert_eq!(request.load_fields, vec!["id", "embedding"]);
+
+        let options = LoadOptions::new()
+            .skip_load_dynamic_field(true)
+            .refresh(true);
+        let request = client.load_collection_request("books", options);
+        assert!(request.skip_load_dynamic_field);
+        assert!(request.refresh);
+    }
+
+    #[test]
+    fn test_primary_ids() {
+        let result = |field| QueryResult {
+            fields_data: vec![FieldData {
+                data_type: DataType::Int64 as i32,
+                field_name: "id".to_string(),
+                field_id: 0,
+                valid_data: vec![],
+                field,
+            }],
+            collection_name: "c".to_string(),
+        };
+
+        let ids = primary_ids(
+            &result(Some(Field::Scalars(ScalarField {
+                data: Some(ScalarFieldData::LongData(vec![3, 1])),
+            }))),
+            "id",
+        )
+        .unwrap();
+        assert!(matches!(ids, IdField::IntId(ids) if ids == vec![3, 1]));
+
+        let ids = primary_ids(
+            &result(Some(Field::Scalars(ScalarField {
+                data: Some(ScalarFieldData::StringData(vec!["a".to_string()])),
+            }))),
+            "id",
+        )
+        .unwrap();
+        assert!(matches!(ids, IdField::StrId(ids) if ids == vec!["a".to_string()]));
+
+        // no entity matched
+        let ids = primary_ids(&result(None), "id").unwrap();
+        assert!(matches!(ids, IdField::IntId(ids) if ids.is_empty()));
+
+        let result = primary_ids(&result(None), "pk");
+        assert!(matches!(result, Err(Error::Unexpected(_))));
+    }
+
+    #[test]
+    fn test_ids_expr() {
+        assert_eq!(
+            ids_expr("id", &IdField::IntId(vec![1, 2, 3])).unwrap(),
+            "id in [1, 2, 3]"
+        );
+        assert_eq!(
+            ids_expr(
+                "pk",
+                &IdField::StrId(vec!["a".to_string(), r#"say "hi" \o/"#.to_string()])
+            )
+            .unwrap(),
+            r#"pk in ["a", "say \"hi\" \\o/"]"#
+        );
+
+        let result = ids_expr("id", &IdField::IntId(vec![]));
+        assert!(matches!(result, Err(Error::InvalidParameter(..))));
+    }
+
+    #[tokio::test]
+    async fn test_collection_id_cache() {
+        let cache = CollectionIdCache::default();
+        let describes = std::sync::atomic::AtomicUsize::new(0);
+        let describe = || async {
+            describes.fetch_add(1, Ordering::SeqCst);
+            Ok(42)
+        };
+
+        assert_eq!(cache.get_or_fetch("", "books", describe).await.unwrap(), 42);
+        assert_eq!(cache.get_or_fetch("", "books", describe).await.unwrap(), 42);
+        assert_eq!(describes.load(Ordering::SeqCst), 1);
+
+        // another database is another collection
+        cache
+            .get_or_fetch("other", "books", describe)
+            .await
+            .unwrap();
+        assert_eq!(describes.load(Ordering::SeqCst), 2);
+
+        // dropped or renamed
+        cache.invalidate("", "books");
+        cache.get_or_fetch("", "books", describe).await.unwrap();
+        assert_eq!(describes.load(Ordering::SeqCst), 3);
+
+        // failures are not cached
+        let result = cache
+            .get_or_fetch("", "movies", || async {
+                Err(Error::Unexpected("not found".to_owned()))
+            })
+            .await;
+        assert!(result.is_err());
+        cache.get_or_fetch("", "movies", describe).await.unwrap();
+        assert_eq!(describes.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_segments() {
+        // canned get_flush_state results: not flushed twice, then flushed
+        let states = Mutex::new(vec![false, false, true].into_iter());
+        let requests = Mutex::new(vec![]);
+        let get_flush_state = |segment_ids: Vec<i64>| {
+            requests.lock().unwrap().push(segment_ids);
+            let flushed = states.lock().unwrap().next().unwrap();
+            async move { Ok(flushed) }
+        };
+
+        wait_for_segments(
+            vec![3, 1, 3, 2, 1],
+            Duration::from_millis(1),
+            Duration::from_secs(10),
+            get_flush_state,
+        )
+        .await
+        .unwrap();
+
+        // deduplicated and sorted, and no more polls once flushed
+        assert_eq!(requests.lock().unwrap().clone(), vec![vec![1, 2, 3]; 3]);
+
+        // nothing to wait for
+        wait_for_segments(
+            vec![],
+            Duration::from_millis(1),
+            Duration::from_secs(10),
+            |_| async { Err(Error::Unexpected("polled without segments".to_string())) },
+        )
+        .await
+        .unwrap();
+
+        let result = wait_for_segments(
+            vec![1],
+            Duration::from_millis(1),
+            Duration::from_millis(5),
+            |_| async { Ok(false) },
+        )
+        .await;
+        assert!(
+            matches!(result, Err(Error::Timeout(ref op, _)) if op == "wait_for_segments_flushed")
+        );
+    }
+
+    #[test]
+    fn test_collection_properties() {
+        assert_eq!(
+            ttl_property(Duration::from_secs(7 * 24 * 3600)).unwrap(),
+            ("collection.ttl.seconds".to_string(), "604800".to_string())
+        );
+        assert_eq!(
+            ttl_property(Duration::from_millis(1500)).unwrap().1,
+            "2".to_string()
+        );
+        assert!(matches!(
+            ttl_property(Duration::from_millis(500)),
+            Err(Error::InvalidParameter(ref p, _)) if p == "ttl"
+        ));
+        // disables the TTL
+        assert_eq!(
+            ttl_property(Duration::ZERO).unwrap(),
+            ("collection.ttl.seconds".to_string(), "0".to_string())
+        );
+        assert_eq!(
+            mmap_property(true),
+            ("mmap.enabled".to_string(), "true".to_string())
+        );
+
+        // read back from describe_collection
+        let mut metadata = CollectionMetadata {
+            name: "books".to_string(),
+            id: 1,
+            schema: None,
+            created_timestamp: 0,
+            created_utc_timestamp: 0,
+            shards_num: 1,
+            aliases: vec![],
+            consistency_level: crate::common::ConsistencyLevel::Bounded,
+            properties: HashMap::from([
+                ttl_property(Duration::from_secs(3600)).unwrap(),
+                mmap_property(false),
+            ]),
+            num_partitions: 0,
+            db_name: "default".to_string(),
+        };
+        assert_eq!(metadata.ttl(), Some(Duration::from_secs(3600)));
+        let (key, value) = ttl_property(Duration::ZERO).unwrap();
+        metadata.properties.insert(key, value);
+        assert_eq!(metadata.ttl(), None);
+        assert_eq!(metadata.mmap_enabled(), Some(false));
+        metadata.properties.clear();
+        assert_eq!(metadata.ttl(), None);
+        assert_eq!(metadata.mmap_enabled(), None);
+    }
+
+    #[test]
+    fn test_auth_token() {
+        assert_eq!(
+            auth_token(Some("root".to_string()), Some("Milvus".to_string())).unwrap(),
+            Some(general_purpose::STANDARD.encode("root:Milvus"))
+        );
+        assert_eq!(auth_token(None, None).unwrap(), None);
+        assert_eq!(
+            auth_token(Some(String::new()), Some(String::new())).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_auth_token_one_sided() {
+        let result = auth_token(Some("root".to_string()), None);
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "password"));
+
+        let result = auth_token(Some("root".to_string()), Some(String::new()));
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "password"));
+
+        let result = auth_token(None, Some("Milvus".to_string()));
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "username"));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Milvus server at VDB_HOST"]
+    async fn test_client_builder_handshake() -> Result<()> {
+        let client = Client::builder(get_vdb_host_address().as_str(), 19530)
+            .build()
+            .await?;
+        assert!(client.server_info().is_none());
+
+        let client = Client::builder(get_vdb_host_address().as_str(), 19530)
+            .handshake(true)
+            .build()
+            .await?;
+        let server_info = client.server_info().expect("negotiated by build");
+        assert_eq!(server_info.version(), client.get_version().await?);
+        // shared with the clones
+        assert!(client.clone().server_info().is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_client_builder_debug_redacts_secrets() {
+        let builder = Client::builder("localhost", 19530).credentials("root", "Milvus");
+        let debug = format!("{:?}", builder);
+        assert!(debug.contains(r#"username: Some("root")"#));
+        assert!(debug.contains(r#"password: Some("<redacted>")"#));
+        assert!(!debug.contains("Milvus"));
+
+        let debug = format!(
+            "{:?}",
+            Client::builder("localhost", 19530).api_key("key-123")
+        );
+        assert!(debug.contains(r#"api_key: Some("<redacted>")"#));
+        assert!(!debug.contains("key-123"));
+    }
+
+    #[tokio::test]
+    async fn test_client_builder() {
+        let builder = Client::builder("localhost", 19530);
+        assert_eq!(builder.token().unwrap(), None);
+        assert_eq!(
+            builder
+                .clone()
+                .credentials("root", "Milvus")
+                .token()
+                .unwrap(),
+            Some(general_purpose::STANDARD.encode("root:Milvus"))
+        );
+
+        let with_key = builder.clone().api_key("key");
+        assert_eq!(
+            with_key.token().unwrap(),
+            Some(general_purpose::STANDARD.encode("key"))
+        );
+        let result = with_key.credentials("root", "Milvus").token();
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "api_key"));
+        let result = builder.clone().api_key("").token();
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "api_key"));
+
+        let client = builder
+            .database("books")
+            .source_id(7)
+            .describe_cache_ttl(Duration::from_secs(60))
+            .configure(lazy_client());
+        assert_eq!(client.db_name, "books");
+        assert_eq!(client.source_id, 7);
+        assert_eq!(client.describe_cache.ttl, Duration::from_secs(60));
+        assert!(client.metrics.is_none());
+    }
+
+    /// A client whose channel connects on first use, to inspect the requests it builds without a server.
+    fn lazy_client() -> Client {
+        let conn = tonic::transport::Endpoint::from_static("http://127.0.0.1:19530").connect_lazy();
+        Client::with_channel(
+            conn,
+            AuthInterceptor {
+                token: Arc::new(RwLock::new(None)),
+                identifier: Arc::new(RwLock::new(None)),
+            },
+        )
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordedRpcs(Mutex<Vec<(&'static str, RpcOutcome)>>);
+    impl MetricsRecorder for Arc<RecordedRpcs> {
+        fn record_rpc(&self, method: &'static str, _latency: Duration, outcome: RpcOutcome) {
+            self.0.lock().unwrap().push((method, outcome));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_with_metrics() {
+        use milvus::proto::common::Status;
+
+        let recorded = Arc::new(RecordedRpcs::default());
+        let client = lazy_client().with_metrics(recorded.clone());
+
+        let ok = async { Ok(tonic::Response::new(Status::default())) };
+        client.call("flush", "c", ok).await.unwrap();
+        let failed =
+            async { Err::<tonic::Response<Status>, _>(tonic::Status::unavailable("down")) };
+        assert!(client.clone().call("search", "c", failed).await.is_err());
+        // a failure reported in the status of the reply
+        let not_found = async {
+            Ok(tonic::Response::new(Status {
+                error_code: ErrorCode::CollectionNotExists as i32,
+                code: 100,
+                reason: "collection not found".to_string(),
+                ..Default::default()
+            }))
+        };
+        client
+            .call("load_collection", "c", not_found)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *recorded.0.lock().unwrap(),
+            vec![
+                ("flush", RpcOutcome::Ok),
+                ("search", RpcOutcome::Grpc(tonic::Code::Unavailable)),
+                (
+                    "load_collection",
+                    RpcOutcome::Server(ErrorCode::CollectionNotExists, 100)
+                ),
+            ]
+        );
+        assert!(!RpcOutcome::Grpc(tonic::Code::Unavailable).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_new_msg() {
+        let client = lazy_client().with_source_id(7);
+        let first = client.new_msg(MsgType::Search);
+        assert_eq!(first.msg_type, MsgType::Search as i32);
+        assert_eq!(first.source_id, 7);
+
+        // the ids increase across the clones
+        let second = client.clone().new_msg(MsgType::Search);
+        assert_eq!(second.msg_id, first.msg_id + 1);
+        let request = client.get_load_state_request("events", vec![]);
+        assert_eq!(request.base.unwrap().msg_id, first.msg_id + 2);
+    }
+
+    #[tokio::test]
+    async fn test_describe_cache() {
+        let metadata = CollectionMetadata {
+            name: "books".to_string(),
+            id: 1,
+            schema: None,
+            created_timestamp: 0,
+            created_utc_timestamp: 0,
+            shards_num: 1,
+            aliases: vec![],
+            consistency_level: crate::common::ConsistencyLevel::Bounded,
+            properties: HashMap::new(),
+            num_partitions: 0,
+            db_name: "default".to_string(),
+        };
+
+        // disabled by default
+        let client = lazy_client();
+        let db = client.db_name.clone();
+        client.describe_cache.insert(&db, "books", &metadata);
+        assert!(client.describe_cache.get(&db, "books").is_none());
+
+        let client = lazy_client().with_describe_cache_ttl(Duration::from_secs(60));
+        client.describe_cache.insert(&db, "books", &metadata);
+        let cached = client.clone().describe_cache.get(&db, "books");
+        assert_eq!(cached.map(|metadata| metadata.id), Some(1));
+        assert!(client.describe_cache.get("other", "books").is_none());
+        client.invalidate("books");
+        assert!(client.describe_cache.get(&db, "books").is_none());
+
+        // described through an alias
+        let aliased = CollectionMetadata {
+            aliases: vec!["library".to_string()],
+            ..metadata.clone()
+        };
+        client.describe_cache.insert(&db, "library", &aliased);
+        client.describe_cache.insert("other", "library", &aliased);
+        client.invalidate("books");
+        assert!(client.describe_cache.get(&db, "library").is_none());
+        assert!(client.describe_cache.get("other", "library").is_some());
+        client.describe_cache.insert(&db, "books", &aliased);
+        client.invalidate("library");
+        assert!(client.describe_cache.get(&db, "books").is_none());
+
+        // expired
+        let cache = DescribeCache::new(Duration::from_millis(1));
+        cache.insert("default", "books", &metadata);
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(cache.get("default", "books").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_set_token() {
+        let client = lazy_client();
+        let mut interceptor = AuthInterceptor {
+            token: client.token.clone(),
+            identifier: client.identifier.clone(),
+        };
+        let authorization = |interceptor: &mut AuthInterceptor| {
+            interceptor
+                .call(Request::new(()))
+                .unwrap()
+                .metadata()
+                .get("authorization")
+                .map(|v| v.to_str().unwrap().to_string())
+        };
+        assert_eq!(authorization(&mut interceptor), None);
+
+        // shared with the clones
+        client.clone().set_api_key("key-1").unwrap();
+        assert_eq!(
+            authorization(&mut interceptor),
+            Some(general_purpose::STANDARD.encode("key-1"))
+        );
+        client.set_token(Some("cm9vdDpNaWx2dXM=")).unwrap();
+        assert_eq!(
+            authorization(&mut interceptor),
+            Some("cm9vdDpNaWx2dXM=".to_string())
+        );
+
+        assert!(matches!(
+            client.set_token(Some("line\nbreak")),
+            Err(Error::InvalidParameter(ref p, _)) if p == "token"
+        ));
+        assert!(client.set_api_key("").is_err());
+        client.set_token(None).unwrap();
+        assert_eq!(authorization(&mut interceptor), None);
+    }
+
+    #[tokio::test]
+    async fn test_get_collection_stats_request_db_name() {
+        let client = lazy_client();
+        assert_eq!(client.get_collection_stats_request("c1").db_name, "");
+
+        let client = client.with_database("analytics");
+        assert_eq!(client.database(), "analytics");
+        let request = client.get_collection_stats_request("c1");
+        assert_eq!(request.db_name, "analytics");
+        assert_eq!(request.collection_name, "c1");
+    }
+
+    #[tokio::test]
+    async fn test_get_flush_state_for_collection_request() {
+        let client = lazy_client().with_database("analytics");
+        let request =
+            client.get_flush_state_for_collection_request("books", 445_632_372_386_562_049);
+        assert_eq!(request.db_name, "analytics");
+        assert_eq!(request.collection_name, "books");
+        assert_eq!(request.flush_ts, 445_632_372_386_562_049);
+        assert!(request.segment_i_ds.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_load_state_request() {
+        let client = lazy_client().with_database("analytics");
+        let request = client.get_load_state_request("events", vec!["day_2024_06_01"]);
+        assert_eq!(request.db_name, "analytics");
+        assert_eq!(request.collection_name, "events");
+        assert_eq!(request.partition_names, vec!["day_2024_06_01"]);
+
+        assert_eq!(LoadState::from(3), LoadState::Loaded);
+        assert!(LoadState::from(3).is_loaded());
+        assert_eq!(LoadState::from(42), LoadState::NotExist);
+    }
+
+    #[tokio::test]
+    async fn test_insert_request_db_name() {
+        let client = lazy_client().with_database("analytics");
+        let fields_data = vec![FieldData {
+            data_type: DataType::Int64 as i32,
+            field_name: "id".to_string(),
+            field_id: 0,
+            valid_data: vec![],
+            field: Some(Field::Scalars(ScalarField {
+                data: Some(ScalarFieldData::LongData(vec![1, 2, 3])),
+            })),
+        }];
+
+        let request = client.insert_request("c1", "", fields_data).unwrap();
+        assert_eq!(request.db_name, "analytics");
+        assert_eq!(request.num_rows, 3);
+
+        // an explicit database wins over the client one
+        assert_eq!(client.resolve_db_name(None), "analytics");
+        assert_eq!(client.resolve_db_name(Some("other")), "other");
+    }
+
+    #[tokio::test]
+    async fn test_create_collection_request_name_mismatch() {
+        let client = lazy_client();
+        let schema = partition_key_schema();
+
+        let request = client
+            .create_collection_request("docs", schema.clone(), None, None)
+            .unwrap();
+        assert_eq!(request.collection_name, "docs");
+
+        let result = client.create_collection_request("other", schema.clone(), None, None);
+        assert!(matches!(result, Err(Error::InvalidParameter(..))));
+
+        let mut unnamed = schema;
+        unnamed.name = String::new();
+        let result = client.create_collection_request("", unnamed, None, None);
+        assert!(
+            matches!(result, Err(Error::InvalidParameter(_, ref msg)) if msg == "expected a non-empty collection name")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_collection_handle_query_request_primary_key() {
+        let handle = CollectionHandle {
+            client: lazy_client(),
+            name: "docs".to_string(),
+            schema: partition_key_schema(),
+        };
+
+        let count_id = |request: &milvus::proto::milvus::QueryRequest| {
+            request.output_fields.iter().filter(|f| *f == "id").count()
+        };
+
+        let request = handle
+            .query_request("id > 0", vec!["tenant"], None)
+            .unwrap();
+        assert_eq!(count_id(&request), 1);
+        assert!(request.output_fields.contains(&"tenant".to_string()));
+
+        // already requested, and even twice
+        let request = handle
+            .query_request("id > 0", vec!["id", "tenant", "id"], None)
+            .unwrap();
+        assert_eq!(count_id(&request), 1);
+        assert_eq!(request.output_fields.len(), 2);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Milvus server at VDB_HOST"]
+    async fn test_get_index_statistics() -> Result<()> {
+        let client = Client::new(
+            get_vdb_host_address().as_str(),
+            19530,
+            None,
+            None,
+            Some(std::time::Duration::from_secs(10)),
+        )
+        .await?;
+
+        let name = "test_get_index_statistics";
+        let schema = CollectionSchema::new(
+            name,
+            vec![
+                FieldSchema::new("id", FieldType::Int64(true, true), None),
+                FieldSchema::new("embedding", FieldType::FloatVector(4), None),
+            ],
+            None,
+        )?;
+        if client.has_collection(name).await? {
+            client.drop_collection(name).await?;
+        }
+        client.create_collection(name, schema, None, None).await?;
+
+        let params = HashMap::from([
+            ("index_type".to_string(), "FLAT".to_string()),
+            ("metric_type".to_string(), "L2".to_string()),
+        ]);
+        client
+            .create_index(name, "embedding", Some(params), "embedding_index")
+            .await?;
+
+        // nothing inserted yet
+        let progress = client
+            .get_index_statistics(None, name, "embedding_index")
+            .await?;
+        assert_eq!(progress.indexed_rows, 0);
+        assert_eq!(progress.total_rows, 0);
+
+        let indexes = client
+            .describe_index_statistics(None, name, "embedding_index")
+            .await?;
+        assert_eq!(indexes.len(), 1);
+        assert_eq!(indexes[0].field_name, "embedding");
+        assert_eq!(indexes[0].indexed_rows, 0);
+        assert_eq!(indexes[0].pending_index_rows, 0);
+        assert_eq!(indexes[0].total_rows, 0);
+
+        client.drop_collection(name).await
+    }
+
+    #[test]
+    fn test_search_text_vectors_hits() {
+        // the response of the server to the search of one vector with two hits
+        let data = milvus::proto::schema::SearchResultData {
+            num_queries: 1,
+            top_k: 2,
+            fields_data: vec![milvus::proto::schema::FieldData {
+                r#type: DataType::VarChar as i32,
+                field_name: "book_name".to_string(),
+                field: Some(milvus::proto::schema::field_data::Field::Scalars(
+                    milvus::proto::schema::ScalarField {
+                        data: Some(milvus::proto::schema::scalar_field::Data::StringData(
+                            milvus::proto::schema::StringArray {
+                                data: vec!["Dune".to_string(), "Hyperion".to_string()],
+                            },
+                        )),
+                    },
+                )),
+                ..Default::default()
+            }],
+            scores: vec![0.12, 0.34],
+            ids: Some(milvus::proto::schema::IDs {
+                id_field: Some(milvus::proto::schema::i_ds::IdField::IntId(
+                    milvus::proto::schema::LongArray { data: vec![7, 3] },
+                )),
+            }),
+            topks: vec![2],
+            ..Default::default()
+        };
+        let result = SearchResult {
+            results: Some(data.into()),
+            collection_name: "books".to_string(),
+            nq: 1,
+        };
+
+        let groups = result.per_query();
+        assert_eq!(groups.len(), 1);
+        let hits = &groups[0].hits;
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].id, Some(Value::Long(7)));
+        assert_eq!(hits[0].score, 0.12);
+        assert_eq!(
+            hits[0].fields.get("book_name"),
+            Some(&Value::String("Dune".to_string()))
+        );
+        assert_eq!(hits[1].id, Some(Value::Long(3)));
+        assert_eq!(hits[1].score, 0.34);
+        assert_eq!(
+            hits[1].fields.get("book_name"),
+            Some(&Value::String("Hyperion".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Milvus server at VDB_HOST"]
+    async fn test_describe_collections() -> Result<()> {
+        let client = Client::new(
+            get_vdb_host_address().as_str(),
+            19530,
+            None,
+            None,
+            Some(std::time::Duration::from_secs(10)),
+        )
+        .await?;
+
+        let name = "test_describe_collections";
+        let schema = CollectionSchema::new(
+            name,
+            vec![
+                FieldSchema::new("book_id", FieldType::Int64(true, false), None),
+                FieldSchema::new("book_intro", FieldType::FloatVector(2), None),
+            ],
+            None,
+        )?;
+        client
+            .create_collection_if_not_exists(name, schema, None, None)
+            .await?;
+
+        let results = client
+            .describe_collections(vec!["test_describe_collections_missing", name])
+            .await;
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[0], Err(Error::NotFound(_))));
+        assert_eq!(results[1].as_ref().unwrap().name, name);
+
+        client.drop_collection(name).await
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Milvus server at VDB_HOST"]
+    async fn test_create_collection_if_not_exists() -> Result<()> {
+        let client = Client::new(
+            get_vdb_host_address().as_str(),
+            19530,
+            None,
+            None,
+            Some(std::time::Duration::from_secs(10)),
+        )
+        .await?;
+
+        let name = "test_create_collection_if_not_exists";
+        let schema = CollectionSchema::new(
+            name,
+            vec![
+                FieldSchema::new("book_id", FieldType::Int64(true, false), None),
+                FieldSchema::new("book_intro", FieldType::FloatVector(2), None),
+            ],
+            None,
+        )?;
+        if client.has_collection(name).await? {
+            client.drop_collection(name).await?;
+        }
+
+        let outcome = client
+            .create_collection_if_not_exists(name, schema.clone(), None, None)
+            .await?;
+        assert!(outcome.created);
+        let outcome = client
+            .create_collection_if_not_exists(name, schema.clone(), None, None)
+            .await?;
+        assert!(!outcome.created);
+
+        // two concurrent deployments both succeed
+        client.drop_collection(name).await?;
+        let (first, second) = tokio::join!(
+            client.create_collection_if_not_exists(name, schema.clone(), None, None),
+            client.create_collection_if_not_exists(name, schema, None, None),
+        );
+        let (first, second) = (first?, second?);
+        assert!(first.created || second.created);
+
+        client.drop_collection(name).await
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Milvus server at VDB_HOST"]
+    async fn test_search_text_vectors() -> Result<()> {
+        let client = Client::new(
+            get_vdb_host_address().as_str(),
+            19530,
+            None,
+            None,
+            Some(std::time::Duration::from_secs(10)),
+        )
+        .await?;
+
+        let name = "test_search_text_vectors";
+        let schema = CollectionSchema::new(
+            name,
+            vec![
+                FieldSchema::new("book_id", FieldType::Int64(true, false), None),
+                FieldSchema::new("book_name", FieldType::VarChar(64, false, false), None),
+                FieldSchema::new("book_intro", FieldType::FloatVector(2), None),
+            ],
+            None,
+        )?;
+        if client.has_collection(name).await? {
+            client.drop_collection(name).await?;
+        }
+        client.create_collection(name, schema, None, None).await?;
+        let params = HashMap::from([
+            ("index_type".to_string(), "FLAT".to_string()),
+            ("metric_type".to_string(), "L2".to_string()),
+        ]);
+        client
+            .create_index(name, "book_intro", Some(params), "book_intro_index")
+            .await?;
+
+        let books = client.collection(name).await?;
+        let rows = [(1, "Dune", [0.1, 0.2]), (2, "Hyperion", [0.9, 0.8])]
+            .into_iter()
+            .map(|(id, book_name, intro)| {
+                HashMap::from([
+                    ("book_id".to_string(), Value::Long(id)),
+                    (
+                        "book_name".to_string(),
+                        Value::String(book_name.to_string()),
+                    ),
+                    ("book_intro".to_string(), Value::FloatVector(intro.to_vec())),
+                ])
+            })
+            .collect();
+        books.insert_rows("", rows).await?;
+        client.flush(vec![name]).await?;
+        client.load_collection(name, None).await?;
+
+        let groups = books
+            .search_text_vectors(vec![vec![0.1, 0.2]], "book_intro", 2, vec!["book_name"])
+            .await?;
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].hits.len(), 2);
+        assert_eq!(
+            groups[0].hits[0].fields.get("book_name"),
+            Some(&Value::String("Dune".to_string()))
+        );
+        assert!(groups[0].hits[0].score <= groups[0].hits[1].score);
+
+        client.drop_collection(name).await
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a Milvus server at VDB_HOST"]
+    async fn test_raw_client() -> Result<()> {
+        let client = Client::new(
+            get_vdb_host_address().as_str(),
+            19530,
+            None,
+            None,
+            Some(std::time::Duration::from_secs(10)),
+        )
+        .await?;
+
+        let response = client
+            .raw()
+            .get_version(milvus::proto::milvus::GetVersionRequest {})
+            .await?
+            .into_inner();
+        status_to_result_ctx(&response.status, "get_version")?;
+        assert_eq!(response.version, client.get_version().await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_pool_round_robin() {
+        let pool = ClientPool::from_clients(vec![
+            lazy_client().with_source_id(7),
+            lazy_client(),
+            lazy_client(),
+        ]);
+        assert_eq!(pool.size(), 3);
+
+        // the clients share the msg_id sequence, so the ids stay unique
+        let first = pool.clients[0].new_msg(MsgType::Search);
+        let second = pool.clients[1].new_msg(MsgType::Search);
+        assert_eq!(second.msg_id, first.msg_id + 1);
+        assert_eq!(second.source_id, 7);
+
+        let picks: Vec<*const Client> = (0..4).map(|_| pool.get() as *const Client).collect();
+        assert_ne!(picks[0], picks[1]);
+        assert_ne!(picks[1], picks[2]);
+        assert_eq!(picks[0], picks[3]);
+
+        // a write through one client is seen by the others
+        pool.get().record_write("books", 42);
+        assert_eq!(pool.get().last_write_ts("books"), Some(42));
+        assert_eq!(pool.get().last_write_ts("books"), Some(42));
+
+        let pool = pool.with_database("analytics");
+        assert!(pool.clients.iter().all(|c| c.database() == "analytics"));
+        assert_eq!(pool.get().last_write_ts("books"), None);
+    }
+
+    #[tokio::test]
+    async fn test_query_iterator_pages() {
+        let client = lazy_client();
+        let mut iterator = QueryIterator {
+            client,
+            collection_name: "books".to_string(),
+            expr: "year > 2000".to_string(),
+            output_fields: vec!["title".to_string(), "id".to_string()],
+            batch_size: 2,
+            primary_field: FieldSchema::new("id", FieldType::Int64(true, false), None),
+            last_pk: None,
+            exhausted: false,
+        };
+        let page = |ids: Vec<i64>| QueryResult {
+            fields_data: vec![FieldData {
+                data_type: DataType::Int64 as i32,
+                field_name: "id".to_string(),
+                field_id: 0,
+                valid_data: vec![],
+                field: Some(Field::Scalars(ScalarField {
+                    data: Some(ScalarFieldData::LongData(ids)),
+                })),
+            }],
+            collection_name: "books".to_string(),
+        };
+
+        let request = iterator.next_request().unwrap();
+        assert_eq!(request.expr, "year > 2000");
+        let params: HashMap<String, String> = request
+            .query_params
+            .into_iter()
+            .map(|p| (p.key, p.value))
+            .collect();
+        assert_eq!(params["limit"], "2");
+        assert_eq!(params["iterator"], "True");
+
+        // a full page, there may be more
+        assert_eq!(iterator.advance(&page(vec![3, 8])).unwrap(), 2);
+        assert!(!iterator.exhausted);
+        let request = iterator.next_request().unwrap();
+        assert_eq!(request.expr, "(year > 2000) and id > 8");
+
+        // a short page is the last one
+        assert_eq!(iterator.advance(&page(vec![13])).unwrap(), 1);
+        assert!(iterator.exhausted);
+        assert!(iterator.next_batch().await.unwrap().is_none());
+
+        // string keys are quoted
+        iterator.expr = String::new();
+        iterator.last_pk = Some(Value::String("a\"b".to_string()));
+        assert_eq!(iterator.next_request().unwrap().expr, r#"id > "a\"b""#);
+    }
+
+    #[tokio::test]
+    async fn test_delete_and_report_page_request() {
+        let client = lazy_client();
+        let params = |request: &milvus::proto::milvus::QueryRequest| -> HashMap<String, String> {
+            request
+                .query_params
+                .iter()
+                .map(|p| (p.key.clone(), p.value.clone()))
+                .collect()
+        };
+
+        let request = client
+            .delete_and_report_page_request("books", Some("2024"), "year < 2000", "id", None)
+            .unwrap();
+        assert_eq!(request.expr, "year < 2000");
+        assert_eq!(request.output_fields, vec!["id".to_string()]);
+        assert_eq!(request.partition_names, vec!["2024".to_string()]);
+        assert_eq!(
+            params(&request)["limit"],
+            DELETE_REPORT_BATCH_SIZE.to_string()
+        );
+        assert_eq!(params(&request)["iterator"], "True");
+
+        // the next page resumes after the last key
+        let request = client
+            .delete_and_report_page_request(
+                "books",
+                None,
+                "year < 2000",
+                "id",
+                Some(&Value::Long(42)),
+            )
+            .unwrap();
+        assert_eq!(request.expr, "(year < 2000) and id > 42");
+        assert!(request.partition_names.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_iterator_pages() {
+        let client = lazy_client();
+        let mut iterator = client
+            .search_iterator("books", vec![0.5, 1.0], "embedding", 3, 5)
+            .unwrap();
+        let hits = |ids: &[i64]| QueryHits {
+            hits: ids
+                .iter()
+                .map(|id| Hit {
+                    id: Some(Value::Long(*id)),
+                    score: *id as f32,
+                    fields: HashMap::new(),
+                })
+                .collect(),
+        };
+        let ids = |page: &QueryHits| -> Vec<Option<Value>> {
+            page.hits.iter().map(|hit| hit.id.clone()).collect()
+        };
+        let search_param = |request: &milvus::proto::milvus::SearchRequest, key: &str| {
+            request
+                .search_params
+                .iter()
+                .find(|kv| kv.key == key)
+                .map(|kv| kv.value.clone())
+                .unwrap()
+        };
+
+        let request = iterator.next_request(3).unwrap();
+        assert_eq!(search_param(&request, "offset"), "0");
+        assert_eq!(search_param(&request, "topk"), "3");
+        let page = iterator.advance(hits(&[1, 2, 3]), 3);
+        assert_eq!(ids(&page), ids(&hits(&[1, 2, 3])));
+
+        // the ranking shifted, 3 comes again
+        let request = iterator.next_request(2).unwrap();
+        assert_eq!(search_param(&request, "offset"), "3");
+        let page = iterator.advance(hits(&[3, 4]), 2);
+        assert_eq!(ids(&page), ids(&hits(&[4])));
+        assert!(!iterator.exhausted);
+
+        // capped at total
+        let page = iterator.advance(hits(&[5, 6]), 2);
+        assert_eq!(ids(&page), ids(&hits(&[5])));
+        assert!(iterator.next_batch().await.unwrap().is_none());
+
+        // a short page is the last one
+        let mut iterator = client
+            .search_iterator("books", vec![0.5, 1.0], "embedding", 3, 10)
+            .unwrap();
+        iterator.advance(hits(&[1]), 3);
+        assert!(iterator.exhausted);
+        assert!(iterator.next_batch().await.unwrap().is_none());
+
+        assert!(client
+            .search_iterator("books", vec![0.5], "embedding", 0, 10)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_hybrid_search_request() {
+        let client = lazy_client();
+        let reqs = vec![
+            AnnSearchRequest::new("text_embedding", vec![vec![0.1, 0.2], vec![0.3, 0.4]])
+                .limit(20)
+                .param("nprobe", 8),
+            AnnSearchRequest::new("image_embedding", vec![vec![0.5; 3], vec![0.6; 3]])
+                .expr("year > 2000"),
+        ];
+
+        let request = client
+            .hybrid_search_request(
+                "books",
+                reqs.clone(),
+                RerankStrategy::Weighted {
+                    weights: vec![0.75, 0.25],
+                },
+                5,
+                vec!["title"],
+            )
+            .unwrap();
+        assert_eq!(request.requests.len(), 2);
+        assert_eq!(request.output_fields, vec!["title".to_string()]);
+        let params = |pairs: &[KeyValuePair]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|p| (p.key.clone(), p.value.clone()))
+                .collect()
+        };
+        let text = params(&request.requests[0].search_params);
+        assert_eq!(text["anns_field"], "text_embedding");
+        assert_eq!(text["topk"], "20");
+        assert_eq!(text["params"], r#"{"nprobe":8}"#);
+        assert_eq!(request.requests[0].nq, 2);
+        assert_eq!(request.requests[1].dsl, "year > 2000");
+        let rank = params(&request.rank_params);
+        assert_eq!(rank["strategy"], "weighted");
+        assert_eq!(rank["params"], r#"{"weights":[0.75,0.25]}"#);
+        assert_eq!(rank["limit"], "5");
+
+        let request = client
+            .hybrid_search_request(
+                "books",
+                reqs.clone(),
+                RerankStrategy::Rrf { k: 60 },
+                5,
+                vec![],
+            )
+            .unwrap();
+        assert_eq!(params(&request.rank_params)["params"], r#"{"k":60}"#);
+
+        // one weight per search
+        let err = client
+            .hybrid_search_request(
+                "books",
+                reqs.clone(),
+                RerankStrategy::Weighted { weights: vec![1.0] },
+                5,
+                vec![],
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidParameter(name, _) if name == "weights"));
+
+        // as many query vectors in every search
+        let mut uneven = reqs;
+        uneven[1].vectors.pop();
+        let err = client
+            .hybrid_search_request("books", uneven, RerankStrategy::Rrf { k: 60 }, 5, vec![])
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidParameter(name, _) if name == "reqs[1]"));
+    }
+
+    #[tokio::test]
+    async fn test_search_with_request_range() {
+        use crate::common::MetricType;
+
+        let client = lazy_client();
+        // within cosine distance 0.2, i.e. similarity above 0.8
+        let options = SearchOptions::new()
+            .metric_type(MetricType::Cosine)
+            .range(0.8, 1.0)
+            .nprobe(8);
+
+        let request = client
+            .search_with_request("books", vec![vec![0.5, 1.0]], "embedding", options)
+            .unwrap();
+        let params = request
+            .search_params
+            .iter()
+            .find(|kv| kv.key == "params")
+            .unwrap();
+        assert_eq!(
+            params.value,
+            r#"{"nprobe":8,"radius":0.8,"range_filter":1.0}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_with_request() {
+        use crate::common::MetricType;
+
+        let client = lazy_client();
+        let options = SearchOptions::new()
+            .top_k(3)
+            .metric_type(MetricType::IP)
+            .nprobe(16)
+            .expr("year > 2000")
+            .output_fields(vec!["title"])
+            .consistency_level(crate::common::ConsistencyLevel::Eventually);
+
+        let request = client
+            .search_with_request(
+                "books",
+                vec![vec![0.5, 1.0], vec![-1.0, 2.0]],
+                "embedding",
+                options,
+            )
+            .unwrap();
+        assert_eq!(request.nq, 2);
+        assert_eq!(request.dsl, "year > 2000");
+        assert_eq!(request.output_fields, vec!["title".to_string()]);
+        assert_eq!(request.guarantee_timestamp, crate::utils::GTS_EVENTUALLY);
+        assert!(!request.use_default_consistency);
+
+        let params: HashMap<String, String> = request
+            .search_params
+            .into_iter()
+            .map(|kv| (kv.key, kv.value))
+            .collect();
+        assert_eq!(params["anns_field"], "embedding");
+        assert_eq!(params["topk"], "3");
+        assert_eq!(params["metric_type"], "IP");
+        assert_eq!(params["params"], r#"{"nprobe":16}"#);
+
+        let group = PlaceholderGroup::decode(request.placeholder_group.as_slice()).unwrap();
+        assert_eq!(group.placeholders[0].values.len(), 2);
+        assert_eq!(
+            group.placeholders[0].values[1],
+            [(-1.0f32).to_le_bytes(), 2.0f32.to_le_bytes()].concat()
+        );
+
+        // mismatched dimensions
+        let result = client.search_with_request(
+            "books",
+            vec![vec![0.5, 1.0], vec![-1.0]],
+            "embedding",
+            SearchOptions::new(),
+        );
+        assert!(matches!(result, Err(Error::InvalidParameter(..))));
+
+        // a binary metric
+        let result = client.search_with_request(
+            "books",
+            vec![vec![0.5, 1.0]],
+            "embedding",
+            SearchOptions::new().metric_type(MetricType::Hamming),
+        );
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "metric_type"));
+    }
+
+    #[tokio::test]
+    async fn test_search_binary_vectors_request() {
+        let client = lazy_client();
+        let options = SearchOptions::new()
+            .top_k(3)
+            .metric_type(MetricType::Hamming);
+
+        let request = client
+            .search_binary_vectors_request(
+                "books",
+                vec![vec![0b1010_1010, 0xff], vec![0, 1]],
+                "fingerprint",
+                options,
+            )
+            .unwrap();
+        assert_eq!(request.nq, 2);
+        let params: HashMap<String, String> = request
+            .search_params
+            .into_iter()
+            .map(|kv| (kv.key, kv.value))
+            .collect();
+        assert_eq!(params["anns_field"], "fingerprint");
+        assert_eq!(params["metric_type"], "HAMMING");
+
+        let group = PlaceholderGroup::decode(request.placeholder_group.as_slice()).unwrap();
+        assert_eq!(
+            group.placeholders[0].r#type,
+            PlaceholderType::BinaryVector as i32
+        );
+        assert_eq!(
+            group.placeholders[0].values,
+            vec![vec![0b1010_1010, 0xff], vec![0, 1]]
+        );
+
+        // the metric of the index is used without one
+        let request = client
+            .search_binary_vectors_request(
+                "books",
+                vec![vec![1]],
+                "fingerprint",
+                SearchOptions::new(),
+            )
+            .unwrap();
+        assert!(!request
+            .search_params
+            .iter()
+            .any(|kv| kv.key == "metric_type"));
+
+        // a float metric
+        let result = client.search_binary_vectors_request(
+            "books",
+            vec![vec![1]],
+            "fingerprint",
+            SearchOptions::new().metric_type(MetricType::L2),
+        );
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "metric_type"));
+
+        // mismatched dimensions
+        let result = client.search_binary_vectors_request(
+            "books",
+            vec![vec![1, 2], vec![3]],
+            "fingerprint",
+            SearchOptions::new(),
+        );
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "vectors[1]"));
+    }
+
+    #[tokio::test]
+    async fn test_search_with_request_session_ts() {
+        let client = lazy_client();
+        let options =
+            SearchOptions::new().consistency_level(crate::common::ConsistencyLevel::Session);
+        let vectors = vec![vec![0.5, 1.0]];
+
+        let request = client
+            .search_with_request("books", vectors.clone(), "embedding", options.clone())
+            .unwrap();
+        assert_eq!(request.guarantee_timestamp, 0);
+
+        // what `insert` records from its response, shared with the clones
+        let insert_ts = 445_632_372_386_562_049;
+        client.clone().record_write("books", insert_ts);
+        client.record_write("books", insert_ts - 1);
+
+        let request = client
+            .search_with_request("books", vectors.clone(), "embedding", options.clone())
+            .unwrap();
+        assert_eq!(request.guarantee_timestamp, insert_ts);
+
+        // other collections and databases are not affected
+        let request = client
+            .search_with_request("movies", vectors.clone(), "embedding", options.clone())
+            .unwrap();
+        assert_eq!(request.guarantee_timestamp, 0);
+        let request = client
+            .clone()
+            .with_database("other")
+            .search_with_request("books", vectors, "embedding", options)
+            .unwrap();
+        assert_eq!(request.guarantee_timestamp, 0);
+    }
+
+    #[tokio::test]
+    async fn test_mutation_result_records_session_ts() {
+        use milvus::proto::common::Status;
+
+        let client = lazy_client();
+        let insert_ts = 445_632_372_386_562_049;
+
+        // what `insert` and `delete` do with their reply
+        let response = milvus::proto::milvus::MutationResult {
+            status: Some(Status::default()),
+            timestamp: insert_ts,
+            ..Default::default()
+        };
+        let result = client.mutation_result("books", "insert", response).unwrap();
+        assert_eq!(result.timestamp, insert_ts);
+        assert_eq!(client.last_write_ts("books"), Some(insert_ts));
+
+        let options =
+            SearchOptions::new().consistency_level(crate::common::ConsistencyLevel::Session);
+        let request = client
+            .search_with_request("books", vec![vec![0.5, 1.0]], "embedding", options)
+            .unwrap();
+        assert_eq!(request.guarantee_timestamp, insert_ts);
+
+        // a failed mutation is not recorded
+        let response = milvus::proto::milvus::MutationResult {
+            status: Some(Status {
+                error_code: ErrorCode::UnexpectedError as i32,
+                reason: "down".to_string(),
+                ..Default::default()
+            }),
+            timestamp: insert_ts + 1,
+            ..Default::default()
+        };
+        assert!(client.mutation_result("books", "delete", response).is_err());
+        assert_eq!(client.last_write_ts("books"), Some(insert_ts));
+    }
+
+    #[tokio::test]
+    async fn test_query_with_request_pagination() {
+        let client = lazy_client();
+
+        let options = QueryOptions::new()
+            .output_fields(vec!["id"])
+            .offset(100)
+            .limit(50);
+        let request = client
+            .query_with_request("books", "id > 0", options)
+            .unwrap();
+        let params: HashMap<String, String> = request
+            .query_params
+            .into_iter()
+            .map(|kv| (kv.key, kv.value))
+            .collect();
+        assert_eq!(params["offset"], "100");
+        assert_eq!(params["limit"], "50");
+
+        let request = client
+            .query_with_request("books", "id > 0", QueryOptions::new())
+            .unwrap();
+        assert!(request.query_params.is_empty());
+
+        let result = client.query_with_request("books", "id > 0", QueryOptions::new().offset(10));
+        assert!(matches!(result, Err(Error::InvalidParameter(..))));
+    }
+
+    #[tokio::test]
+    async fn test_query_with_request_partition_names() {
+        let client = lazy_client();
+
+        // all the partitions
+        let request = client
+            .query_with_request("books", "id > 0", QueryOptions::new())
+            .unwrap();
+        assert!(request.partition_names.is_empty());
+
+        let options = QueryOptions::new().partition_names(vec!["novels"]);
+        let request = client
+            .query_with_request("books", "id > 0", options)
+            .unwrap();
+        assert_eq!(request.partition_names, vec!["novels"]);
+
+        let options = QueryOptions::new().partition_names(vec![]);
+        let result = client.query_with_request("books", "id > 0", options);
+        assert!(matches!(result, Err(Error::InvalidParameter(..))));
+    }
+
+    #[tokio::test]
+    async fn test_delete_request_partition_name() {
+        let client = lazy_client();
+
+        // all the partitions
+        let request = client.delete_request("books", None, "id in [1]").unwrap();
+        assert_eq!(request.partition_name, "");
+        assert_eq!(request.expr, "id in [1]");
+
+        let request = client
+            .delete_request("books", Some("novels"), "id in [1]")
+            .unwrap();
+        assert_eq!(request.partition_name, "novels");
+
+        let result = client.delete_request("books", Some(""), "id in [1]");
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "partition_name"));
+    }
+
+    #[tokio::test]
+    async fn test_query_with_request_consistency() {
+        let client = lazy_client();
+
+        let request = client
+            .query_with_request("books", "id > 0", QueryOptions::new())
+            .unwrap();
+        assert!(request.use_default_consistency);
+        assert_eq!(request.guarantee_timestamp, 0);
+
+        let options =
+            QueryOptions::new().consistency_level(crate::common::ConsistencyLevel::Eventually);
+        let request = client
+            .query_with_request("books", "id > 0", options)
+            .unwrap();
+        assert!(!request.use_default_consistency);
+        assert_eq!(request.guarantee_timestamp, crate::utils::GTS_EVENTUALLY);
+
+        let insert_ts = 445_632_372_386_562_049;
+        client.record_write("books", insert_ts);
+        let options =
+            QueryOptions::new().consistency_level(crate::common::ConsistencyLevel::Session);
+        let request = client
+            .query_with_request("books", "id > 0", options)
+            .unwrap();
+        assert_eq!(request.guarantee_timestamp, insert_ts);
+
+        let request = client
+            .query_with_request(
+                "books",
+                "id > 0",
+                QueryOptions::new().guarantee_timestamp(42),
+            )
+            .unwrap();
+        assert_eq!(
+            request.consistency_level,
+            ConsistencyLevel::Customized as i32
+        );
+        assert_eq!(request.guarantee_timestamp, 42);
     }
 
     #[tokio::test]
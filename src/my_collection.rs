@@ -1,6 +1,19 @@
-use crate::{common::ConsistencyLevel, schema::CollectionSchema};
+use crate::{
+    common::{ConsistencyLevel, IndexBuildState, MetricType, QuotaState},
+    my_client::{COLLECTION_TTL_SECONDS, MMAP_ENABLED},
+    my_error::{Error, Result},
+    schema::{CollectionSchema, SchemaError, DYNAMIC_FIELD_NAME},
+    utils::hybrid_ts_to_unix_ms,
+};
 // use milvus::proto::milvus::UserEntity;
+use milvus::proto::schema::DataType;
 use num_traits::FromPrimitive;
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    ops::Range,
+    time::Duration,
+};
 
 #[derive(Debug, Clone)]
 pub struct CollectionMetadata {
@@ -18,6 +31,29 @@ pub struct CollectionMetadata {
     pub aliases: Vec<String>,
     /// The consistency level that the collection used
     pub consistency_level: ConsistencyLevel,
+    /// The collection properties, e.g. `COLLECTION_TTL_SECONDS`
+    pub properties: HashMap<String, String>,
+    /// The number of partitions, created from the partition key
+    pub num_partitions: i64,
+    /// The database of the collection
+    pub db_name: String,
+}
+impl CollectionMetadata {
+    /// The time to live of the entities, if set by `Client::set_collection_ttl`. `None` once disabled with a zero TTL.
+    pub fn ttl(&self) -> Option<Duration> {
+        self.properties
+            .get(COLLECTION_TTL_SECONDS)
+            .and_then(|ttl| ttl.parse().ok())
+            .filter(|&secs: &u64| secs > 0)
+            .map(Duration::from_secs)
+    }
+
+    /// Whether the loaded data is memory-mapped, if set by `Client::set_collection_mmap`.
+    pub fn mmap_enabled(&self) -> Option<bool> {
+        self.properties
+            .get(MMAP_ENABLED)
+            .and_then(|enabled| enabled.parse().ok())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -26,8 +62,19 @@ pub struct CollectionInfo {
     pub id: i64,
     pub created_timestamp: u64,
     pub created_utc_timestamp: u64,
-    pub in_memory_percentage: i64,
-    pub query_service_available: bool,
+    /// The loading progress in percent, `None` if not reported by the server
+    pub in_memory_percentage: Option<i64>,
+    /// Whether the collection is loaded and can be searched, `None` if not reported by the server
+    pub query_service_available: Option<bool>,
+}
+
+/// The loading progress of a collection or of some of its partitions, in percent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadingProgress {
+    /// Progress of the initial load
+    pub progress: i64,
+    /// Progress of a load issued again on a loaded collection to make newly inserted data searchable
+    pub refresh_progress: i64,
 }
 
 #[derive(Debug, Clone)]
@@ -36,7 +83,8 @@ pub struct PartitionInfo {
     pub id: i64,
     pub created_timestamp: u64,
     pub created_utc_timestamp: u64,
-    pub in_memory_percentage: i64,
+    /// The loading progress in percent, `None` if not reported by the server
+    pub in_memory_percentage: Option<i64>,
 }
 
 #[derive(Debug, Clone)]
@@ -47,13 +95,36 @@ pub struct IndexInfo {
     pub field_name: String,
     pub indexed_rows: i64,
     pub total_rows: i64,
-    pub state: i32,
+    /// The number of rows waiting to be indexed
+    pub pending_index_rows: i64,
+    pub state: IndexBuildState,
+    /// Only set when `state` is `Failed`
     pub index_state_fail_reason: String,
 }
+impl From<milvus::proto::milvus::IndexDescription> for IndexInfo {
+    fn from(description: milvus::proto::milvus::IndexDescription) -> Self {
+        IndexInfo {
+            index_name: description.index_name,
+            index_id: description.index_id,
+            params: description
+                .params
+                .into_iter()
+                .map(|kv| (kv.key, kv.value))
+                .collect(),
+            field_name: description.field_name,
+            indexed_rows: description.indexed_rows,
+            total_rows: description.total_rows,
+            pending_index_rows: description.pending_index_rows,
+            state: description.state.into(),
+            index_state_fail_reason: description.index_state_fail_reason,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct IndexState {
-    pub state: i32,
+    pub state: IndexBuildState,
+    /// Only set when `state` is `Failed`
     pub fail_reason: String,
 }
 
@@ -68,15 +139,172 @@ pub struct FieldData {
     pub data_type: i32,
     pub field_name: String,
     pub field_id: i64,
+    /// Whether each row has a value, empty if they all do; the data only holds the values of the valid rows on insert
+    pub valid_data: Vec<bool>,
     pub field: Option<Field>,
 }
+impl FieldData {
+    /// Build the data of the field `field_name` of type `data_type`, checking `field` holds data of that type.
+    ///
+    /// `Int8` and `Int16` fields hold their own data or `IntData`, whose values must fit in the type, and `String` fields hold
+    /// `StringData`.
+    /// A mismatch returns `SchemaError::FieldWrongType` instead of being rejected by the server on insert.
+    pub fn try_new(field_name: &str, data_type: DataType, field: Field) -> Result<FieldData> {
+        let actual = field.data_type();
+        let compatible = actual == data_type
+            || matches!(
+                (data_type, actual),
+                (DataType::Int8 | DataType::Int16, DataType::Int32)
+                    | (DataType::String, DataType::VarChar)
+            );
+        if !compatible {
+            return Err(Error::from(SchemaError::FieldWrongType(
+                field_name.to_owned(),
+                data_type,
+                actual,
+            )));
+        }
+
+        let field_data = FieldData {
+            data_type: data_type as i32,
+            field_name: field_name.to_owned(),
+            field_id: 0,
+            valid_data: vec![],
+            field: Some(field),
+        };
+        field_data.check_int_range()?;
+
+        Ok(field_data)
+    }
+
+    /// The number of rows held by the field, null ones included.
+    pub fn num_rows(&self) -> usize {
+        if !self.valid_data.is_empty() {
+            return self.valid_data.len();
+        }
+
+        self.data_len()
+    }
+
+    /// Whether the field is null at `row`.
+    pub fn is_null(&self, row: usize) -> bool {
+        self.valid_data.get(row) == Some(&false)
+    }
+
+    /// The number of values of the data.
+    fn data_len(&self) -> usize {
+        match &self.field {
+            Some(Field::Scalars(field)) => field.data.as_ref().map(|d| d.len()).unwrap_or(0),
+            Some(Field::Vectors(field)) => field.num_rows(),
+            None => 0,
+        }
+    }
+
+    /// The range of the data holding the values of the rows in `range`.
+    ///
+    /// The server returns a value, possibly a placeholder, for every row, while the inserted data only holds the values of
+    /// the valid rows.
+    fn data_range(&self, range: Range<usize>) -> Range<usize> {
+        if self.valid_data.is_empty() || self.data_len() == self.valid_data.len() {
+            return range;
+        }
+
+        let valid_before = |row: usize| self.valid_data[..row].iter().filter(|v| **v).count();
+        valid_before(range.start)..valid_before(range.end)
+    }
+
+    /// Check the values of an `Int8` or `Int16` field fit in the declared type.
+    ///
+    /// Milvus transfers these types widened to `IntData`, so an out of range value would otherwise only be detected by the server.
+    pub fn check_int_range(&self) -> Result<()> {
+        let data_type = DataType::from_i32(self.data_type);
+        let range = match data_type {
+            Some(DataType::Int8) => i8::MIN as i32..=i8::MAX as i32,
+            Some(DataType::Int16) => i16::MIN as i32..=i16::MAX as i32,
+            _ => return Ok(()),
+        };
+
+        if let Some(Field::Scalars(ScalarField {
+            data: Some(ScalarFieldData::IntData(values)),
+        })) = &self.field
+        {
+            if let Some(value) = values.iter().find(|v| !range.contains(*v)) {
+                return Err(Error::from(SchemaError::ValueOutOfRange(
+                    self.field_name.clone(),
+                    *value as i64,
+                    data_type.unwrap(),
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The value of the field at `row`, or `None` if the field has no such row or is null at `row`.
+    pub fn value(&self, row: usize) -> Option<Value> {
+        if row >= self.num_rows() || self.is_null(row) {
+            return None;
+        }
+
+        let index = self.data_range(row..row + 1).start;
+        match self.field.as_ref()? {
+            Field::Scalars(field) => field.data.as_ref()?.get(index),
+            Field::Vectors(field) => field.get(index),
+        }
+    }
+
+    /// The rows of a float vector field, e.g. the stored embeddings returned as an output field.
+    ///
+    /// Returns `None` if the field holds no float vectors or has no dimension.
+    pub fn as_float_vectors(&self) -> Option<Vec<Vec<f32>>> {
+        match self.field.as_ref()? {
+            Field::Vectors(VectorField {
+                dim,
+                data: Some(VectorFieldData::FloatVec(v)),
+            }) if *dim > 0 => Some(v.chunks_exact(*dim as usize).map(<[f32]>::to_vec).collect()),
+            _ => None,
+        }
+    }
+
+    /// Copy the rows in `range` into a new `FieldData`.
+    ///
+    /// Panics if `range` is out of the rows of the field.
+    pub fn slice_rows(&self, range: Range<usize>) -> FieldData {
+        let data_range = self.data_range(range.clone());
+        let valid_data = if self.valid_data.is_empty() {
+            vec![]
+        } else {
+            self.valid_data[range].to_vec()
+        };
+
+        FieldData {
+            data_type: self.data_type,
+            field_name: self.field_name.clone(),
+            field_id: self.field_id,
+            valid_data,
+            field: self.field.as_ref().map(|field| match field {
+                Field::Scalars(field) => Field::Scalars(ScalarField {
+                    data: field
+                        .data
+                        .as_ref()
+                        .map(|data| data.slice_rows(data_range.clone())),
+                }),
+                Field::Vectors(field) => Field::Vectors(field.slice_rows(data_range.clone())),
+            }),
+        }
+    }
+}
 impl From<FieldData> for milvus::proto::schema::FieldData {
     fn from(field_data: FieldData) -> Self {
         Self {
             r#type: field_data.data_type,
+            // the name is reserved to the dynamic field
+            is_dynamic: field_data.field_name == DYNAMIC_FIELD_NAME,
             field_name: field_data.field_name,
             field_id: field_data.field_id,
             field: field_data.field.map(|f| f.into()),
+            valid_data: field_data.valid_data,
+            ..Default::default()
         }
     }
 }
@@ -86,6 +314,7 @@ impl From<milvus::proto::schema::FieldData> for FieldData {
             data_type: field_data.r#type,
             field_name: field_data.field_name,
             field_id: field_data.field_id,
+            valid_data: field_data.valid_data,
             field: field_data.field.map(|f| f.into()),
         }
     }
@@ -96,6 +325,16 @@ pub enum Field {
     Scalars(ScalarField),
     Vectors(VectorField),
 }
+impl Field {
+    /// The data type the data naturally maps to, `DataType::None` without data.
+    pub fn data_type(&self) -> DataType {
+        match self {
+            Field::Scalars(field) => field.data.as_ref().map(|data| data.data_type()),
+            Field::Vectors(field) => field.data.as_ref().map(|data| data.data_type()),
+        }
+        .unwrap_or(DataType::None)
+    }
+}
 impl From<Field> for milvus::proto::schema::field_data::Field {
     fn from(field: Field) -> Self {
         match field {
@@ -147,12 +386,89 @@ impl From<milvus::proto::schema::ScalarField> for ScalarField {
 #[derive(Debug, Clone, PartialEq)]
 pub enum ScalarFieldData {
     BoolData(Vec<bool>),
+    /// The data of an `Int8` field, sent widened to `IntData` as Milvus expects
+    Int8Data(Vec<i8>),
+    /// The data of an `Int16` field, sent widened to `IntData` as Milvus expects
+    Int16Data(Vec<i16>),
     IntData(Vec<i32>),
     LongData(Vec<i64>),
     FloatData(Vec<f32>),
     DoubleData(Vec<f64>),
     StringData(Vec<String>),
     BytesData(Vec<Vec<u8>>),
+    /// One serialized JSON document per row, e.g. the undeclared fields of the dynamic field
+    JsonData(Vec<Vec<u8>>),
+}
+impl ScalarFieldData {
+    /// The data type the data naturally maps to, `Int32` for `IntData` and `VarChar` for `StringData`.
+    pub fn data_type(&self) -> DataType {
+        match self {
+            ScalarFieldData::BoolData(_) => DataType::Bool,
+            ScalarFieldData::Int8Data(_) => DataType::Int8,
+            ScalarFieldData::Int16Data(_) => DataType::Int16,
+            ScalarFieldData::IntData(_) => DataType::Int32,
+            ScalarFieldData::LongData(_) => DataType::Int64,
+            ScalarFieldData::FloatData(_) => DataType::Float,
+            ScalarFieldData::DoubleData(_) => DataType::Double,
+            ScalarFieldData::StringData(_) => DataType::VarChar,
+            ScalarFieldData::BytesData(_) => DataType::None,
+            ScalarFieldData::JsonData(_) => DataType::Json,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            ScalarFieldData::BoolData(v) => v.len(),
+            ScalarFieldData::Int8Data(v) => v.len(),
+            ScalarFieldData::Int16Data(v) => v.len(),
+            ScalarFieldData::IntData(v) => v.len(),
+            ScalarFieldData::LongData(v) => v.len(),
+            ScalarFieldData::FloatData(v) => v.len(),
+            ScalarFieldData::DoubleData(v) => v.len(),
+            ScalarFieldData::StringData(v) => v.len(),
+            ScalarFieldData::BytesData(v) => v.len(),
+            ScalarFieldData::JsonData(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The value at `row`, or `None` if out of range.
+    pub fn get(&self, row: usize) -> Option<Value> {
+        match self {
+            ScalarFieldData::BoolData(v) => v.get(row).map(|v| Value::Bool(*v)),
+            ScalarFieldData::Int8Data(v) => v.get(row).map(|v| Value::Int(*v as i32)),
+            ScalarFieldData::Int16Data(v) => v.get(row).map(|v| Value::Int(*v as i32)),
+            ScalarFieldData::IntData(v) => v.get(row).map(|v| Value::Int(*v)),
+            ScalarFieldData::LongData(v) => v.get(row).map(|v| Value::Long(*v)),
+            ScalarFieldData::FloatData(v) => v.get(row).map(|v| Value::Float(*v)),
+            ScalarFieldData::DoubleData(v) => v.get(row).map(|v| Value::Double(*v)),
+            ScalarFieldData::StringData(v) => v.get(row).map(|v| Value::String(v.clone())),
+            ScalarFieldData::BytesData(v) => v.get(row).map(|v| Value::Bytes(v.clone())),
+            // JSON is always UTF-8
+            ScalarFieldData::JsonData(v) => v
+                .get(row)
+                .map(|v| Value::String(String::from_utf8_lossy(v).into_owned())),
+        }
+    }
+
+    /// Copy the rows in `range` into a new `ScalarFieldData`.
+    pub fn slice_rows(&self, range: Range<usize>) -> ScalarFieldData {
+        match self {
+            ScalarFieldData::BoolData(v) => ScalarFieldData::BoolData(v[range].to_vec()),
+            ScalarFieldData::Int8Data(v) => ScalarFieldData::Int8Data(v[range].to_vec()),
+            ScalarFieldData::Int16Data(v) => ScalarFieldData::Int16Data(v[range].to_vec()),
+            ScalarFieldData::IntData(v) => ScalarFieldData::IntData(v[range].to_vec()),
+            ScalarFieldData::LongData(v) => ScalarFieldData::LongData(v[range].to_vec()),
+            ScalarFieldData::FloatData(v) => ScalarFieldData::FloatData(v[range].to_vec()),
+            ScalarFieldData::DoubleData(v) => ScalarFieldData::DoubleData(v[range].to_vec()),
+            ScalarFieldData::StringData(v) => ScalarFieldData::StringData(v[range].to_vec()),
+            ScalarFieldData::BytesData(v) => ScalarFieldData::BytesData(v[range].to_vec()),
+            ScalarFieldData::JsonData(v) => ScalarFieldData::JsonData(v[range].to_vec()),
+        }
+    }
 }
 impl From<ScalarFieldData> for milvus::proto::schema::scalar_field::Data {
     fn from(data: ScalarFieldData) -> Self {
@@ -160,6 +476,16 @@ impl From<ScalarFieldData> for milvus::proto::schema::scalar_field::Data {
             ScalarFieldData::BoolData(v) => milvus::proto::schema::scalar_field::Data::BoolData({
                 milvus::proto::schema::BoolArray { data: v }
             }),
+            ScalarFieldData::Int8Data(v) => milvus::proto::schema::scalar_field::Data::IntData(
+                milvus::proto::schema::IntArray {
+                    data: v.into_iter().map(i32::from).collect(),
+                },
+            ),
+            ScalarFieldData::Int16Data(v) => milvus::proto::schema::scalar_field::Data::IntData(
+                milvus::proto::schema::IntArray {
+                    data: v.into_iter().map(i32::from).collect(),
+                },
+            ),
             ScalarFieldData::IntData(v) => milvus::proto::schema::scalar_field::Data::IntData(
                 milvus::proto::schema::IntArray { data: v },
             ),
@@ -182,9 +508,22 @@ impl From<ScalarFieldData> for milvus::proto::schema::scalar_field::Data {
             ScalarFieldData::BytesData(v) => milvus::proto::schema::scalar_field::Data::BytesData(
                 milvus::proto::schema::BytesArray { data: v },
             ),
+            ScalarFieldData::JsonData(v) => milvus::proto::schema::scalar_field::Data::JsonData(
+                milvus::proto::schema::JsonArray { data: v },
+            ),
         }
     }
 }
+impl From<Vec<i8>> for ScalarFieldData {
+    fn from(v: Vec<i8>) -> Self {
+        ScalarFieldData::Int8Data(v)
+    }
+}
+impl From<Vec<i16>> for ScalarFieldData {
+    fn from(v: Vec<i16>) -> Self {
+        ScalarFieldData::Int16Data(v)
+    }
+}
 impl From<milvus::proto::schema::scalar_field::Data> for ScalarFieldData {
     fn from(data: milvus::proto::schema::scalar_field::Data) -> Self {
         match data {
@@ -209,6 +548,9 @@ impl From<milvus::proto::schema::scalar_field::Data> for ScalarFieldData {
             milvus::proto::schema::scalar_field::Data::BytesData(v) => {
                 ScalarFieldData::BytesData(v.data)
             }
+            milvus::proto::schema::scalar_field::Data::JsonData(v) => {
+                ScalarFieldData::JsonData(v.data)
+            }
         }
     }
 }
@@ -218,6 +560,190 @@ pub struct VectorField {
     pub dim: i64,
     pub data: Option<VectorFieldData>,
 }
+impl VectorField {
+    /// The number of rows held by the field.
+    ///
+    /// `dim` is the number of components of a row; for binary vectors a component is a bit, so a row takes `dim / 8` bytes.
+    /// Sparse vectors take one element per row whatever `dim`.
+    pub fn num_rows(&self) -> usize {
+        let row_len = self.row_len();
+        if row_len == 0 {
+            return 0;
+        }
+
+        match &self.data {
+            Some(VectorFieldData::BinaryVec(v)) => v.len() / row_len,
+            Some(VectorFieldData::FloatVec(v)) => v.len() / row_len,
+            Some(VectorFieldData::Int8Vec(v)) => v.len() / row_len,
+            Some(VectorFieldData::SparseFloatVec(v)) => v.len(),
+            None => 0,
+        }
+    }
+
+    /// The vector at `row`, or `None` if out of range.
+    pub fn get(&self, row: usize) -> Option<Value> {
+        if row >= self.num_rows() {
+            return None;
+        }
+
+        let row_len = self.row_len();
+        let range = row * row_len..(row + 1) * row_len;
+        match self.data.as_ref()? {
+            VectorFieldData::BinaryVec(v) => Some(Value::BinaryVector(v[range].to_vec())),
+            VectorFieldData::FloatVec(v) => Some(Value::FloatVector(v[range].to_vec())),
+            VectorFieldData::Int8Vec(v) => Some(Value::Int8Vector(v[range].to_vec())),
+            VectorFieldData::SparseFloatVec(v) => Some(Value::SparseFloatVector(v[row].clone())),
+        }
+    }
+
+    /// Copy the rows in `range` into a new `VectorField`.
+    pub fn slice_rows(&self, range: Range<usize>) -> VectorField {
+        let row_len = self.row_len();
+        let range = range.start * row_len..range.end * row_len;
+
+        VectorField {
+            dim: self.dim,
+            data: self.data.as_ref().map(|data| match data {
+                VectorFieldData::BinaryVec(v) => {
+                    VectorFieldData::BinaryVec(v[range.clone()].to_vec())
+                }
+                VectorFieldData::FloatVec(v) => {
+                    VectorFieldData::FloatVec(v[range.clone()].to_vec())
+                }
+                VectorFieldData::Int8Vec(v) => VectorFieldData::Int8Vec(v[range.clone()].to_vec()),
+                VectorFieldData::SparseFloatVec(v) => {
+                    VectorFieldData::SparseFloatVec(v[range.clone()].to_vec())
+                }
+            }),
+        }
+    }
+
+    /// The number of elements of the data a row takes.
+    fn row_len(&self) -> usize {
+        let row_len = match &self.data {
+            Some(VectorFieldData::BinaryVec(_)) => self.dim / 8,
+            Some(VectorFieldData::SparseFloatVec(_)) => 1,
+            _ => self.dim,
+        };
+
+        row_len.max(0) as usize
+    }
+
+    /// Build a binary vector field from the bit-packed rows of dimension `dim` laid end to end in `data`.
+    ///
+    /// `dim` is in bits: it must be a positive multiple of 8, and a row takes `dim / 8` bytes of `data`.
+    pub fn from_binary(dim: i64, data: Vec<u8>) -> Result<VectorField> {
+        if dim <= 0 || dim % 8 != 0 {
+            return Err(Error::InvalidParameter(
+                "dim".to_owned(),
+                format!("expected a positive multiple of 8, got {}", dim),
+            ));
+        }
+        if data.len() % (dim / 8) as usize != 0 {
+            return Err(Error::InvalidParameter(
+                "data".to_owned(),
+                format!(
+                    "length {} is not a multiple of {} bytes, the row size of dimension {}",
+                    data.len(),
+                    dim / 8,
+                    dim
+                ),
+            ));
+        }
+
+        Ok(VectorField {
+            dim,
+            data: Some(VectorFieldData::BinaryVec(data)),
+        })
+    }
+
+    /// Build an int8 vector field from the rows of dimension `dim` laid end to end in `data`.
+    ///
+    /// `dim` must be positive and divide the length of `data`.
+    pub fn from_int8(dim: i64, data: Vec<i8>) -> Result<VectorField> {
+        if dim <= 0 {
+            return Err(Error::InvalidParameter(
+                "dim".to_owned(),
+                format!("expected a positive dimension, got {}", dim),
+            ));
+        }
+        if data.len() % dim as usize != 0 {
+            return Err(Error::InvalidParameter(
+                "data".to_owned(),
+                format!(
+                    "length {} is not a multiple of dimension {}",
+                    data.len(),
+                    dim
+                ),
+            ));
+        }
+
+        Ok(VectorField {
+            dim,
+            data: Some(VectorFieldData::Int8Vec(data)),
+        })
+    }
+
+    /// Build a float vector field from rows, e.g. the embeddings output by a model.
+    ///
+    /// The dimension is the one of the first row; all rows must have the same, non-zero dimension.
+    pub fn from_rows(rows: Vec<Vec<f32>>) -> Result<VectorField> {
+        let dim = rows_dim(&rows)?;
+
+        Ok(VectorField {
+            dim: dim as i64,
+            data: Some(rows.into_iter().flatten().collect()),
+        })
+    }
+
+    /// Build a float vector field from `f64` rows.
+    ///
+    /// Milvus stores float vectors as `f32`, so every value is narrowed to `f32` and loses precision beyond what `f32` can represent.
+    /// All rows must have the same, non-zero dimension, and every value must be finite after the cast.
+    pub fn from_f64_rows(rows: Vec<Vec<f64>>) -> Result<VectorField> {
+        let dim = rows_dim(&rows)?;
+
+        let mut data = Vec::with_capacity(rows.len() * dim);
+        for (i, row) in rows.into_iter().enumerate() {
+            for v in row {
+                let v32 = v as f32;
+                if !v32.is_finite() {
+                    return Err(Error::InvalidParameter(
+                        format!("rows[{}]", i),
+                        format!("non-finite value {}", v),
+                    ));
+                }
+                data.push(v32);
+            }
+        }
+
+        Ok(VectorField {
+            dim: dim as i64,
+            data: Some(VectorFieldData::FloatVec(data)),
+        })
+    }
+}
+/// The dimension of the first row, checking all rows have it and it is not zero.
+fn rows_dim<T>(rows: &[Vec<T>]) -> Result<usize> {
+    let dim = match rows.first() {
+        Some(row) if !row.is_empty() => row.len(),
+        _ => {
+            return Err(Error::InvalidParameter(
+                "rows".to_owned(),
+                "expected at least one non-empty row".to_owned(),
+            ))
+        }
+    };
+
+    if let Some((i, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != dim) {
+        return Err(Error::InvalidParameter(
+            format!("rows[{}]", i),
+            format!("dimension {} does not match {}", row.len(), dim),
+        ));
+    }
+
+    Ok(dim)
+}
 impl From<VectorField> for milvus::proto::schema::VectorField {
     fn from(field: VectorField) -> Self {
         milvus::proto::schema::VectorField {
@@ -238,6 +764,21 @@ impl From<milvus::proto::schema::VectorField> for VectorField {
 pub enum VectorFieldData {
     BinaryVec(Vec<u8>),
     FloatVec(Vec<f32>),
+    /// Quantized vectors, `dim` values per row
+    Int8Vec(Vec<i8>),
+    /// One sparse vector per row
+    SparseFloatVec(Vec<SparseFloatVector>),
+}
+impl VectorFieldData {
+    /// The data type of the vectors.
+    pub fn data_type(&self) -> DataType {
+        match self {
+            VectorFieldData::BinaryVec(_) => DataType::BinaryVector,
+            VectorFieldData::FloatVec(_) => DataType::FloatVector,
+            VectorFieldData::Int8Vec(_) => DataType::Int8Vector,
+            VectorFieldData::SparseFloatVec(_) => DataType::SparseFloatVector,
+        }
+    }
 }
 impl From<VectorFieldData> for milvus::proto::schema::vector_field::Data {
     fn from(data: VectorFieldData) -> Self {
@@ -248,9 +789,35 @@ impl From<VectorFieldData> for milvus::proto::schema::vector_field::Data {
             VectorFieldData::FloatVec(v) => milvus::proto::schema::vector_field::Data::FloatVector(
                 milvus::proto::schema::FloatArray { data: v },
             ),
+            VectorFieldData::Int8Vec(v) => milvus::proto::schema::vector_field::Data::Int8Vector(
+                v.into_iter().map(|v| v as u8).collect(),
+            ),
+            VectorFieldData::SparseFloatVec(v) => {
+                milvus::proto::schema::vector_field::Data::SparseFloatVector(
+                    milvus::proto::schema::SparseFloatArray {
+                        dim: v.iter().map(|row| row.dim()).max().unwrap_or(0),
+                        contents: v.iter().map(|row| row.to_bytes()).collect(),
+                    },
+                )
+            }
         }
     }
 }
+impl From<&[f32]> for VectorFieldData {
+    fn from(v: &[f32]) -> Self {
+        VectorFieldData::FloatVec(v.to_vec())
+    }
+}
+impl<const N: usize> From<[f32; N]> for VectorFieldData {
+    fn from(v: [f32; N]) -> Self {
+        VectorFieldData::FloatVec(v.to_vec())
+    }
+}
+impl FromIterator<f32> for VectorFieldData {
+    fn from_iter<I: IntoIterator<Item = f32>>(iter: I) -> Self {
+        VectorFieldData::FloatVec(iter.into_iter().collect())
+    }
+}
 impl From<milvus::proto::schema::vector_field::Data> for VectorFieldData {
     fn from(data: milvus::proto::schema::vector_field::Data) -> Self {
         match data {
@@ -260,9 +827,290 @@ impl From<milvus::proto::schema::vector_field::Data> for VectorFieldData {
             milvus::proto::schema::vector_field::Data::FloatVector(v) => {
                 VectorFieldData::FloatVec(v.data)
             }
+            milvus::proto::schema::vector_field::Data::Int8Vector(v) => {
+                VectorFieldData::Int8Vec(v.into_iter().map(|v| v as i8).collect())
+            }
+            milvus::proto::schema::vector_field::Data::SparseFloatVector(v) => {
+                VectorFieldData::SparseFloatVec(
+                    v.contents
+                        .iter()
+                        .map(|row| SparseFloatVector::from_bytes(row))
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+/// A sparse float vector, e.g. the term weights of a BM25 or SPLADE embedding.
+///
+/// Only the non-zero components are kept, `values[i]` being the component at `indices[i]`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SparseFloatVector {
+    pub indices: Vec<u32>,
+    pub values: Vec<f32>,
+}
+impl SparseFloatVector {
+    /// Build a sparse vector from `(index, value)` pairs, in any order.
+    pub fn new(mut components: Vec<(u32, f32)>) -> Self {
+        components.sort_by_key(|(index, _)| *index);
+
+        let (indices, values) = components.into_iter().unzip();
+        SparseFloatVector { indices, values }
+    }
+
+    /// The dimension the vector spans, i.e. its largest index plus one.
+    pub fn dim(&self) -> i64 {
+        self.indices.iter().max().map_or(0, |i| *i as i64 + 1)
+    }
+
+    /// The wire format of a row: each component as a little-endian `u32` index then a little-endian `f32` value.
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        self.indices
+            .iter()
+            .zip(&self.values)
+            .flat_map(|(index, value)| index.to_le_bytes().into_iter().chain(value.to_le_bytes()))
+            .collect()
+    }
+
+    /// Parse a row in the wire format, ignoring a trailing incomplete component.
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Self {
+        let (indices, values) = bytes
+            .chunks_exact(8)
+            .map(|chunk| {
+                (
+                    u32::from_le_bytes(chunk[..4].try_into().unwrap()),
+                    f32::from_le_bytes(chunk[4..].try_into().unwrap()),
+                )
+            })
+            .unzip();
+
+        SparseFloatVector { indices, values }
+    }
+}
+
+/// The values of a field to insert with `Client::insert_columns`, converted to the data type declared by the schema.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnData {
+    Bool(Vec<bool>),
+    /// The values of any integer field, which must fit in its type
+    Int(Vec<i64>),
+    /// The values of a `Float` or a `Double` field
+    Float(Vec<f64>),
+    /// The values of a `VarChar` or a `String` field
+    String(Vec<String>),
+    FloatVector(Vec<Vec<f32>>),
+    /// Bit-packed binary vectors, 8 dimensions per byte
+    BinaryVector(Vec<Vec<u8>>),
+    Int8Vector(Vec<Vec<i8>>),
+    SparseFloatVector(Vec<SparseFloatVector>),
+}
+impl ColumnData {
+    pub fn len(&self) -> usize {
+        match self {
+            ColumnData::Bool(v) => v.len(),
+            ColumnData::Int(v) => v.len(),
+            ColumnData::Float(v) => v.len(),
+            ColumnData::String(v) => v.len(),
+            ColumnData::FloatVector(v) => v.len(),
+            ColumnData::BinaryVector(v) => v.len(),
+            ColumnData::Int8Vector(v) => v.len(),
+            ColumnData::SparseFloatVector(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The values of the column, as the field `field_name` of type `data_type` holds them.
+    pub(crate) fn into_values(self, field_name: &str, data_type: DataType) -> Result<Vec<Value>> {
+        let values = match self {
+            ColumnData::Bool(v) => v.into_iter().map(Value::Bool).collect(),
+            ColumnData::Int(v) => match data_type {
+                // the Int8 and Int16 ranges are checked by `FieldData::check_int_range`
+                DataType::Int8 | DataType::Int16 | DataType::Int32 => v
+                    .into_iter()
+                    .map(|v| {
+                        i32::try_from(v).map(Value::Int).map_err(|_| {
+                            Error::from(SchemaError::ValueOutOfRange(
+                                field_name.to_string(),
+                                v,
+                                data_type,
+                            ))
+                        })
+                    })
+                    .collect::<Result<_>>()?,
+                _ => v.into_iter().map(Value::Long).collect(),
+            },
+            ColumnData::Float(v) => match data_type {
+                DataType::Float => v.into_iter().map(|v| Value::Float(v as f32)).collect(),
+                _ => v.into_iter().map(Value::Double).collect(),
+            },
+            ColumnData::String(v) => v.into_iter().map(Value::String).collect(),
+            ColumnData::FloatVector(v) => v.into_iter().map(Value::FloatVector).collect(),
+            ColumnData::BinaryVector(v) => v.into_iter().map(Value::BinaryVector).collect(),
+            ColumnData::Int8Vector(v) => v.into_iter().map(Value::Int8Vector).collect(),
+            ColumnData::SparseFloatVector(v) => {
+                v.into_iter().map(Value::SparseFloatVector).collect()
+            }
+        };
+
+        Ok(values)
+    }
+}
+
+/// A single typed value of a field.
+///
+/// Floats compare and hash by their bit pattern, so a `Value` can be used as a `HashMap` key.
+#[derive(Debug, Clone)]
+pub enum Value {
+    Bool(bool),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    FloatVector(Vec<f32>),
+    /// Bit-packed binary vector, 8 dimensions per byte
+    BinaryVector(Vec<u8>),
+    Int8Vector(Vec<i8>),
+    SparseFloatVector(SparseFloatVector),
+}
+impl Value {
+    /// The data type the value naturally maps to.
+    pub fn data_type(&self) -> DataType {
+        match self {
+            Value::Bool(_) => DataType::Bool,
+            Value::Int(_) => DataType::Int32,
+            Value::Long(_) => DataType::Int64,
+            Value::Float(_) => DataType::Float,
+            Value::Double(_) => DataType::Double,
+            Value::String(_) => DataType::VarChar,
+            Value::Bytes(_) => DataType::None,
+            Value::FloatVector(_) => DataType::FloatVector,
+            Value::BinaryVector(_) => DataType::BinaryVector,
+            Value::Int8Vector(_) => DataType::Int8Vector,
+            Value::SparseFloatVector(_) => DataType::SparseFloatVector,
+        }
+    }
+
+    /// Whether the value is a vector.
+    pub fn is_vector(&self) -> bool {
+        matches!(
+            self,
+            Value::FloatVector(_)
+                | Value::BinaryVector(_)
+                | Value::Int8Vector(_)
+                | Value::SparseFloatVector(_)
+        )
+    }
+}
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::Long(a), Value::Long(b)) => a == b,
+            (Value::Float(a), Value::Float(b)) => a.to_bits() == b.to_bits(),
+            (Value::Double(a), Value::Double(b)) => a.to_bits() == b.to_bits(),
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::Bytes(a), Value::Bytes(b)) => a == b,
+            (Value::FloatVector(a), Value::FloatVector(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.to_bits() == b.to_bits())
+            }
+            (Value::BinaryVector(a), Value::BinaryVector(b)) => a == b,
+            (Value::Int8Vector(a), Value::Int8Vector(b)) => a == b,
+            (Value::SparseFloatVector(a), Value::SparseFloatVector(b)) => {
+                a.indices == b.indices
+                    && a.values.len() == b.values.len()
+                    && a.values
+                        .iter()
+                        .zip(&b.values)
+                        .all(|(a, b)| a.to_bits() == b.to_bits())
+            }
+            _ => false,
         }
     }
 }
+impl Eq for Value {}
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::Bool(v) => v.hash(state),
+            Value::Int(v) => v.hash(state),
+            Value::Long(v) => v.hash(state),
+            Value::Float(v) => v.to_bits().hash(state),
+            Value::Double(v) => v.to_bits().hash(state),
+            Value::String(v) => v.hash(state),
+            Value::Bytes(v) => v.hash(state),
+            Value::FloatVector(v) => v.iter().for_each(|v| v.to_bits().hash(state)),
+            Value::BinaryVector(v) => v.hash(state),
+            Value::Int8Vector(v) => v.hash(state),
+            Value::SparseFloatVector(v) => {
+                v.indices.hash(state);
+                v.values.iter().for_each(|v| v.to_bits().hash(state));
+            }
+        }
+    }
+}
+impl From<bool> for Value {
+    fn from(v: bool) -> Self {
+        Value::Bool(v)
+    }
+}
+impl From<i32> for Value {
+    fn from(v: i32) -> Self {
+        Value::Int(v)
+    }
+}
+impl From<i64> for Value {
+    fn from(v: i64) -> Self {
+        Value::Long(v)
+    }
+}
+impl From<f32> for Value {
+    fn from(v: f32) -> Self {
+        Value::Float(v)
+    }
+}
+impl From<f64> for Value {
+    fn from(v: f64) -> Self {
+        Value::Double(v)
+    }
+}
+impl From<String> for Value {
+    fn from(v: String) -> Self {
+        Value::String(v)
+    }
+}
+impl From<&str> for Value {
+    fn from(v: &str) -> Self {
+        Value::String(v.to_string())
+    }
+}
+impl From<Vec<u8>> for Value {
+    fn from(v: Vec<u8>) -> Self {
+        Value::Bytes(v)
+    }
+}
+impl From<Vec<f32>> for Value {
+    fn from(v: Vec<f32>) -> Self {
+        Value::FloatVector(v)
+    }
+}
+impl From<Vec<i8>> for Value {
+    fn from(v: Vec<i8>) -> Self {
+        Value::Int8Vector(v)
+    }
+}
+impl From<SparseFloatVector> for Value {
+    fn from(v: SparseFloatVector) -> Self {
+        Value::SparseFloatVector(v)
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct MutationResult {
@@ -273,13 +1121,90 @@ pub struct MutationResult {
     pub insert_cnt: i64,
     pub delete_cnt: i64,
     pub upsert_cnt: i64,
+    /// Hybrid timestamp of the mutation
     pub timestamp: u64,
 }
+impl MutationResult {
+    /// The wall-clock time of the mutation, in milliseconds since the Unix epoch.
+    pub fn unix_timestamp_ms(&self) -> u64 {
+        hybrid_ts_to_unix_ms(self.timestamp)
+    }
+
+    /// The result of a mutation of no row.
+    pub(crate) fn empty() -> Self {
+        MutationResult {
+            id: None,
+            succ_index: vec![],
+            err_index: vec![],
+            acknowledged: true,
+            insert_cnt: 0,
+            delete_cnt: 0,
+            upsert_cnt: 0,
+            timestamp: 0,
+        }
+    }
+
+    /// Append the result of the next request of a mutation split in several, whose rows follow the `row_offset` rows of
+    /// the previous requests.
+    ///
+    /// The row indexes of `other` are shifted by `row_offset`, so they index the rows of the whole mutation.
+    pub(crate) fn append(&mut self, other: MutationResult, row_offset: u32) {
+        let ids = other.id.and_then(|id| id.id_field);
+        self.id = match (self.id.take().and_then(|id| id.id_field), ids) {
+            (Some(IdField::IntId(mut all)), Some(IdField::IntId(ids))) => {
+                all.extend(ids);
+                Some(IdField::IntId(all))
+            }
+            (Some(IdField::StrId(mut all)), Some(IdField::StrId(ids))) => {
+                all.extend(ids);
+                Some(IdField::StrId(all))
+            }
+            (None, ids) => ids,
+            // a collection has a single primary key type
+            (all, _) => all,
+        }
+        .map(|id_field| Id {
+            id_field: Some(id_field),
+        });
+
+        self.succ_index
+            .extend(other.succ_index.iter().map(|i| i + row_offset));
+        self.err_index
+            .extend(other.err_index.iter().map(|i| i + row_offset));
+        self.acknowledged &= other.acknowledged;
+        self.insert_cnt += other.insert_cnt;
+        self.delete_cnt += other.delete_cnt;
+        self.upsert_cnt += other.upsert_cnt;
+        self.timestamp = self.timestamp.max(other.timestamp);
+    }
+}
+
+/// The progress of `Client::insert_stream_with_progress`, reported after each chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InsertProgress {
+    /// The number of chunks inserted so far
+    pub chunks: usize,
+    /// The number of rows inserted so far
+    pub rows: u64,
+}
 
 #[derive(Debug, Clone)]
 pub struct Id {
     id_field: Option<IdField>,
 }
+impl Id {
+    /// The primary keys, `None` if the server returned none.
+    pub fn id_field(&self) -> Option<&IdField> {
+        self.id_field.as_ref()
+    }
+}
+impl From<IdField> for Id {
+    fn from(id_field: IdField) -> Self {
+        Id {
+            id_field: Some(id_field),
+        }
+    }
+}
 impl From<Id> for milvus::proto::schema::IDs {
     fn from(id: Id) -> Self {
         milvus::proto::schema::IDs {
@@ -299,6 +1224,46 @@ pub enum IdField {
     IntId(Vec<i64>),
     StrId(Vec<String>),
 }
+impl IdField {
+    pub fn len(&self) -> usize {
+        match self {
+            IdField::IntId(v) => v.len(),
+            IdField::StrId(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The primary key at `row`, `None` if out of range.
+    pub fn get(&self, row: usize) -> Option<IdValue> {
+        match self {
+            IdField::IntId(v) => v.get(row).map(|id| IdValue::Int(*id)),
+            IdField::StrId(v) => v.get(row).map(|id| IdValue::Str(id.clone())),
+        }
+    }
+
+    /// The primary keys, one per row.
+    pub fn iter(&self) -> impl Iterator<Item = IdValue> + '_ {
+        (0..self.len()).filter_map(|row| self.get(row))
+    }
+}
+
+/// A single primary key of an `IdField`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IdValue {
+    Int(i64),
+    Str(String),
+}
+impl From<IdValue> for Value {
+    fn from(id: IdValue) -> Self {
+        match id {
+            IdValue::Int(v) => Value::Long(v),
+            IdValue::Str(v) => Value::String(v),
+        }
+    }
+}
 impl From<IdField> for milvus::proto::schema::i_ds::IdField {
     fn from(id_field: IdField) -> Self {
         match id_field {
@@ -328,6 +1293,31 @@ impl From<milvus::proto::schema::i_ds::IdField> for IdField {
 pub struct SearchResult {
     pub results: Option<SearchResultData>,
     pub collection_name: String,
+    /// The number of queries sent
+    pub nq: i64,
+}
+impl SearchResult {
+    /// Split the flat result into the hits of each query, in the order the queries were sent.
+    ///
+    /// There is always one group per query, even when the server returns no result for some or all of them, so the groups line
+    /// up with the input vectors.
+    pub fn per_query(&self) -> Vec<QueryHits> {
+        let mut groups: Vec<QueryHits> = match &self.results {
+            Some(data) => data
+                .to_records()
+                .into_iter()
+                .map(|hits| QueryHits { hits })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let nq = self.nq.max(0) as usize;
+        if groups.len() < nq {
+            groups.resize(nq, QueryHits::default());
+        }
+
+        groups
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -355,6 +1345,117 @@ impl From<milvus::proto::schema::SearchResultData> for SearchResultData {
         }
     }
 }
+impl SearchResultData {
+    /// Split the flat columns into the records of each query, using the cumulative `topks` offsets.
+    ///
+    /// A record is a `Hit`: the id, `Value::Long` or `Value::String` depending on the primary key, the score and the output
+    /// fields by name. Unlike `SearchResult::per_query`, queries without a `topks` entry are not padded.
+    pub fn to_records(&self) -> Vec<Vec<Hit>> {
+        let mut offset = 0;
+        self.topks
+            .iter()
+            .map(|topk| {
+                let rows = offset..offset + (*topk).max(0) as usize;
+                offset = rows.end;
+                rows.map(|row| self.hit(row)).collect()
+            })
+            .collect()
+    }
+
+    /// The hit at `row` of the flat result.
+    fn hit(&self, row: usize) -> Hit {
+        let id = self
+            .id
+            .as_ref()
+            .and_then(|id| id.id_field.as_ref())
+            .and_then(|id_field| id_field.get(row))
+            .map(Value::from);
+
+        Hit {
+            id,
+            score: self.scores.get(row).copied().unwrap_or_default(),
+            fields: self
+                .fields_data
+                .iter()
+                .filter_map(|field| Some((field.field_name.clone(), field.value(row)?)))
+                .collect(),
+        }
+    }
+}
+
+/// A single hit of a search query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hit {
+    /// The primary key, `Value::Long` or `Value::String`
+    pub id: Option<Value>,
+    /// The distance or similarity, depending on the metric type
+    pub score: f32,
+    /// The output fields, by field name
+    pub fields: HashMap<String, Value>,
+}
+
+/// The hits of one query of a search, ordered as returned by the server.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueryHits {
+    pub hits: Vec<Hit>,
+}
+impl QueryHits {
+    /// Keep the hits scoring `threshold` or better: at most `threshold` for a distance, at least `threshold` for a similarity.
+    ///
+    /// `metric` must be the metric of the search. The hits keep their order.
+    pub fn filter_by_score(&self, threshold: f32, metric: MetricType) -> QueryHits {
+        let keep = |score: f32| {
+            if metric.is_similarity() {
+                score >= threshold
+            } else {
+                score <= threshold
+            }
+        };
+
+        QueryHits {
+            hits: self
+                .hits
+                .iter()
+                .filter(|hit| keep(hit.score))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Group the hits by the value of the scalar output field `field_name`, e.g. to dedupe chunks by document id.
+    ///
+    /// Hits keep their order within a group. Fails if a hit lacks the field, or the field is a vector.
+    pub fn group_by(&self, field_name: &str) -> Result<HashMap<Value, Vec<Hit>>> {
+        let mut groups: HashMap<Value, Vec<Hit>> = HashMap::new();
+        for hit in &self.hits {
+            let value = match hit.fields.get(field_name) {
+                Some(value) if value.is_vector() => {
+                    return Err(Error::InvalidParameter(
+                        "field_name".to_owned(),
+                        format!("{} is a vector field", field_name),
+                    ))
+                }
+                Some(value) => value.clone(),
+                None => {
+                    return Err(Error::InvalidParameter(
+                        "field_name".to_owned(),
+                        format!("{} is not an output field", field_name),
+                    ))
+                }
+            };
+            groups.entry(value).or_default().push(hit.clone());
+        }
+
+        Ok(groups)
+    }
+}
+
+/// Whether `Client::create_collection_if_not_exists` created the collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CreateCollectionOutcome {
+    /// `false` if the collection already existed, and was left as is
+    pub created: bool,
+}
 
 #[derive(Debug, Clone)]
 pub struct FlushResult {
@@ -362,6 +1463,8 @@ pub struct FlushResult {
     pub collection_segment_ids: std::collections::HashMap<String, Vec<i64>>,
     pub flush_collection_segment_ids: std::collections::HashMap<String, Vec<i64>>,
     pub collection_seal_times: std::collections::HashMap<String, i64>,
+    /// The flush timestamp of each collection, to poll with `Client::get_flush_state_for_collection`
+    pub collection_flush_ts: std::collections::HashMap<String, u64>,
 }
 
 #[derive(Debug, Clone)]
@@ -474,6 +1577,103 @@ pub struct Metrics {
     /// metrics from which component
     pub component_name: String,
 }
+impl Metrics {
+    /// Parse the JSON response.
+    pub fn parse(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::from_str(&self.response)?)
+    }
+
+    /// The hardware usage and rates of the nodes in the response to a `MetricsRequest::system_info()` request.
+    ///
+    /// The fields missing from the response of a node are left to their default value.
+    pub fn nodes(&self) -> Result<Vec<NodeMetrics>> {
+        let response = self.parse()?;
+        let nodes = match response.get("nodes_info").and_then(|v| v.as_array()) {
+            Some(nodes) => nodes,
+            None => return Ok(vec![]),
+        };
+
+        Ok(nodes
+            .iter()
+            .filter_map(|node| node.get("infos"))
+            .map(NodeMetrics::from_infos)
+            .collect())
+    }
+}
+
+/// The request of `get_metrics`, in the JSON format expected by the server.
+#[derive(Debug, Clone)]
+pub struct MetricsRequest {
+    metric_type: String,
+}
+impl MetricsRequest {
+    /// Request the topology of the cluster, with the hardware usage and the rates of each node, see `Metrics::nodes`.
+    pub fn system_info() -> Self {
+        Self {
+            metric_type: "system_info".to_string(),
+        }
+    }
+}
+impl From<MetricsRequest> for String {
+    fn from(request: MetricsRequest) -> Self {
+        serde_json::json!({ "metric_type": request.metric_type }).to_string()
+    }
+}
+
+/// The metrics of a node, e.g. a proxy or a query node.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NodeMetrics {
+    pub name: String,
+    /// e.g. `proxy` or `querynode`
+    pub node_type: String,
+    pub cpu_core_count: i64,
+    /// In percent
+    pub cpu_core_usage: f64,
+    /// In bytes
+    pub memory: u64,
+    /// In bytes
+    pub memory_usage: u64,
+    /// In bytes
+    pub disk: f64,
+    /// In bytes
+    pub disk_usage: f64,
+    /// The rates of the node by label, e.g. `Search` or `Insert` in requests per second
+    pub rates: HashMap<String, f64>,
+}
+impl NodeMetrics {
+    fn from_infos(infos: &serde_json::Value) -> Self {
+        let str_field = |v: &serde_json::Value, key| {
+            v.get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string()
+        };
+        let hardware = &infos["hardware_infos"];
+        let rates = infos["quota_metrics"]["Rms"]
+            .as_array()
+            .map(|rates| {
+                rates
+                    .iter()
+                    .filter_map(|rate| {
+                        Some((rate["Label"].as_str()?.to_string(), rate["Rate"].as_f64()?))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        NodeMetrics {
+            name: str_field(infos, "name"),
+            node_type: str_field(infos, "type"),
+            cpu_core_count: hardware["cpu_core_count"].as_i64().unwrap_or_default(),
+            cpu_core_usage: hardware["cpu_core_usage"].as_f64().unwrap_or_default(),
+            memory: hardware["memory"].as_u64().unwrap_or_default(),
+            memory_usage: hardware["memory_usage"].as_u64().unwrap_or_default(),
+            disk: hardware["disk"].as_f64().unwrap_or_default(),
+            disk_usage: hardware["disk_usage"].as_f64().unwrap_or_default(),
+            rates,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct ComponentState {
@@ -540,6 +1740,13 @@ pub struct CompactionMergeInfo {
     pub target: i64,
 }
 
+/// An import task and the files it imports, see `Client::import`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportTask {
+    pub id: i64,
+    pub files: Vec<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ImportStateResult {
     pub state: ImportState,
@@ -626,6 +1833,7 @@ pub struct GrantEntity {
     pub object: Option<ObjectEntity>,
     pub object_name: String,
     pub grantor: Option<GrantorEntity>,
+    pub db_name: String,
 }
 impl From<milvus::proto::milvus::GrantEntity> for GrantEntity {
     fn from(grant_entity: milvus::proto::milvus::GrantEntity) -> Self {
@@ -634,6 +1842,7 @@ impl From<milvus::proto::milvus::GrantEntity> for GrantEntity {
             object: grant_entity.object.map(|object| object.into()),
             object_name: grant_entity.object_name,
             grantor: grant_entity.grantor.map(|grantor| grantor.into()),
+            db_name: grant_entity.db_name,
         }
     }
 }
@@ -644,6 +1853,7 @@ impl From<GrantEntity> for milvus::proto::milvus::GrantEntity {
             object: grant_entity.object.map(|object| object.into()),
             object_name: grant_entity.object_name,
             grantor: grant_entity.grantor.map(|grantor| grantor.into()),
+            db_name: grant_entity.db_name,
         }
     }
 }
@@ -746,8 +1956,786 @@ impl From<RoleEntity> for milvus::proto::milvus::RoleEntity {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct ServerInfo {
+    /// The server version, e.g. `v2.3.4`
+    pub build_tags: String,
+    pub build_time: String,
+    pub git_commit: String,
+    pub go_version: String,
+    pub deploy_mode: String,
+    pub reserved: std::collections::HashMap<String, String>,
+}
+impl ServerInfo {
+    /// The version of the server.
+    pub fn version(&self) -> &str {
+        &self.build_tags
+    }
+}
+impl From<milvus::proto::common::ServerInfo> for ServerInfo {
+    fn from(info: milvus::proto::common::ServerInfo) -> Self {
+        ServerInfo {
+            build_tags: info.build_tags,
+            build_time: info.build_time,
+            git_commit: info.git_commit,
+            go_version: info.go_version,
+            deploy_mode: info.deploy_mode,
+            reserved: info.reserved,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Health {
     pub is_healthy: bool,
     pub reasons: Vec<String>,
+    /// The limits in effect because of exceeded quotas, empty if none
+    pub quota_states: Vec<QuotaState>,
+}
+impl Health {
+    /// Whether the server denies writes, e.g. because the disk quota is reached.
+    pub fn is_write_denied(&self) -> bool {
+        self.quota_states.contains(&QuotaState::DenyToWrite)
+    }
+
+    /// Whether the server denies reads.
+    pub fn is_read_denied(&self) -> bool {
+        self.quota_states.contains(&QuotaState::DenyToRead)
+    }
+}
+impl From<milvus::proto::milvus::CheckHealthResponse> for Health {
+    fn from(response: milvus::proto::milvus::CheckHealthResponse) -> Self {
+        Health {
+            is_healthy: response.is_healthy,
+            reasons: response.reasons,
+            quota_states: response
+                .quota_states
+                .into_iter()
+                .map(QuotaState::from)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_nodes() {
+        let request: String = MetricsRequest::system_info().into();
+        assert_eq!(request, r#"{"metric_type":"system_info"}"#);
+
+        let metrics = Metrics {
+            response: r#"{"nodes_info": [
+                {"identifier": 1, "infos": {
+                    "name": "proxy1",
+                    "type": "proxy",
+                    "hardware_infos": {"cpu_core_count": 8, "cpu_core_usage": 12.5, "memory": 1024, "memory_usage": 512, "disk": 2048.0, "disk_usage": 100.0},
+                    "quota_metrics": {"Rms": [{"Label": "Search", "Rate": 3.5}, {"Label": "Insert", "Rate": 0}]}
+                }},
+                {"identifier": 2, "infos": {"name": "querynode2", "type": "querynode"}}
+            ]}"#
+            .to_string(),
+            component_name: "proxy".to_string(),
+        };
+
+        let nodes = metrics.nodes().unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].name, "proxy1");
+        assert_eq!(nodes[0].node_type, "proxy");
+        assert_eq!(nodes[0].cpu_core_count, 8);
+        assert_eq!(nodes[0].memory_usage, 512);
+        assert_eq!(nodes[0].disk, 2048.0);
+        assert_eq!(nodes[0].rates.get("Search"), Some(&3.5));
+        assert_eq!(nodes[0].rates.get("Insert"), Some(&0.0));
+        // missing fields are left to their default
+        assert_eq!(nodes[1].memory, 0);
+        assert!(nodes[1].rates.is_empty());
+
+        let metrics = Metrics {
+            response: "not json".to_string(),
+            component_name: String::new(),
+        };
+        assert!(matches!(metrics.parse(), Err(Error::SerdeJsonErr(_))));
+    }
+
+    fn int_field_data(data_type: DataType, values: Vec<i32>) -> FieldData {
+        FieldData {
+            data_type: data_type as i32,
+            field_name: "age".to_string(),
+            field_id: 0,
+            valid_data: vec![],
+            field: Some(Field::Scalars(ScalarField {
+                data: Some(ScalarFieldData::IntData(values)),
+            })),
+        }
+    }
+
+    #[test]
+    fn test_field_data_check_int_range() {
+        assert!(int_field_data(DataType::Int8, vec![-128, 0, 127])
+            .check_int_range()
+            .is_ok());
+        assert!(int_field_data(DataType::Int16, vec![-32768, 300, 32767])
+            .check_int_range()
+            .is_ok());
+        // Int32 is not narrowed
+        assert!(int_field_data(DataType::Int32, vec![i32::MAX])
+            .check_int_range()
+            .is_ok());
+
+        let result = int_field_data(DataType::Int8, vec![1, 300]).check_int_range();
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::ValueOutOfRange(
+                _,
+                300,
+                DataType::Int8
+            )))
+        ));
+
+        let result = int_field_data(DataType::Int16, vec![-40000]).check_int_range();
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::ValueOutOfRange(
+                _,
+                -40000,
+                DataType::Int16
+            )))
+        ));
+    }
+
+    #[test]
+    fn test_field_data_slice_rows() {
+        let scalars = FieldData {
+            data_type: DataType::Int64 as i32,
+            field_name: "id".to_string(),
+            field_id: 0,
+            valid_data: vec![],
+            field: Some(Field::Scalars(ScalarField {
+                data: Some(ScalarFieldData::LongData(vec![1, 2, 3, 4, 5])),
+            })),
+        };
+        let chunk = scalars.slice_rows(1..3);
+        assert_eq!(chunk.field_name, "id");
+        match chunk.field {
+            Some(Field::Scalars(field)) => {
+                assert_eq!(field.data, Some(ScalarFieldData::LongData(vec![2, 3])))
+            }
+            _ => panic!("expected a scalar field"),
+        }
+
+        let vectors = VectorField {
+            dim: 2,
+            data: Some(VectorFieldData::FloatVec(vec![
+                0.0, 0.1, 1.0, 1.1, 2.0, 2.1, 3.0, 3.1, 4.0, 4.1,
+            ])),
+        };
+        assert_eq!(vectors.num_rows(), 5);
+        let chunk = vectors.slice_rows(3..5);
+        assert_eq!(chunk.dim, 2);
+        assert_eq!(
+            chunk.data,
+            Some(VectorFieldData::FloatVec(vec![3.0, 3.1, 4.0, 4.1]))
+        );
+
+        // 16-dimension binary vectors take 2 bytes a row
+        let binary = VectorField {
+            dim: 16,
+            data: Some(VectorFieldData::BinaryVec(vec![0, 1, 2, 3, 4, 5])),
+        };
+        assert_eq!(binary.num_rows(), 3);
+        assert_eq!(
+            binary.slice_rows(1..2).data,
+            Some(VectorFieldData::BinaryVec(vec![2, 3]))
+        );
+    }
+
+    #[test]
+    fn test_sparse_float_vector_field() {
+        let rows = vec![
+            SparseFloatVector::new(vec![(7, 0.5), (2, 1.5)]),
+            SparseFloatVector::default(),
+            SparseFloatVector::new(vec![(30_000, 0.25)]),
+        ];
+        assert_eq!(rows[0].indices, vec![2, 7]);
+        assert_eq!(rows[0].values, vec![1.5, 0.5]);
+        assert_eq!(rows[0].dim(), 8);
+        assert_eq!(rows[1].dim(), 0);
+        assert_eq!(SparseFloatVector::from_bytes(&rows[0].to_bytes()), rows[0]);
+
+        // one element per row, whatever the dimension
+        let field = VectorField {
+            dim: 0,
+            data: Some(VectorFieldData::SparseFloatVec(rows.clone())),
+        };
+        assert_eq!(field.num_rows(), 3);
+        assert_eq!(
+            field.get(2),
+            Some(Value::SparseFloatVector(rows[2].clone()))
+        );
+        assert_eq!(
+            field.slice_rows(1..3).data,
+            Some(VectorFieldData::SparseFloatVec(rows[1..].to_vec()))
+        );
+
+        let data: milvus::proto::schema::vector_field::Data =
+            VectorFieldData::SparseFloatVec(rows.clone()).into();
+        match &data {
+            milvus::proto::schema::vector_field::Data::SparseFloatVector(array) => {
+                assert_eq!(array.dim, 30_001);
+                assert_eq!(array.contents.len(), 3);
+                assert_eq!(array.contents[0].len(), 16);
+                assert!(array.contents[1].is_empty());
+            }
+            _ => panic!("expected a sparse float vector"),
+        }
+        assert_eq!(
+            VectorFieldData::from(data),
+            VectorFieldData::SparseFloatVec(rows)
+        );
+    }
+
+    #[test]
+    fn test_field_data_try_new() {
+        let vectors = Field::Vectors(VectorField {
+            dim: 2,
+            data: Some(VectorFieldData::FloatVec(vec![0.1, 0.2])),
+        });
+        let field_data = FieldData::try_new("embedding", DataType::FloatVector, vectors).unwrap();
+        assert_eq!(field_data.data_type, DataType::FloatVector as i32);
+        assert_eq!(field_data.num_rows(), 1);
+
+        let scalars = |data| Field::Scalars(ScalarField { data: Some(data) });
+        assert!(FieldData::try_new(
+            "age",
+            DataType::Int16,
+            scalars(ScalarFieldData::IntData(vec![1, 2]))
+        )
+        .is_ok());
+        assert!(FieldData::try_new(
+            "title",
+            DataType::String,
+            scalars(ScalarFieldData::StringData(vec!["Dune".to_string()]))
+        )
+        .is_ok());
+
+        let result = FieldData::try_new(
+            "embedding",
+            DataType::FloatVector,
+            scalars(ScalarFieldData::FloatData(vec![0.1, 0.2])),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::FieldWrongType(
+                _,
+                DataType::FloatVector,
+                DataType::Float
+            )))
+        ));
+
+        let result = FieldData::try_new(
+            "age",
+            DataType::Int8,
+            scalars(ScalarFieldData::IntData(vec![1, 300])),
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::ValueOutOfRange(..)))
+        ));
+    }
+
+    #[test]
+    fn test_field_data_valid_data() {
+        // inserted data only holds the values of the valid rows
+        let field_data = FieldData {
+            data_type: DataType::Int64 as i32,
+            field_name: "year".to_string(),
+            field_id: 0,
+            valid_data: vec![true, false, true, true],
+            field: Some(Field::Scalars(ScalarField {
+                data: Some(ScalarFieldData::LongData(vec![1965, 1989, 1815])),
+            })),
+        };
+        assert_eq!(field_data.num_rows(), 4);
+        assert_eq!(field_data.value(1), None);
+        assert_eq!(field_data.value(2), Some(Value::Long(1989)));
+        assert_eq!(field_data.value(4), None);
+        let chunk = field_data.slice_rows(1..3);
+        assert_eq!(chunk.valid_data, vec![false, true]);
+        assert_eq!(chunk.value(1), Some(Value::Long(1989)));
+
+        // returned data holds a placeholder for the null rows
+        let field_data = FieldData {
+            valid_data: vec![true, false, true],
+            field: Some(Field::Scalars(ScalarField {
+                data: Some(ScalarFieldData::LongData(vec![1965, 0, 1815])),
+            })),
+            ..field_data
+        };
+        assert_eq!(field_data.value(1), None);
+        assert_eq!(field_data.value(2), Some(Value::Long(1815)));
+        assert_eq!(
+            field_data.slice_rows(2..3).value(0),
+            Some(Value::Long(1815))
+        );
+    }
+
+    #[test]
+    fn test_small_int_data() {
+        let data = ScalarFieldData::from(vec![-128i8, 0, 127]);
+        assert_eq!(data.data_type(), DataType::Int8);
+        assert_eq!(data.get(0), Some(Value::Int(-128)));
+        assert_eq!(
+            data.slice_rows(1..3),
+            ScalarFieldData::Int8Data(vec![0, 127])
+        );
+
+        let field = Field::Scalars(ScalarField { data: Some(data) });
+        let field_data = FieldData::try_new("rating", DataType::Int8, field).unwrap();
+        let field_data: milvus::proto::schema::FieldData = field_data.into();
+        assert_eq!(field_data.r#type, DataType::Int8 as i32);
+        match field_data.field {
+            Some(milvus::proto::schema::field_data::Field::Scalars(field)) => assert_eq!(
+                field.data,
+                Some(milvus::proto::schema::scalar_field::Data::IntData(
+                    milvus::proto::schema::IntArray {
+                        data: vec![-128, 0, 127]
+                    }
+                ))
+            ),
+            _ => panic!("expected scalars"),
+        }
+
+        let field = Field::Scalars(ScalarField {
+            data: Some(ScalarFieldData::from(vec![-32768i16, 32767])),
+        });
+        assert_eq!(field.data_type(), DataType::Int16);
+        assert!(FieldData::try_new("year", DataType::Int16, field.clone()).is_ok());
+        assert!(FieldData::try_new("year", DataType::Int8, field).is_err());
+    }
+
+    #[test]
+    fn test_bytes_data_type() {
+        // bytes are scalars, not bit-packed binary vectors
+        let data = ScalarFieldData::BytesData(vec![vec![0u8, 1u8]]);
+        assert_eq!(data.data_type(), DataType::None);
+        assert_eq!(Value::Bytes(vec![0u8, 1u8]).data_type(), DataType::None);
+
+        let field = Field::Scalars(ScalarField { data: Some(data) });
+        assert_ne!(field.data_type(), DataType::BinaryVector);
+        assert!(FieldData::try_new("payload", DataType::BinaryVector, field).is_err());
+    }
+
+    #[test]
+    fn test_field_data_as_float_vectors() {
+        let mut field_data = FieldData {
+            data_type: DataType::FloatVector as i32,
+            field_name: "embedding".to_string(),
+            field_id: 0,
+            valid_data: vec![],
+            field: Some(Field::Vectors(VectorField {
+                dim: 2,
+                data: Some(VectorFieldData::FloatVec(vec![
+                    0.1, 0.2, 0.3, 0.4, 0.5, 0.6,
+                ])),
+            })),
+        };
+        assert_eq!(
+            field_data.as_float_vectors(),
+            Some(vec![vec![0.1, 0.2], vec![0.3, 0.4], vec![0.5, 0.6]])
+        );
+
+        field_data.field = Some(Field::Vectors(VectorField {
+            dim: 16,
+            data: Some(VectorFieldData::BinaryVec(vec![0, 1])),
+        }));
+        assert_eq!(field_data.as_float_vectors(), None);
+
+        field_data.field = Some(Field::Scalars(ScalarField {
+            data: Some(ScalarFieldData::FloatData(vec![0.1])),
+        }));
+        assert_eq!(field_data.as_float_vectors(), None);
+    }
+
+    #[test]
+    fn test_health_quota_states() {
+        let health = Health::from(milvus::proto::milvus::CheckHealthResponse {
+            is_healthy: false,
+            reasons: vec!["disk quota exhausted".to_string()],
+            quota_states: vec![5, 2, 42],
+            ..Default::default()
+        });
+        assert_eq!(
+            health.quota_states,
+            vec![
+                QuotaState::DenyToWrite,
+                QuotaState::ReadLimited,
+                QuotaState::Unknown
+            ]
+        );
+        assert!(health.is_write_denied());
+        assert!(!health.is_read_denied());
+    }
+
+    #[test]
+    fn test_binary_vector_field() {
+        let field = VectorField::from_binary(16, vec![0, 1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(field.num_rows(), 3);
+        assert_eq!(field.get(1), Some(Value::BinaryVector(vec![2, 3])));
+
+        assert!(matches!(
+            VectorField::from_binary(12, vec![0; 6]),
+            Err(Error::InvalidParameter(ref p, _)) if p == "dim"
+        ));
+        assert!(matches!(
+            VectorField::from_binary(0, vec![]),
+            Err(Error::InvalidParameter(ref p, _)) if p == "dim"
+        ));
+        assert!(matches!(
+            VectorField::from_binary(32, vec![0; 6]),
+            Err(Error::InvalidParameter(ref p, _)) if p == "data"
+        ));
+    }
+
+    #[test]
+    fn test_int8_vector_field() {
+        let field = VectorField::from_int8(2, vec![-128, 0, 1, 2, 127, -1]).unwrap();
+        assert_eq!(field.num_rows(), 3);
+        assert_eq!(field.get(2), Some(Value::Int8Vector(vec![127, -1])));
+        assert_eq!(
+            field.slice_rows(0..1).data,
+            Some(VectorFieldData::Int8Vec(vec![-128, 0]))
+        );
+
+        let data: milvus::proto::schema::vector_field::Data = field.data.clone().unwrap().into();
+        assert_eq!(
+            data,
+            milvus::proto::schema::vector_field::Data::Int8Vector(vec![128, 0, 1, 2, 127, 255])
+        );
+        assert_eq!(VectorFieldData::from(data), field.data.unwrap());
+
+        assert!(matches!(
+            VectorField::from_int8(4, vec![0; 6]),
+            Err(Error::InvalidParameter(ref p, _)) if p == "data"
+        ));
+        assert!(matches!(
+            VectorField::from_int8(0, vec![]),
+            Err(Error::InvalidParameter(ref p, _)) if p == "dim"
+        ));
+    }
+
+    #[test]
+    fn test_index_info_build_state() {
+        let description = milvus::proto::milvus::IndexDescription {
+            index_name: "embedding_index".to_string(),
+            field_name: "embedding".to_string(),
+            state: 4,
+            index_state_fail_reason: "out of memory".to_string(),
+            ..Default::default()
+        };
+        let info = IndexInfo::from(description.clone());
+        assert_eq!(info.state, IndexBuildState::Failed);
+        assert!(info.state.is_failed());
+        assert!(!info.state.is_finished());
+
+        let info = IndexInfo::from(milvus::proto::milvus::IndexDescription {
+            state: 3,
+            ..description.clone()
+        });
+        assert!(info.state.is_finished());
+
+        // unknown to this client
+        let info = IndexInfo::from(milvus::proto::milvus::IndexDescription {
+            state: 42,
+            ..description
+        });
+        assert_eq!(info.state, IndexBuildState::IndexStateNone);
+    }
+
+    #[test]
+    fn test_mutation_result_append() {
+        let chunk = |ids: Vec<i64>, err_index: Vec<u32>, timestamp| MutationResult {
+            id: Some(Id {
+                id_field: Some(IdField::IntId(ids.clone())),
+            }),
+            succ_index: (0..ids.len() as u32)
+                .filter(|i| !err_index.contains(i))
+                .collect(),
+            err_index,
+            acknowledged: false,
+            insert_cnt: ids.len() as i64,
+            delete_cnt: 0,
+            upsert_cnt: 0,
+            timestamp,
+        };
+
+        let mut result = MutationResult::empty();
+        result.append(chunk(vec![1, 2, 3], vec![], 20), 0);
+        result.append(chunk(vec![4, 5], vec![1], 10), 3);
+
+        assert_eq!(result.insert_cnt, 5);
+        assert_eq!(result.succ_index, vec![0, 1, 2, 3]);
+        assert_eq!(result.err_index, vec![4]);
+        assert_eq!(result.timestamp, 20);
+        match result.id.and_then(|id| id.id_field) {
+            Some(IdField::IntId(ids)) => assert_eq!(ids, vec![1, 2, 3, 4, 5]),
+            id => panic!("unexpected ids {:?}", id),
+        }
+    }
+
+    #[test]
+    fn test_mutation_result_unix_timestamp_ms() {
+        let result = MutationResult {
+            id: None,
+            succ_index: vec![0],
+            err_index: vec![],
+            acknowledged: false,
+            insert_cnt: 1,
+            delete_cnt: 0,
+            upsert_cnt: 0,
+            timestamp: 445_632_372_386_562_049,
+        };
+        // 2023-11-14T09:03:12.417Z
+        assert_eq!(result.unix_timestamp_ms(), 1_699_952_592_417);
+    }
+
+    #[test]
+    fn test_search_result_per_query_pads_to_nq() {
+        let result = SearchResult {
+            results: None,
+            collection_name: "docs".to_string(),
+            nq: 3,
+        };
+        assert_eq!(result.per_query(), vec![QueryHits::default(); 3]);
+
+        // everything filtered out
+        let result = SearchResult {
+            results: Some(SearchResultData {
+                num_queries: 3,
+                top_k: 10,
+                fields_data: vec![],
+                scores: vec![],
+                id: None,
+                topks: vec![],
+            }),
+            collection_name: "docs".to_string(),
+            nq: 3,
+        };
+        assert_eq!(result.per_query(), vec![QueryHits::default(); 3]);
+    }
+
+    fn scored_hits(scores: &[f32]) -> QueryHits {
+        QueryHits {
+            hits: scores
+                .iter()
+                .enumerate()
+                .map(|(i, score)| Hit {
+                    id: Some(Value::Long(i as i64)),
+                    score: *score,
+                    fields: HashMap::new(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_query_hits_filter_by_score_distance() {
+        let hits = scored_hits(&[0.1, 0.2, 0.5, 0.9]);
+
+        let kept = hits.filter_by_score(0.5, MetricType::L2);
+        let scores: Vec<f32> = kept.hits.iter().map(|hit| hit.score).collect();
+        assert_eq!(scores, vec![0.1, 0.2, 0.5]);
+
+        assert!(hits
+            .filter_by_score(0.05, MetricType::Hamming)
+            .hits
+            .is_empty());
+    }
+
+    #[test]
+    fn test_query_hits_filter_by_score_similarity() {
+        let hits = scored_hits(&[0.95, 0.8, 0.4, -0.2]);
+
+        let kept = hits.filter_by_score(0.8, MetricType::Cosine);
+        let scores: Vec<f32> = kept.hits.iter().map(|hit| hit.score).collect();
+        assert_eq!(scores, vec![0.95, 0.8]);
+
+        assert_eq!(hits.filter_by_score(-1.0, MetricType::IP), hits);
+    }
+
+    #[test]
+    fn test_query_hits_group_by() {
+        let result = SearchResult {
+            results: Some(SearchResultData {
+                num_queries: 1,
+                top_k: 5,
+                fields_data: vec![FieldData {
+                    data_type: DataType::VarChar as i32,
+                    field_name: "category".to_string(),
+                    field_id: 0,
+                    valid_data: vec![],
+                    field: Some(Field::Scalars(ScalarField {
+                        data: Some(ScalarFieldData::StringData(
+                            ["news", "sports", "news", "tech", "sports"]
+                                .iter()
+                                .map(|s| s.to_string())
+                                .collect(),
+                        )),
+                    })),
+                }],
+                scores: vec![0.9, 0.8, 0.7, 0.6, 0.5],
+                id: Some(Id {
+                    id_field: Some(IdField::IntId(vec![1, 2, 3, 4, 5])),
+                }),
+                topks: vec![5],
+            }),
+            collection_name: "docs".to_string(),
+            nq: 1,
+        };
+
+        let queries = result.per_query();
+        assert_eq!(queries.len(), 1);
+        let groups = queries[0].group_by("category").unwrap();
+        assert_eq!(groups.len(), 3);
+
+        let ids = |category: &str| -> Vec<Option<Value>> {
+            groups[&Value::from(category)]
+                .iter()
+                .map(|hit| hit.id.clone())
+                .collect()
+        };
+        assert_eq!(
+            ids("news"),
+            vec![Some(Value::Long(1)), Some(Value::Long(3))]
+        );
+        assert_eq!(
+            ids("sports"),
+            vec![Some(Value::Long(2)), Some(Value::Long(5))]
+        );
+        assert_eq!(ids("tech"), vec![Some(Value::Long(4))]);
+        assert_eq!(groups[&Value::from("sports")][1].score, 0.5);
+
+        assert!(matches!(
+            queries[0].group_by("missing"),
+            Err(Error::InvalidParameter(..))
+        ));
+    }
+
+    #[test]
+    fn test_search_result_data_to_records() {
+        let mut data = SearchResultData {
+            num_queries: 2,
+            top_k: 2,
+            fields_data: vec![FieldData {
+                data_type: DataType::Int64 as i32,
+                field_name: "year".to_string(),
+                field_id: 0,
+                valid_data: vec![],
+                field: Some(Field::Scalars(ScalarField {
+                    data: Some(ScalarFieldData::LongData(vec![1965, 1989, 1815])),
+                })),
+            }],
+            scores: vec![0.1, 0.2, 0.3],
+            id: Some(Id {
+                id_field: Some(IdField::IntId(vec![7, 8, 9])),
+            }),
+            // the second query has a single hit
+            topks: vec![2, 1],
+        };
+
+        let records = data.to_records();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].len(), 2);
+        assert_eq!(
+            records[1],
+            vec![Hit {
+                id: Some(Value::Long(9)),
+                score: 0.3,
+                fields: HashMap::from([("year".to_string(), Value::Long(1815))]),
+            }]
+        );
+
+        data.id = Some(Id {
+            id_field: Some(IdField::StrId(
+                ["dune", "hyperion", "emma"]
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+            )),
+        });
+        let records = data.to_records();
+        assert_eq!(records[0][1].id, Some(Value::from("hyperion")));
+        assert_eq!(records[0][1].fields["year"], Value::Long(1989));
+        assert_eq!(records[1][0].id, Some(Value::from("emma")));
+    }
+
+    #[test]
+    fn test_id_field_iter() {
+        let ids = IdField::IntId(vec![7, 8]);
+        assert_eq!(
+            ids.iter().collect::<Vec<_>>(),
+            vec![IdValue::Int(7), IdValue::Int(8)]
+        );
+        assert_eq!(ids.get(2), None);
+
+        let ids = IdField::StrId(vec!["dune".to_string()]);
+        assert_eq!(ids.len(), 1);
+        let id = ids.iter().next().unwrap();
+        assert_eq!(id, IdValue::Str("dune".to_string()));
+        assert_eq!(Value::from(id), Value::from("dune"));
+        assert!(IdField::IntId(vec![]).iter().next().is_none());
+    }
+
+    #[test]
+    fn test_vector_field_from_rows() {
+        let field = VectorField::from_rows(vec![vec![0.5, 1.0], vec![3.0, 0.25]]).unwrap();
+        assert_eq!(field.dim, 2);
+        assert_eq!(field.num_rows(), 2);
+        assert_eq!(
+            field.data,
+            Some(VectorFieldData::from([0.5, 1.0, 3.0, 0.25]))
+        );
+
+        let result = VectorField::from_rows(vec![vec![0.5, 1.0], vec![3.0]]);
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "rows[1]"));
+        let result = VectorField::from_rows(vec![]);
+        assert!(matches!(result, Err(Error::InvalidParameter(ref p, _)) if p == "rows"));
+
+        let embedding: &[f32] = &[0.1, 0.2];
+        assert_eq!(
+            VectorFieldData::from(embedding),
+            VectorFieldData::FloatVec(vec![0.1, 0.2])
+        );
+        let data: VectorFieldData = embedding.iter().map(|v| v * 2.0).collect();
+        assert_eq!(data, VectorFieldData::FloatVec(vec![0.2, 0.4]));
+    }
+
+    #[test]
+    fn test_vector_field_from_f64_rows() {
+        let field =
+            VectorField::from_f64_rows(vec![vec![0.5, 1.0, -2.0], vec![3.0, 0.25, 1e-3]]).unwrap();
+        assert_eq!(field.dim, 3);
+        assert_eq!(
+            field.data,
+            Some(VectorFieldData::FloatVec(vec![
+                0.5, 1.0, -2.0, 3.0, 0.25, 1e-3
+            ]))
+        );
+
+        // mismatched dimension
+        let result = VectorField::from_f64_rows(vec![vec![0.5, 1.0], vec![3.0]]);
+        assert!(matches!(result, Err(Error::InvalidParameter(..))));
+    }
+
+    #[test]
+    fn test_vector_field_from_f64_rows_non_finite() {
+        let result = VectorField::from_f64_rows(vec![vec![0.5, 1.0], vec![f64::NAN, 1.0]]);
+        assert!(matches!(result, Err(Error::InvalidParameter(..))));
+
+        // finite in f64 but overflows f32
+        let result = VectorField::from_f64_rows(vec![vec![f64::MAX, 1.0]]);
+        assert!(matches!(result, Err(Error::InvalidParameter(..))));
+    }
 }
@@ -8,45 +8,203 @@ use tonic::Status as GrpcError;
 
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("{0:?}")]
+    #[error("connection error: {0}")]
     Communication(#[from] CommError),
 
     // #[error("{0:?}")]
     // Collection(#[from] CollectionError),
-    #[error("{0:?}")]
+    #[error("grpc error [{:?}]: {}", .0.code(), .0.message())]
     Grpc(#[from] GrpcError),
 
-    #[error("{0:?}")]
+    #[error("schema error: {0}")]
     Schema(#[from] SchemaError),
 
-    #[error("{0:?} {1:?}")]
-    Server(ErrorCode, String),
+    /// An error reported by the server: the legacy error code and reason, then the numeric code and detail set by newer servers.
+    #[error("milvus server error [{}]: {}", error_code_name(.0, .2), server_message(.1, .3))]
+    Server(ErrorCode, String, i32, String),
 
-    #[error("{0:?}")]
+    #[error("failed to encode a message: {0}")]
     ProstEncode(#[from] prost::EncodeError),
 
-    #[error("{0:?}")]
+    #[error("failed to decode a message: {0}")]
     ProstDecode(#[from] prost::DecodeError),
 
     #[error("Conversion error")]
     Conversion,
 
-    #[error("{0:?}")]
+    #[error("json error: {0}")]
     SerdeJsonErr(#[from] serde_json::Error),
 
     #[error("parameter {0:?} with invalid value {1:?}")]
     InvalidParameter(String, String),
 
+    #[error("{0} timed out after {1:?}")]
+    Timeout(String, std::time::Duration),
+
+    /// The server could not find a collection, partition, index or database, with the reason it gave.
+    #[error("not found: {0}")]
+    NotFound(String),
+
     // #[error("{0:?}")]
     // Other(#[from] anyhow::Error),
     #[error("{0}")]
     Unexpected(String),
 }
 
+/// The numeric code of newer servers rejecting a request over the rate limit.
+const RATE_LIMIT_CODE: i32 = 8;
+
+/// The numeric codes of newer servers for a missing collection, partition, index and database.
+const NOT_FOUND_CODES: [i32; 4] = [100, 200, 700, 800];
+
+impl Error {
+    /// Whether the request may succeed if sent again later, e.g. the server was unavailable or rate limited it.
+    ///
+    /// Schema, parameter and other errors of the request itself are never retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Grpc(status) => matches!(
+                status.code(),
+                tonic::Code::Unavailable
+                    | tonic::Code::DeadlineExceeded
+                    | tonic::Code::ResourceExhausted
+            ),
+            Error::Server(error_code, _, code, _) => {
+                *error_code == ErrorCode::RateLimit || *code == RATE_LIMIT_CODE
+            }
+            _ => false,
+        }
+    }
+}
+
 impl From<Status> for Error {
     fn from(s: Status) -> Self {
-        Error::Server(ErrorCode::from_i32(s.error_code).unwrap(), s.reason)
+        // newer servers may send legacy codes unknown to this client
+        let error_code = ErrorCode::from_i32(s.error_code).unwrap_or(ErrorCode::UnexpectedError);
+        let not_found = matches!(
+            error_code,
+            ErrorCode::CollectionNotExists
+                | ErrorCode::CollectionNameNotFound
+                | ErrorCode::IndexNotExist
+        ) || NOT_FOUND_CODES.contains(&s.code);
+        if not_found {
+            Error::NotFound(s.reason)
+        } else {
+            Error::Server(error_code, s.reason, s.code, s.detail)
+        }
+    }
+}
+
+/// The name of the code of a server error, e.g. `CollectionNotExists`, or the numeric code set by newer servers without a legacy one.
+fn error_code_name(error_code: &ErrorCode, code: &i32) -> String {
+    if *error_code == ErrorCode::Success && *code != 0 {
+        format!("code {}", code)
+    } else {
+        format!("{:?}", error_code)
+    }
+}
+
+/// The reason of a server error, followed by its detail if it adds anything.
+fn server_message(reason: &str, detail: &str) -> String {
+    if detail.is_empty() || reason.contains(detail) {
+        reason.to_string()
+    } else {
+        format!("{} ({})", reason, detail)
     }
 }
 
 pub type Result<T> = result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_display() {
+        let err = Error::Server(
+            ErrorCode::CollectionNotExists,
+            "collection c1 not found".to_string(),
+            0,
+            String::new(),
+        );
+        assert_eq!(
+            err.to_string(),
+            "milvus server error [CollectionNotExists]: collection c1 not found"
+        );
+
+        // a newer server setting only the numeric code
+        let err = Error::Server(
+            ErrorCode::Success,
+            "collection not found[collection=c1]".to_string(),
+            100,
+            "collection not found".to_string(),
+        );
+        assert_eq!(
+            err.to_string(),
+            "milvus server error [code 100]: collection not found[collection=c1]"
+        );
+
+        let err = Error::Server(
+            ErrorCode::UnexpectedError,
+            "insert failed".to_string(),
+            65535,
+            "segment is full".to_string(),
+        );
+        assert_eq!(
+            err.to_string(),
+            "milvus server error [UnexpectedError]: insert failed (segment is full)"
+        );
+
+        let err = Error::from(GrpcError::unavailable("connection refused"));
+        assert_eq!(
+            err.to_string(),
+            "grpc error [Unavailable]: connection refused"
+        );
+
+        let err = Error::from(SchemaError::NoPrimaryKey);
+        assert_eq!(
+            err.to_string(),
+            "schema error: can not find any primary key"
+        );
+    }
+
+    #[test]
+    fn test_not_found() {
+        let status = |error_code: ErrorCode, code| Status {
+            error_code: error_code as i32,
+            reason: "missing".to_string(),
+            code,
+            ..Default::default()
+        };
+
+        for status in [
+            status(ErrorCode::CollectionNotExists, 0),
+            status(ErrorCode::IndexNotExist, 0),
+            // partition not found, only set by newer servers
+            status(ErrorCode::UnexpectedError, 200),
+        ] {
+            let err = Error::from(status);
+            assert!(matches!(err, Error::NotFound(ref reason) if reason == "missing"));
+        }
+
+        let err = Error::from(status(ErrorCode::IllegalArgument, 0));
+        assert!(matches!(err, Error::Server(ErrorCode::IllegalArgument, ..)));
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(Error::from(GrpcError::unavailable("")).is_retryable());
+        assert!(Error::from(GrpcError::deadline_exceeded("")).is_retryable());
+        assert!(Error::from(GrpcError::resource_exhausted("")).is_retryable());
+        assert!(!Error::from(GrpcError::invalid_argument("")).is_retryable());
+
+        let server =
+            |error_code, code| Error::Server(error_code, String::new(), code, String::new());
+        assert!(server(ErrorCode::RateLimit, 0).is_retryable());
+        assert!(server(ErrorCode::Success, RATE_LIMIT_CODE).is_retryable());
+        assert!(!server(ErrorCode::CollectionNotExists, 0).is_retryable());
+
+        assert!(!Error::from(SchemaError::NoPrimaryKey).is_retryable());
+        assert!(!Error::InvalidParameter("limit".to_owned(), "0".to_owned()).is_retryable());
+    }
+}
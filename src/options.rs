@@ -1,3 +1,9 @@
+use crate::common::{self, MetricType};
+use crate::my_error::{Error, Result};
+use milvus::proto::common::KeyValuePair;
+use std::time::Duration;
+use tonic::transport::Endpoint;
+
 #[derive(Debug)]
 pub struct CreateCollectionOptions {
     shard_num: i32,
@@ -60,3 +66,553 @@ impl From<ConsistencyLevel> for milvus::proto::common::ConsistencyLevel {
         }
     }
 }
+
+/// Options of `Client::search_with`.
+///
+/// ```
+/// use wasmedge_vdb::{common::MetricType, options::SearchOptions};
+///
+/// let options = SearchOptions::new()
+///     .top_k(5)
+///     .metric_type(MetricType::L2)
+///     .nprobe(16)
+///     .expr("year > 2000")
+///     .output_fields(vec!["title"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchOptions {
+    pub(crate) top_k: i64,
+    pub(crate) metric_type: Option<MetricType>,
+    pub(crate) params: serde_json::Map<String, serde_json::Value>,
+    pub(crate) expr: String,
+    pub(crate) output_fields: Vec<String>,
+    pub(crate) consistency_level: Option<common::ConsistencyLevel>,
+    pub(crate) guarantee_timestamp: u64,
+    /// All the partitions if `None`
+    pub(crate) partition_names: Option<Vec<String>>,
+    pub(crate) offset: i64,
+    pub(crate) round_decimal: i32,
+}
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            top_k: 10,
+            metric_type: None,
+            params: serde_json::Map::new(),
+            expr: String::new(),
+            output_fields: vec![],
+            consistency_level: None,
+            guarantee_timestamp: 0,
+            partition_names: None,
+            offset: 0,
+            round_decimal: -1,
+        }
+    }
+}
+impl SearchOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of hits to return per query, 10 by default.
+    pub fn top_k(mut self, top_k: i64) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    /// The number of hits to return per query, same as `top_k`. Combine with `offset` to page through the hits.
+    pub fn limit(self, limit: i64) -> Self {
+        self.top_k(limit)
+    }
+
+    /// The metric of the search, which must match the one of the index. The index one is used if not set.
+    ///
+    /// Float vectors are searched with `L2`, `IP` or `Cosine`, binary vectors with `Hamming` or `Jaccard`, see
+    /// `Client::search_binary_vectors`; a mismatch returns `Error::InvalidParameter`.
+    pub fn metric_type(mut self, metric_type: MetricType) -> Self {
+        self.metric_type = Some(metric_type);
+        self
+    }
+
+    /// The number of clusters to search, for IVF indexes.
+    pub fn nprobe(self, nprobe: i64) -> Self {
+        self.param("nprobe", nprobe)
+    }
+
+    /// The size of the dynamic candidate list, for HNSW indexes.
+    pub fn ef(self, ef: i64) -> Self {
+        self.param("ef", ef)
+    }
+
+    /// Search the vectors within a distance window instead of the `top_k` closest ones, still returning at most `top_k` hits.
+    ///
+    /// For a distance (`L2`, `Hamming`, `Jaccard`) the hits score in `[range_filter, radius)`, `range_filter` being lower; for a
+    /// similarity (`IP`, `Cosine`) they score in `(radius, range_filter]`, `range_filter` being greater.
+    pub fn range(self, radius: f64, range_filter: f64) -> Self {
+        self.param("radius", radius)
+            .param("range_filter", range_filter)
+    }
+
+    /// Set an index-specific search parameter.
+    pub fn param(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.params.insert(key.to_string(), value.into());
+        self
+    }
+
+    /// The boolean expression the hits must match.
+    pub fn expr(mut self, expr: &str) -> Self {
+        self.expr = expr.to_string();
+        self
+    }
+
+    /// The fields to return along with the hits.
+    pub fn output_fields(mut self, output_fields: Vec<&str>) -> Self {
+        self.output_fields = output_fields.into_iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// The consistency level of the search, the one of the collection if not set.
+    pub fn consistency_level(mut self, level: common::ConsistencyLevel) -> Self {
+        self.consistency_level = Some(level);
+        self
+    }
+
+    /// Search the data written up to the hybrid timestamp `ts`, i.e. with the `Customized` consistency level.
+    pub fn guarantee_timestamp(mut self, ts: u64) -> Self {
+        self.consistency_level = Some(common::ConsistencyLevel::Customized);
+        self.guarantee_timestamp = ts;
+        self
+    }
+
+    /// The partitions to search, all of them if not set. An empty list is rejected when searching.
+    pub fn partition_names(mut self, partition_names: Vec<&str>) -> Self {
+        self.partition_names = Some(partition_names.into_iter().map(|s| s.to_string()).collect());
+        self
+    }
+
+    /// The number of hits to skip, for pagination.
+    pub fn offset(mut self, offset: i64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// The number of decimal places of the returned scores, -1 for no rounding.
+    pub fn round_decimal(mut self, round_decimal: i32) -> Self {
+        self.round_decimal = round_decimal;
+        self
+    }
+
+    /// The `search_params` of a search of the vector field `anns_field`.
+    pub(crate) fn search_params(&self, anns_field: &str) -> Vec<KeyValuePair> {
+        let mut params = vec![
+            ("anns_field", anns_field.to_string()),
+            ("topk", self.top_k.to_string()),
+            (
+                "params",
+                serde_json::Value::Object(self.params.clone()).to_string(),
+            ),
+            ("offset", self.offset.to_string()),
+            ("round_decimal", self.round_decimal.to_string()),
+        ];
+        if let Some(metric_type) = self.metric_type {
+            params.push(("metric_type", metric_type.to_string()));
+        }
+
+        params
+            .into_iter()
+            .map(|(key, value)| KeyValuePair {
+                key: key.to_string(),
+                value,
+            })
+            .collect()
+    }
+}
+
+/// Options of `Client::query_with`.
+#[derive(Debug, Clone, Default)]
+pub struct QueryOptions {
+    pub(crate) output_fields: Vec<String>,
+    /// All the partitions if `None`
+    pub(crate) partition_names: Option<Vec<String>>,
+    pub(crate) offset: i64,
+    pub(crate) limit: Option<i64>,
+    pub(crate) consistency_level: Option<common::ConsistencyLevel>,
+    pub(crate) guarantee_timestamp: u64,
+}
+impl QueryOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The fields to return.
+    pub fn output_fields(mut self, output_fields: Vec<&str>) -> Self {
+        self.output_fields = output_fields.into_iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// The partitions to query, all of them if not set. An empty list is rejected when querying.
+    pub fn partition_names(mut self, partition_names: Vec<&str>) -> Self {
+        self.partition_names = Some(partition_names.into_iter().map(|s| s.to_string()).collect());
+        self
+    }
+
+    /// The number of matching entities to skip, for pagination. Requires a `limit`.
+    pub fn offset(mut self, offset: i64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// The maximum number of entities to return, all the matching ones if not set.
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// The consistency level of the query, the one of the collection if not set.
+    pub fn consistency_level(mut self, level: common::ConsistencyLevel) -> Self {
+        self.consistency_level = Some(level);
+        self
+    }
+
+    /// Query the data written up to the hybrid timestamp `ts`, i.e. with the `Customized` consistency level.
+    pub fn guarantee_timestamp(mut self, ts: u64) -> Self {
+        self.consistency_level = Some(common::ConsistencyLevel::Customized);
+        self.guarantee_timestamp = ts;
+        self
+    }
+
+    /// The `query_params` of the query.
+    pub(crate) fn query_params(&self) -> Result<Vec<KeyValuePair>> {
+        let mut params = vec![];
+        match self.limit {
+            Some(limit) => {
+                params.push(("offset", self.offset.to_string()));
+                params.push(("limit", limit.to_string()));
+            }
+            None if self.offset != 0 => {
+                return Err(Error::InvalidParameter(
+                    "offset".to_owned(),
+                    "an offset requires a limit".to_owned(),
+                ))
+            }
+            None => {}
+        }
+
+        Ok(params
+            .into_iter()
+            .map(|(key, value)| KeyValuePair {
+                key: key.to_string(),
+                value,
+            })
+            .collect())
+    }
+}
+
+/// One vector field search of `Client::hybrid_search`.
+#[derive(Debug, Clone)]
+pub struct AnnSearchRequest {
+    pub(crate) anns_field: String,
+    pub(crate) vectors: Vec<Vec<f32>>,
+    pub(crate) options: SearchOptions,
+}
+impl AnnSearchRequest {
+    /// Search the float vector field `anns_field` for the hits closest to `vectors`.
+    pub fn new(anns_field: &str, vectors: Vec<Vec<f32>>) -> Self {
+        Self {
+            anns_field: anns_field.to_string(),
+            vectors,
+            options: SearchOptions::default(),
+        }
+    }
+
+    /// The number of hits of this search passed to the reranking, 10 by default.
+    pub fn limit(mut self, limit: i64) -> Self {
+        self.options = self.options.top_k(limit);
+        self
+    }
+
+    /// The metric of this search, which must match the one of the index of the field. The index one is used if not set.
+    pub fn metric_type(mut self, metric_type: MetricType) -> Self {
+        self.options = self.options.metric_type(metric_type);
+        self
+    }
+
+    /// Set an index-specific search parameter, e.g. `nprobe`.
+    pub fn param(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.options = self.options.param(key, value);
+        self
+    }
+
+    /// The boolean expression the hits of this search must match.
+    pub fn expr(mut self, expr: &str) -> Self {
+        self.options = self.options.expr(expr);
+        self
+    }
+}
+
+/// How `Client::hybrid_search` fuses the hits of its searches into a single ranking.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RerankStrategy {
+    /// Reciprocal rank fusion: a hit scores the sum of `1 / (k + rank)` over the searches, `k` is usually 60.
+    Rrf { k: u32 },
+    /// Weighted sum of the normalized scores, one weight in `[0, 1]` per search, in the order of the searches.
+    Weighted { weights: Vec<f32> },
+}
+impl RerankStrategy {
+    /// The `rank_params` of a hybrid search of `num_requests` searches returning `top_k` hits.
+    pub(crate) fn rank_params(&self, num_requests: usize, top_k: i64) -> Result<Vec<KeyValuePair>> {
+        let (strategy, params) = match self {
+            RerankStrategy::Rrf { k } => ("rrf", serde_json::json!({ "k": k })),
+            RerankStrategy::Weighted { weights } => {
+                if weights.len() != num_requests {
+                    return Err(Error::InvalidParameter(
+                        "weights".to_owned(),
+                        format!(
+                            "expected one weight per search, got {} for {}",
+                            weights.len(),
+                            num_requests
+                        ),
+                    ));
+                }
+                ("weighted", serde_json::json!({ "weights": weights }))
+            }
+        };
+
+        Ok(vec![
+            ("strategy", strategy.to_string()),
+            ("params", params.to_string()),
+            ("limit", top_k.to_string()),
+            ("round_decimal", "-1".to_string()),
+            ("offset", "0".to_string()),
+        ]
+        .into_iter()
+        .map(|(key, value)| KeyValuePair {
+            key: key.to_string(),
+            value,
+        })
+        .collect())
+    }
+}
+
+/// Connection settings of `Client::connect`.
+///
+/// Without keepalive, a connection to a node that went away silently is only noticed when a request times out; set
+/// `http2_keep_alive_interval` on long-lived clients to detect it early.
+#[derive(Debug, Clone)]
+pub struct ConnectOptions {
+    pub(crate) timeout: Duration,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) tcp_keepalive: Option<Duration>,
+    pub(crate) http2_keep_alive_interval: Option<Duration>,
+    pub(crate) keep_alive_timeout: Option<Duration>,
+    pub(crate) keep_alive_while_idle: Option<bool>,
+}
+impl Default for ConnectOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(10),
+            connect_timeout: None,
+            tcp_keepalive: None,
+            http2_keep_alive_interval: None,
+            keep_alive_timeout: None,
+            keep_alive_while_idle: None,
+        }
+    }
+}
+impl ConnectOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The timeout of each request, 10 seconds by default.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The timeout of establishing the connection, only bounded by the operating system if not set.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// The idle time after which TCP keepalive probes are sent, disabled if not set.
+    pub fn tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// The interval of the HTTP/2 pings checking the connection is alive, disabled if not set.
+    pub fn http2_keep_alive_interval(mut self, interval: Duration) -> Self {
+        self.http2_keep_alive_interval = Some(interval);
+        self
+    }
+
+    /// The time to wait for the acknowledgement of a ping before closing the connection.
+    pub fn keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.keep_alive_timeout = Some(timeout);
+        self
+    }
+
+    /// Whether to ping the server even when no request is in flight.
+    pub fn keep_alive_while_idle(mut self, enabled: bool) -> Self {
+        self.keep_alive_while_idle = Some(enabled);
+        self
+    }
+
+    /// Apply the settings to `endpoint`.
+    pub(crate) fn apply(&self, endpoint: Endpoint) -> Endpoint {
+        let mut endpoint = endpoint
+            .timeout(self.timeout)
+            .tcp_keepalive(self.tcp_keepalive);
+        if let Some(timeout) = self.connect_timeout {
+            endpoint = endpoint.connect_timeout(timeout);
+        }
+        if let Some(interval) = self.http2_keep_alive_interval {
+            endpoint = endpoint.http2_keep_alive_interval(interval);
+        }
+        if let Some(timeout) = self.keep_alive_timeout {
+            endpoint = endpoint.keep_alive_timeout(timeout);
+        }
+        if let Some(enabled) = self.keep_alive_while_idle {
+            endpoint = endpoint.keep_alive_while_idle(enabled);
+        }
+
+        endpoint
+    }
+}
+
+/// Options of `Client::load_collection_with`.
+///
+/// ```
+/// use wasmedge_vdb::options::LoadOptions;
+///
+/// let options = LoadOptions::new()
+///     .replica_number(2)
+///     .resource_groups(vec!["rg1", "rg2"])
+///     .load_fields(vec!["id", "embedding"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LoadOptions {
+    pub(crate) replica_number: i32,
+    pub(crate) resource_groups: Vec<String>,
+    pub(crate) load_fields: Vec<String>,
+    pub(crate) skip_load_dynamic_field: bool,
+    pub(crate) refresh: bool,
+}
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            replica_number: 1,
+            resource_groups: vec![],
+            load_fields: vec![],
+            skip_load_dynamic_field: false,
+            refresh: false,
+        }
+    }
+}
+impl LoadOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of replicas to load, 1 by default.
+    pub fn replica_number(mut self, replica_number: i32) -> Self {
+        self.replica_number = replica_number;
+        self
+    }
+
+    /// Load the replicas on these resource groups, the default resource group if empty.
+    pub fn resource_groups(mut self, resource_groups: Vec<&str>) -> Self {
+        self.resource_groups = resource_groups.iter().map(|x| x.to_string()).collect();
+        self
+    }
+
+    /// Only load these fields into memory, all the fields if empty.
+    ///
+    /// The primary key and vector fields searched must be loaded; the fields not loaded cannot be filtered on or output.
+    pub fn load_fields(mut self, load_fields: Vec<&str>) -> Self {
+        self.load_fields = load_fields.iter().map(|x| x.to_string()).collect();
+        self
+    }
+
+    /// Do not load the dynamic field, whose keys then cannot be filtered on or output.
+    pub fn skip_load_dynamic_field(mut self, skip: bool) -> Self {
+        self.skip_load_dynamic_field = skip;
+        self
+    }
+
+    /// Reload an already loaded collection, to bring the segments written since into memory without releasing it.
+    pub fn refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+}
+
+/// Options of `Client::import`.
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    pub(crate) backup: bool,
+    pub(crate) bucket: Option<String>,
+    pub(crate) start_ts: Option<u64>,
+    pub(crate) end_ts: Option<u64>,
+    pub(crate) extra: Vec<(String, String)>,
+}
+impl ImportOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Import the insert logs of a backup instead of JSON or NumPy files, each file being a directory of logs.
+    pub fn backup(mut self, backup: bool) -> Self {
+        self.backup = backup;
+        self
+    }
+
+    /// The bucket holding the files, the one of the server if not set.
+    pub fn bucket(mut self, bucket: &str) -> Self {
+        self.bucket = Some(bucket.to_string());
+        self
+    }
+
+    /// Only import the backed up entities written at or after the hybrid timestamp `ts`.
+    pub fn start_ts(mut self, ts: u64) -> Self {
+        self.start_ts = Some(ts);
+        self
+    }
+
+    /// Only import the backed up entities written at or before the hybrid timestamp `ts`.
+    pub fn end_ts(mut self, ts: u64) -> Self {
+        self.end_ts = Some(ts);
+        self
+    }
+
+    /// Set an option unknown to this client.
+    pub fn option(mut self, key: &str, value: &str) -> Self {
+        self.extra.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// The `options` of the import.
+    pub(crate) fn import_options(&self) -> Vec<KeyValuePair> {
+        let mut options = vec![];
+        if self.backup {
+            options.push(("backup".to_string(), "true".to_string()));
+        }
+        if let Some(bucket) = &self.bucket {
+            options.push(("bucket".to_string(), bucket.clone()));
+        }
+        if let Some(ts) = self.start_ts {
+            options.push(("start_ts".to_string(), ts.to_string()));
+        }
+        if let Some(ts) = self.end_ts {
+            options.push(("end_ts".to_string(), ts.to_string()));
+        }
+        options.extend(self.extra.iter().cloned());
+
+        options
+            .into_iter()
+            .map(|(key, value)| KeyValuePair { key, value })
+            .collect()
+    }
+}
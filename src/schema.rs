@@ -1,13 +1,25 @@
+use crate::my_collection::Value;
 use crate::my_error::{Error, Result};
+use milvus::proto::schema::value_field;
 use std::fmt;
 
 use milvus::proto::schema::DataType;
 
+/// The name of the field holding the fields not declared in the schema, when the dynamic field is enabled.
+pub const DYNAMIC_FIELD_NAME: &str = "$meta";
+
+/// The largest `max_length` of a `VarChar` field accepted by the server.
+pub const MAX_VARCHAR_LENGTH: MaxLength = 65535;
+
+/// The largest dimension of a vector field accepted by the server.
+pub const MAX_DIMENSION: Dimension = 32768;
+
 #[derive(Debug, Clone)]
 pub struct CollectionSchema {
     pub(crate) name: String,
     pub(crate) description: String,
     pub(crate) fields: Vec<FieldSchema>,
+    pub(crate) enable_dynamic_field: bool,
 }
 
 impl CollectionSchema {
@@ -25,15 +37,59 @@ impl CollectionSchema {
             return Err(Error::from(SchemaError::NoPrimaryKey));
         }
 
+        for f in fields.iter() {
+            f.check_params()?;
+        }
+
+        let mut partition_keys = fields.iter().filter(|f| f.is_partition_key);
+        if let Some(first) = partition_keys.next() {
+            if let Some(second) = partition_keys.next() {
+                return Err(Error::from(SchemaError::DuplicatePartitionKey(
+                    second.name.clone(),
+                    first.name.clone(),
+                )));
+            }
+            match first.ty {
+                FieldType::Int64(false, _) | FieldType::VarChar(_, false, _) => {}
+                ref ty => {
+                    return Err(Error::from(SchemaError::UnsupportedPartitionKey(
+                        first.name.clone(),
+                        ty.clone().into(),
+                    )))
+                }
+            }
+        }
+
         // let this = std::mem::replace(self, CollectionSchemaBuilder::new("".into(), ""));
 
         Ok(CollectionSchema {
             fields: fields.into_iter().map(|x| x.into()).collect(),
             name: name.into(),
             description: description.unwrap_or_default().into(),
+            enable_dynamic_field: false,
         })
     }
 
+    /// Whether entities can hold fields not declared in the schema, stored in the `DYNAMIC_FIELD_NAME` field.
+    pub fn is_dynamic_field_enabled(&self) -> bool {
+        self.enable_dynamic_field
+    }
+
+    /// Start building the schema of the collection `name` field by field.
+    pub fn builder(name: &str) -> CollectionSchemaBuilder {
+        CollectionSchemaBuilder::new(name)
+    }
+
+    /// Return the primary key field, if any.
+    pub fn primary_field(&self) -> Option<&FieldSchema> {
+        self.fields.iter().find(|f| f.is_primary())
+    }
+
+    /// Return the field used as partition key, if any.
+    pub fn partition_key_field(&self) -> Option<&FieldSchema> {
+        self.fields.iter().find(|f| f.is_partition_key)
+    }
+
     // pub fn primary_column(&self) -> Option<&FieldSchema> {
     //     self.fields.iter().find(|s| s.is_primary)
     // }
@@ -77,16 +133,113 @@ impl From<CollectionSchema> for milvus::proto::schema::CollectionSchema {
             description: schema.description,
             auto_id: false,
             fields: schema.fields.into_iter().map(Into::into).collect(),
+            enable_dynamic_field: schema.enable_dynamic_field,
+            ..Default::default()
         }
     }
 }
-impl From<milvus::proto::schema::CollectionSchema> for CollectionSchema {
-    fn from(schema: milvus::proto::schema::CollectionSchema) -> Self {
-        CollectionSchema {
+impl TryFrom<milvus::proto::schema::CollectionSchema> for CollectionSchema {
+    type Error = Error;
+
+    /// Fails on a field of a data type this client does not support, see `FieldSchema::try_from`.
+    fn try_from(schema: milvus::proto::schema::CollectionSchema) -> Result<Self> {
+        Ok(CollectionSchema {
             name: schema.name,
             description: schema.description,
-            fields: schema.fields.into_iter().map(Into::into).collect(),
+            // the dynamic field is implied by `enable_dynamic_field`, it is not declared
+            fields: schema
+                .fields
+                .into_iter()
+                .filter(|field| !field.is_dynamic)
+                .map(FieldSchema::try_from)
+                .collect::<Result<_>>()?,
+            enable_dynamic_field: schema.enable_dynamic_field,
+        })
+    }
+}
+
+/// A builder of `CollectionSchema`, e.g. to add fields conditionally.
+#[derive(Debug, Clone, Default)]
+pub struct CollectionSchemaBuilder {
+    name: String,
+    description: String,
+    fields: Vec<FieldSchema>,
+    primary_key: Option<String>,
+    enable_dynamic_field: bool,
+}
+impl CollectionSchemaBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a field, after the ones already added.
+    pub fn add_field(mut self, field: FieldSchema) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Make the `Int64` or `VarChar` field `name` the primary key, instead of flagging it in its `FieldType`.
+    pub fn primary_key(mut self, name: &str) -> Self {
+        self.primary_key = Some(name.to_string());
+        self
+    }
+
+    /// Whether entities can hold fields not declared in the schema, `false` by default.
+    pub fn enable_dynamic_field(mut self, enabled: bool) -> Self {
+        self.enable_dynamic_field = enabled;
+        self
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Build the schema, checking the field names are unique and there is exactly one primary key.
+    pub fn build(mut self) -> Result<CollectionSchema> {
+        for (i, field) in self.fields.iter().enumerate() {
+            if self.fields[..i].iter().any(|f| f.name == field.name) {
+                return Err(Error::InvalidParameter(
+                    "fields".to_owned(),
+                    format!("duplicate field {}", field.name),
+                ));
+            }
         }
+
+        if let Some(name) = &self.primary_key {
+            let field = self
+                .fields
+                .iter_mut()
+                .find(|f| &f.name == name)
+                .ok_or_else(|| SchemaError::FieldDoesNotExists(name.clone()))?;
+            field.ty = match field.ty {
+                FieldType::Int64(_, auto_id) => FieldType::Int64(true, auto_id),
+                FieldType::VarChar(max_length, _, auto_id) => {
+                    FieldType::VarChar(max_length, true, auto_id)
+                }
+                ref ty => {
+                    return Err(Error::from(SchemaError::UnsupportedPrimaryKey(
+                        ty.clone().into(),
+                    )))
+                }
+            };
+        }
+
+        let mut primary_fields = self.fields.iter().filter(|f| f.is_primary());
+        if let (Some(first), Some(second)) = (primary_fields.next(), primary_fields.next()) {
+            return Err(Error::from(SchemaError::DuplicatePrimaryKey(
+                second.name.clone(),
+                first.name.clone(),
+            )));
+        }
+
+        let mut schema = CollectionSchema::new(&self.name, self.fields, Some(&self.description))?;
+        schema.enable_dynamic_field = self.enable_dynamic_field;
+
+        Ok(schema)
     }
 }
 
@@ -95,6 +248,9 @@ pub struct FieldSchema {
     name: String,
     desc: String,
     ty: FieldType,
+    is_partition_key: bool,
+    nullable: bool,
+    default_value: Option<Value>,
 }
 impl FieldSchema {
     pub fn new(name: &str, ty: FieldType, description: Option<&str>) -> Self {
@@ -107,9 +263,61 @@ impl FieldSchema {
             name: name.to_string(),
             desc,
             ty,
+            is_partition_key: false,
+            nullable: false,
+            default_value: None,
         }
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn field_type(&self) -> &FieldType {
+        &self.ty
+    }
+
+    pub fn is_partition_key(&self) -> bool {
+        self.is_partition_key
+    }
+
+    /// Use the field as partition key, routing the entities to partitions by its value.
+    ///
+    /// The field must be a non primary `Int64` or `VarChar` one, and a collection has at most one partition key.
+    pub fn as_partition_key(mut self) -> Self {
+        self.is_partition_key = true;
+        self
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.nullable
+    }
+
+    /// Allow the field to be null, e.g. to omit it from the rows inserted.
+    ///
+    /// Primary keys and partition keys cannot be nullable.
+    pub fn nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
+
+    pub fn get_default_value(&self) -> Option<&Value> {
+        self.default_value.as_ref()
+    }
+
+    /// The value of the field for the rows not providing one, e.g. omitting it from the rows inserted.
+    ///
+    /// The value must have the type of the field, which cannot be a primary key or a vector field.
+    pub fn default_value(mut self, value: Value) -> Self {
+        self.default_value = Some(value);
+        self
+    }
+
+    /// Whether the rows inserted can omit the field.
+    pub(crate) fn is_optional(&self) -> bool {
+        self.nullable || self.default_value.is_some()
+    }
+
     pub fn is_primary(&self) -> bool {
         match &self.ty {
             FieldType::Int64(pk, _) => *pk,
@@ -117,6 +325,135 @@ impl FieldSchema {
             _ => false,
         }
     }
+
+    /// Check `max_length` and the dimension are within the bounds accepted by the server.
+    pub(crate) fn check_params(&self) -> Result<()> {
+        if let Some(value) = &self.default_value {
+            self.default_value_field(value)?;
+        }
+        if self.nullable && (self.is_primary() || self.is_partition_key) {
+            return Err(Error::from(SchemaError::InvalidParameter(
+                self.name.clone(),
+                "a primary key or a partition key cannot be nullable".to_owned(),
+            )));
+        }
+
+        match self.ty {
+            FieldType::VarChar(max_length, _, _)
+                if max_length <= 0 || max_length > MAX_VARCHAR_LENGTH =>
+            {
+                Err(Error::from(SchemaError::InvalidParameter(
+                    self.name.clone(),
+                    format!(
+                        "max_length must be in [1, {}], got {}",
+                        MAX_VARCHAR_LENGTH, max_length
+                    ),
+                )))
+            }
+            FieldType::BinaryVector(dim)
+            | FieldType::FloatVector(dim)
+            | FieldType::Int8Vector(dim)
+                if dim <= 0 || dim > MAX_DIMENSION =>
+            {
+                Err(Error::from(SchemaError::InvalidParameter(
+                    self.name.clone(),
+                    format!("dim must be in [1, {}], got {}", MAX_DIMENSION, dim),
+                )))
+            }
+            // a row is packed into dim / 8 bytes
+            FieldType::BinaryVector(dim) if dim % 8 != 0 => {
+                Err(Error::from(SchemaError::InvalidParameter(
+                    self.name.clone(),
+                    format!(
+                        "dim of a binary vector must be a multiple of 8, got {}",
+                        dim
+                    ),
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Convert `value` to a default value of the field, checking it has the type of the field.
+    ///
+    /// An `Int` value is accepted for any integer field if it fits in its type, and a `Float` value for a `Double` field.
+    pub(crate) fn default_value_field(
+        &self,
+        value: &Value,
+    ) -> Result<milvus::proto::schema::ValueField> {
+        let data_type: DataType = self.ty.clone().into();
+        let out_of_range = |v: i64| {
+            Error::from(SchemaError::ValueOutOfRange(
+                self.name.clone(),
+                v,
+                data_type,
+            ))
+        };
+
+        let data = match (&self.ty, value) {
+            (FieldType::Bool, Value::Bool(v)) => value_field::Data::BoolData(*v),
+            (FieldType::Int8, Value::Int(v)) => {
+                i8::try_from(*v).map_err(|_| out_of_range(*v as i64))?;
+                value_field::Data::IntData(*v)
+            }
+            (FieldType::Int16, Value::Int(v)) => {
+                i16::try_from(*v).map_err(|_| out_of_range(*v as i64))?;
+                value_field::Data::IntData(*v)
+            }
+            (FieldType::Int32, Value::Int(v)) => value_field::Data::IntData(*v),
+            (FieldType::Int64(false, _), Value::Int(v)) => value_field::Data::LongData(*v as i64),
+            (FieldType::Int64(false, _), Value::Long(v)) => value_field::Data::LongData(*v),
+            (FieldType::Float, Value::Float(v)) => value_field::Data::FloatData(*v),
+            (FieldType::Double, Value::Float(v)) => value_field::Data::DoubleData(*v as f64),
+            (FieldType::Double, Value::Double(v)) => value_field::Data::DoubleData(*v),
+            (FieldType::VarChar(max_length, false, _), Value::String(v)) => {
+                if v.len() > *max_length as usize {
+                    return Err(Error::from(SchemaError::InvalidParameter(
+                        self.name.clone(),
+                        format!("default value longer than max_length {}", max_length),
+                    )));
+                }
+                value_field::Data::StringData(v.clone())
+            }
+            (FieldType::String, Value::String(v)) => value_field::Data::StringData(v.clone()),
+            (
+                FieldType::BinaryVector(_)
+                | FieldType::FloatVector(_)
+                | FieldType::Int8Vector(_)
+                | FieldType::SparseFloatVector,
+                _,
+            ) => {
+                return Err(Error::from(SchemaError::InvalidParameter(
+                    self.name.clone(),
+                    "a vector field cannot have a default value".to_owned(),
+                )))
+            }
+            _ if self.is_primary() => {
+                return Err(Error::from(SchemaError::InvalidParameter(
+                    self.name.clone(),
+                    "a primary key cannot have a default value".to_owned(),
+                )))
+            }
+            _ => {
+                return Err(Error::from(SchemaError::FieldWrongType(
+                    self.name.clone(),
+                    data_type,
+                    value.data_type(),
+                )))
+            }
+        };
+
+        Ok(milvus::proto::schema::ValueField { data: Some(data) })
+    }
+
+    /// Whether the values of the field are generated by the server.
+    pub fn is_auto_id(&self) -> bool {
+        match &self.ty {
+            FieldType::Int64(pk, auto_id) => *pk && *auto_id,
+            FieldType::VarChar(_, pk, auto_id) => *pk && *auto_id,
+            _ => false,
+        }
+    }
 }
 impl fmt::Debug for FieldSchema {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::fmt::Result {
@@ -137,14 +474,26 @@ impl fmt::Debug for FieldSchema {
             }
             FieldType::BinaryVector(dim) => format!("dtype: BinaryVector, dimension: {dim}"),
             FieldType::FloatVector(dim) => format!("dtype: FloatVector, dimension: {dim}"),
+            FieldType::Int8Vector(dim) => format!("dtype: Int8Vector, dimension: {dim}"),
+            FieldType::SparseFloatVector => format!("dtype: SparseFloatVector"),
+            FieldType::Json => format!("dtype: Json"),
         };
 
-        let message = format!(
+        let mut message = format!(
             "name: {name}, description: {desc}, {ty}",
             name = self.name,
             desc = self.desc,
             ty = ty,
         );
+        if self.is_partition_key {
+            message.push_str(", is_partition_key: true");
+        }
+        if self.nullable {
+            message.push_str(", nullable: true");
+        }
+        if let Some(value) = &self.default_value {
+            message.push_str(&format!(", default_value: {:?}", value));
+        }
 
         write!(f, "{}", message)
     }
@@ -236,7 +585,9 @@ impl From<FieldSchema> for milvus::proto::schema::FieldSchema {
                     value: max_length.to_string(),
                 }]
             }
-            FieldType::BinaryVector(dimension) | FieldType::FloatVector(dimension) => {
+            FieldType::BinaryVector(dimension)
+            | FieldType::FloatVector(dimension)
+            | FieldType::Int8Vector(dimension) => {
                 vec![milvus::proto::common::KeyValuePair {
                     key: "dim".to_string(),
                     value: dimension.to_string(),
@@ -245,10 +596,16 @@ impl From<FieldSchema> for milvus::proto::schema::FieldSchema {
             _ => vec![],
         };
 
+        // checked by `CollectionSchema::new`
+        let default_value = field
+            .default_value
+            .as_ref()
+            .and_then(|value| field.default_value_field(value).ok());
         let data_type: milvus::proto::schema::DataType = field.ty.into();
 
         milvus::proto::schema::FieldSchema {
-            field_id: unimplemented!(),
+            // assigned by the server
+            field_id: 0,
             name: field.name,
             is_primary_key,
             description: field.desc,
@@ -257,57 +614,87 @@ impl From<FieldSchema> for milvus::proto::schema::FieldSchema {
             index_params: vec![],
             auto_id,
             state: milvus::proto::schema::FieldState::FieldCreated as _,
+            is_partition_key: field.is_partition_key,
+            nullable: field.nullable,
+            default_value,
+            ..Default::default()
         }
     }
 }
-impl From<milvus::proto::schema::FieldSchema> for FieldSchema {
-    fn from(field: milvus::proto::schema::FieldSchema) -> Self {
-        let data_type = DataType::from_i32(field.data_type).unwrap();
-        let ty = match data_type {
-            milvus::proto::schema::DataType::None => FieldType::None,
-            milvus::proto::schema::DataType::Bool => FieldType::Bool,
-            milvus::proto::schema::DataType::Int8 => FieldType::Int8,
-            milvus::proto::schema::DataType::Int16 => FieldType::Int16,
-            milvus::proto::schema::DataType::Int32 => FieldType::Int32,
-            milvus::proto::schema::DataType::Int64 => {
-                FieldType::Int64(field.is_primary_key, field.auto_id)
+impl TryFrom<milvus::proto::schema::FieldSchema> for FieldSchema {
+    type Error = Error;
+
+    /// Fails on a data type this client does not support, e.g. `Array` or one added by a newer server, and on a missing
+    /// `max_length` or `dim` type parameter.
+    fn try_from(field: milvus::proto::schema::FieldSchema) -> Result<Self> {
+        let type_param = |key: &str| -> Result<i64> {
+            field
+                .type_params
+                .iter()
+                .find(|kv| kv.key == key)
+                .and_then(|kv| kv.value.parse().ok())
+                .ok_or_else(|| {
+                    Error::from(SchemaError::InvalidParameter(
+                        field.name.clone(),
+                        format!("missing or invalid type parameter {}", key),
+                    ))
+                })
+        };
+
+        let ty = match DataType::from_i32(field.data_type) {
+            Some(DataType::None) => FieldType::None,
+            Some(DataType::Bool) => FieldType::Bool,
+            Some(DataType::Int8) => FieldType::Int8,
+            Some(DataType::Int16) => FieldType::Int16,
+            Some(DataType::Int32) => FieldType::Int32,
+            Some(DataType::Int64) => FieldType::Int64(field.is_primary_key, field.auto_id),
+            Some(DataType::Float) => FieldType::Float,
+            Some(DataType::Double) => FieldType::Double,
+            Some(DataType::String) => FieldType::String,
+            Some(DataType::VarChar) => {
+                let max_length = type_param("max_length")?;
+                FieldType::VarChar(
+                    MaxLength::try_from(max_length).map_err(|_| {
+                        SchemaError::InvalidParameter(
+                            field.name.clone(),
+                            format!("invalid max_length {}", max_length),
+                        )
+                    })?,
+                    field.is_primary_key,
+                    field.auto_id,
+                )
+            }
+            Some(DataType::BinaryVector) => FieldType::BinaryVector(type_param("dim")?),
+            Some(DataType::FloatVector) => FieldType::FloatVector(type_param("dim")?),
+            Some(DataType::Int8Vector) => FieldType::Int8Vector(type_param("dim")?),
+            Some(DataType::SparseFloatVector) => FieldType::SparseFloatVector,
+            Some(DataType::Json) => FieldType::Json,
+            _ => {
+                return Err(Error::from(SchemaError::UnsupportedDataType(
+                    field.name,
+                    field.data_type,
+                )))
             }
-            milvus::proto::schema::DataType::Float => FieldType::Float,
-            milvus::proto::schema::DataType::Double => FieldType::Double,
-            milvus::proto::schema::DataType::String => FieldType::String,
-            milvus::proto::schema::DataType::VarChar => FieldType::VarChar(
-                field
-                    .type_params
-                    .iter()
-                    .find(|kv| kv.key == "max_length")
-                    .and_then(|kv| kv.value.parse().ok())
-                    .unwrap(),
-                field.is_primary_key,
-                field.auto_id,
-            ),
-            milvus::proto::schema::DataType::BinaryVector => FieldType::BinaryVector(
-                field
-                    .type_params
-                    .iter()
-                    .find(|kv| kv.key == "dim")
-                    .and_then(|kv| kv.value.parse().ok())
-                    .unwrap(),
-            ),
-            milvus::proto::schema::DataType::FloatVector => FieldType::FloatVector(
-                field
-                    .type_params
-                    .iter()
-                    .find(|kv| kv.key == "dim")
-                    .and_then(|kv| kv.value.parse().ok())
-                    .unwrap(),
-            ),
         };
 
-        Self {
+        Ok(Self {
             name: field.name,
             desc: field.description,
             ty,
-        }
+            is_partition_key: field.is_partition_key,
+            nullable: field.nullable,
+            default_value: field.default_value.and_then(|value| value.data).map(
+                |data| match data {
+                    value_field::Data::BoolData(v) => Value::Bool(v),
+                    value_field::Data::IntData(v) => Value::Int(v),
+                    value_field::Data::LongData(v) => Value::Long(v),
+                    value_field::Data::FloatData(v) => Value::Float(v),
+                    value_field::Data::DoubleData(v) => Value::Double(v),
+                    value_field::Data::StringData(v) => Value::String(v),
+                    value_field::Data::BytesData(v) => Value::Bytes(v),
+                },
+            ),
+        })
     }
 }
 
@@ -327,6 +714,12 @@ pub enum FieldType {
     VarChar(MaxLength, PrimaryKey, AutoId),
     BinaryVector(Dimension),
     FloatVector(Dimension),
+    /// Quantized vector, one `i8` per dimension
+    Int8Vector(Dimension),
+    /// The dimension of a sparse vector is not fixed.
+    SparseFloatVector,
+    /// A JSON document per entity, inserted as a `Value::String` holding the serialized document
+    Json,
 }
 impl From<FieldType> for milvus::proto::schema::DataType {
     fn from(field_type: FieldType) -> Self {
@@ -343,24 +736,39 @@ impl From<FieldType> for milvus::proto::schema::DataType {
             FieldType::VarChar(_, _, _) => milvus::proto::schema::DataType::VarChar,
             FieldType::BinaryVector(_) => milvus::proto::schema::DataType::BinaryVector,
             FieldType::FloatVector(_) => milvus::proto::schema::DataType::FloatVector,
+            FieldType::Int8Vector(_) => milvus::proto::schema::DataType::Int8Vector,
+            FieldType::SparseFloatVector => milvus::proto::schema::DataType::SparseFloatVector,
+            FieldType::Json => milvus::proto::schema::DataType::Json,
         }
     }
 }
-impl From<milvus::proto::schema::DataType> for FieldType {
-    fn from(data_type: milvus::proto::schema::DataType) -> Self {
+impl TryFrom<milvus::proto::schema::DataType> for FieldType {
+    type Error = Error;
+
+    /// The field type of `data_type`, with no primary key, a `max_length` and a dimension of 0.
+    ///
+    /// Fails on a data type this client does not support, e.g. `Array`.
+    fn try_from(data_type: milvus::proto::schema::DataType) -> Result<Self> {
         match data_type {
-            milvus::proto::schema::DataType::None => FieldType::None,
-            milvus::proto::schema::DataType::Bool => FieldType::Bool,
-            milvus::proto::schema::DataType::Int8 => FieldType::Int8,
-            milvus::proto::schema::DataType::Int16 => FieldType::Int16,
-            milvus::proto::schema::DataType::Int32 => FieldType::Int32,
-            milvus::proto::schema::DataType::Int64 => FieldType::Int64(false, false),
-            milvus::proto::schema::DataType::Float => FieldType::Float,
-            milvus::proto::schema::DataType::Double => FieldType::Double,
-            milvus::proto::schema::DataType::String => FieldType::String,
-            milvus::proto::schema::DataType::VarChar => FieldType::VarChar(0, false, false),
-            milvus::proto::schema::DataType::BinaryVector => FieldType::BinaryVector(0),
-            milvus::proto::schema::DataType::FloatVector => FieldType::FloatVector(0),
+            milvus::proto::schema::DataType::None => Ok(FieldType::None),
+            milvus::proto::schema::DataType::Bool => Ok(FieldType::Bool),
+            milvus::proto::schema::DataType::Int8 => Ok(FieldType::Int8),
+            milvus::proto::schema::DataType::Int16 => Ok(FieldType::Int16),
+            milvus::proto::schema::DataType::Int32 => Ok(FieldType::Int32),
+            milvus::proto::schema::DataType::Int64 => Ok(FieldType::Int64(false, false)),
+            milvus::proto::schema::DataType::Float => Ok(FieldType::Float),
+            milvus::proto::schema::DataType::Double => Ok(FieldType::Double),
+            milvus::proto::schema::DataType::String => Ok(FieldType::String),
+            milvus::proto::schema::DataType::VarChar => Ok(FieldType::VarChar(0, false, false)),
+            milvus::proto::schema::DataType::BinaryVector => Ok(FieldType::BinaryVector(0)),
+            milvus::proto::schema::DataType::FloatVector => Ok(FieldType::FloatVector(0)),
+            milvus::proto::schema::DataType::Int8Vector => Ok(FieldType::Int8Vector(0)),
+            milvus::proto::schema::DataType::SparseFloatVector => Ok(FieldType::SparseFloatVector),
+            milvus::proto::schema::DataType::Json => Ok(FieldType::Json),
+            data_type => Err(Error::InvalidParameter(
+                "data_type".to_owned(),
+                format!("{:?} is not supported", data_type),
+            )),
         }
     }
 }
@@ -377,6 +785,14 @@ pub enum SchemaError {
     #[error("can not find any primary key")]
     NoPrimaryKey,
 
+    #[error("try to set partition key {0:?}, but {1:?} is also partition key")]
+    DuplicatePartitionKey(String, String),
+
+    #[error(
+        "partition key {0:?} must be a non primary int64 or varchar field, unsupported type {1:?}"
+    )]
+    UnsupportedPartitionKey(String, DataType),
+
     #[error("primary key must be int64 or varchar, unsupported type {0:?}")]
     UnsupportedPrimaryKey(DataType),
 
@@ -397,4 +813,378 @@ pub enum SchemaError {
 
     #[error("field {0:?} must be a vector field")]
     NotVectorField(String),
+
+    #[error("value {1} of field {0:?} is out of the range of {2:?}")]
+    ValueOutOfRange(String, i64, DataType),
+
+    #[error("invalid parameter of field {0:?}: {1}")]
+    InvalidParameter(String, String),
+
+    #[error("data type {1} of field {0:?} is not supported by this client")]
+    UnsupportedDataType(String, i32),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collection_schema_builder() {
+        let with_tags = true;
+        let mut builder = CollectionSchema::builder("books")
+            .description("books and their embeddings")
+            .add_field(FieldSchema::new(
+                "book_id",
+                FieldType::Int64(false, true),
+                None,
+            ))
+            .add_field(FieldSchema::new(
+                "embedding",
+                FieldType::FloatVector(8),
+                None,
+            ))
+            .primary_key("book_id")
+            .enable_dynamic_field(true);
+        if with_tags {
+            builder = builder.add_field(FieldSchema::new(
+                "tags",
+                FieldType::VarChar(256, false, false),
+                None,
+            ));
+        }
+
+        let schema = builder.build().unwrap();
+        assert_eq!(schema.name, "books");
+        assert_eq!(schema.description, "books and their embeddings");
+        assert_eq!(schema.fields.len(), 3);
+        assert!(schema.enable_dynamic_field);
+        let primary_field = schema.primary_field().unwrap();
+        assert_eq!(primary_field.name(), "book_id");
+        assert!(primary_field.is_auto_id());
+
+        let schema: milvus::proto::schema::CollectionSchema = schema.into();
+        assert!(schema.enable_dynamic_field);
+        assert!(schema.fields[0].is_primary_key);
+    }
+
+    #[test]
+    fn test_partition_key() {
+        let id = || FieldSchema::new("id", FieldType::Int64(true, false), None);
+        let tenant = || FieldSchema::new("tenant_id", FieldType::VarChar(64, false, false), None);
+
+        let schema = CollectionSchema::new(
+            "c",
+            vec![
+                id(),
+                tenant().as_partition_key(),
+                FieldSchema::new("embedding", FieldType::FloatVector(8), None),
+            ],
+            None,
+        )
+        .unwrap();
+        assert_eq!(schema.partition_key_field().unwrap().name(), "tenant_id");
+        let schema: milvus::proto::schema::CollectionSchema = schema.into();
+        assert!(schema.fields[1].is_partition_key);
+        let schema: CollectionSchema = schema.try_into().unwrap();
+        assert!(schema.fields[1].is_partition_key());
+
+        let result = CollectionSchema::new(
+            "c",
+            vec![
+                id(),
+                tenant().as_partition_key(),
+                FieldSchema::new("region", FieldType::Int64(false, false), None).as_partition_key(),
+            ],
+            None,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::DuplicatePartitionKey(..)))
+        ));
+
+        let result = CollectionSchema::new(
+            "c",
+            vec![
+                id(),
+                FieldSchema::new("score", FieldType::Float, None).as_partition_key(),
+            ],
+            None,
+        );
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::UnsupportedPartitionKey(
+                _,
+                DataType::Float
+            )))
+        ));
+
+        // the primary key can not be the partition key
+        let result = CollectionSchema::new("c", vec![id().as_partition_key()], None);
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::UnsupportedPartitionKey(..)))
+        ));
+    }
+
+    #[test]
+    fn test_default_value_field() {
+        let data = |field: FieldSchema, value: Value| {
+            field
+                .default_value_field(&value)
+                .map(|value| value.data.unwrap())
+        };
+
+        let rating = FieldSchema::new("rating", FieldType::Int8, None);
+        assert_eq!(
+            data(rating.clone(), Value::Int(-3)).unwrap(),
+            value_field::Data::IntData(-3)
+        );
+        assert!(matches!(
+            data(rating, Value::Int(300)),
+            Err(Error::Schema(SchemaError::ValueOutOfRange(..)))
+        ));
+        assert_eq!(
+            data(
+                FieldSchema::new("score", FieldType::Double, None),
+                Value::Float(0.5)
+            )
+            .unwrap(),
+            value_field::Data::DoubleData(0.5)
+        );
+
+        let title = FieldSchema::new("title", FieldType::VarChar(4, false, false), None);
+        assert_eq!(
+            data(title.clone(), Value::from("Dune")).unwrap(),
+            value_field::Data::StringData("Dune".to_string())
+        );
+        assert!(matches!(
+            data(title.clone(), Value::from("Hyperion")),
+            Err(Error::Schema(SchemaError::InvalidParameter(..)))
+        ));
+        assert!(matches!(
+            data(title, Value::Long(1)),
+            Err(Error::Schema(SchemaError::FieldWrongType(..)))
+        ));
+
+        let embedding = FieldSchema::new("embedding", FieldType::FloatVector(2), None);
+        assert!(matches!(
+            data(embedding, Value::FloatVector(vec![0.0, 0.0])),
+            Err(Error::Schema(SchemaError::InvalidParameter(..)))
+        ));
+    }
+
+    #[test]
+    fn test_nullable_and_default_value() {
+        let id = || FieldSchema::new("id", FieldType::Int64(true, false), None);
+        let schema = |field| CollectionSchema::new("c", vec![id(), field], None);
+
+        let field = FieldSchema::new("year", FieldType::Int64(false, false), None)
+            .nullable(true)
+            .default_value(Value::Long(2000));
+        assert!(field.is_nullable());
+        assert_eq!(field.get_default_value(), Some(&Value::Long(2000)));
+        assert!(schema(field.clone()).is_ok());
+
+        let proto: milvus::proto::schema::FieldSchema = field.into();
+        assert!(proto.nullable);
+        assert_eq!(
+            proto.default_value,
+            Some(milvus::proto::schema::ValueField {
+                data: Some(value_field::Data::LongData(2000))
+            })
+        );
+        let field = FieldSchema::try_from(proto).unwrap();
+        assert!(field.is_nullable());
+        assert_eq!(field.get_default_value(), Some(&Value::Long(2000)));
+
+        // checked by the schema
+        let field = FieldSchema::new("year", FieldType::Int64(false, false), None)
+            .default_value(Value::from("2000"));
+        assert!(matches!(
+            schema(field),
+            Err(Error::Schema(SchemaError::FieldWrongType(..)))
+        ));
+        let result = CollectionSchema::new("c", vec![id().nullable(true)], None);
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::InvalidParameter(..)))
+        ));
+    }
+
+    #[test]
+    fn test_field_params() {
+        let id = || FieldSchema::new("id", FieldType::Int64(true, false), None);
+        let schema = |field| CollectionSchema::new("c", vec![id(), field], None);
+
+        for ty in [
+            FieldType::VarChar(0, false, false),
+            FieldType::VarChar(-1, false, false),
+            FieldType::VarChar(MAX_VARCHAR_LENGTH + 1, false, false),
+            FieldType::FloatVector(0),
+            FieldType::BinaryVector(-8),
+            FieldType::BinaryVector(12),
+            FieldType::Int8Vector(MAX_DIMENSION + 1),
+        ] {
+            let result = schema(FieldSchema::new("f", ty, None));
+            assert!(matches!(
+                result,
+                Err(Error::Schema(SchemaError::InvalidParameter(..)))
+            ));
+        }
+
+        for ty in [
+            FieldType::VarChar(MAX_VARCHAR_LENGTH, false, false),
+            FieldType::FloatVector(MAX_DIMENSION),
+            FieldType::FloatVector(1),
+            FieldType::BinaryVector(8),
+        ] {
+            assert!(schema(FieldSchema::new("f", ty, None)).is_ok());
+        }
+
+        // the builder checks them too
+        let result = CollectionSchema::builder("c")
+            .add_field(id())
+            .add_field(FieldSchema::new(
+                "embedding",
+                FieldType::FloatVector(0),
+                None,
+            ))
+            .build();
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::InvalidParameter(..)))
+        ));
+    }
+
+    #[test]
+    fn test_collection_schema_builder_errors() {
+        let id = || FieldSchema::new("id", FieldType::Int64(true, false), None);
+        let embedding = || FieldSchema::new("embedding", FieldType::FloatVector(8), None);
+
+        let result = CollectionSchema::builder("c")
+            .add_field(embedding())
+            .build();
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::NoPrimaryKey))
+        ));
+
+        let result = CollectionSchema::builder("c")
+            .add_field(id())
+            .add_field(embedding())
+            .primary_key("missing")
+            .build();
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::FieldDoesNotExists(..)))
+        ));
+
+        let result = CollectionSchema::builder("c")
+            .add_field(id())
+            .add_field(embedding())
+            .primary_key("embedding")
+            .build();
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::UnsupportedPrimaryKey(
+                DataType::FloatVector
+            )))
+        ));
+
+        let result = CollectionSchema::builder("c")
+            .add_field(id())
+            .add_field(FieldSchema::new(
+                "key",
+                FieldType::VarChar(16, false, false),
+                None,
+            ))
+            .primary_key("key")
+            .build();
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::DuplicatePrimaryKey(..)))
+        ));
+
+        let result = CollectionSchema::builder("c")
+            .add_field(id())
+            .add_field(embedding())
+            .add_field(embedding())
+            .build();
+        assert!(matches!(result, Err(Error::InvalidParameter(..))));
+    }
+
+    #[test]
+    fn test_json_field() {
+        let schema = CollectionSchema::builder("books")
+            .add_field(FieldSchema::new("id", FieldType::Int64(true, false), None))
+            .add_field(FieldSchema::new("extra", FieldType::Json, None).nullable(true))
+            .add_field(FieldSchema::new(
+                "embedding",
+                FieldType::FloatVector(8),
+                None,
+            ))
+            .enable_dynamic_field(true)
+            .build()
+            .unwrap();
+
+        let mut proto: milvus::proto::schema::CollectionSchema = schema.into();
+        assert_eq!(proto.fields[1].data_type, DataType::Json as i32);
+        // as described by the server
+        proto.fields.push(milvus::proto::schema::FieldSchema {
+            name: DYNAMIC_FIELD_NAME.to_string(),
+            data_type: DataType::Json as i32,
+            is_dynamic: true,
+            ..Default::default()
+        });
+
+        let schema: CollectionSchema = proto.try_into().unwrap();
+        let names: Vec<_> = schema.fields.iter().map(|f| f.name()).collect();
+        assert_eq!(names, vec!["id", "extra", "embedding"]);
+        assert!(matches!(schema.fields[1].field_type(), FieldType::Json));
+        assert!(schema.fields[1].is_nullable());
+        assert!(schema.is_dynamic_field_enabled());
+    }
+
+    #[test]
+    fn test_field_schema_try_from_proto() {
+        let proto =
+            |data_type: i32, type_params: Vec<(&str, &str)>| milvus::proto::schema::FieldSchema {
+                name: "f".to_string(),
+                data_type,
+                type_params: type_params
+                    .into_iter()
+                    .map(|(key, value)| milvus::proto::common::KeyValuePair {
+                        key: key.to_string(),
+                        value: value.to_string(),
+                    })
+                    .collect(),
+                ..Default::default()
+            };
+
+        let field = FieldSchema::try_from(proto(DataType::FloatVector as i32, vec![("dim", "8")]));
+        assert!(matches!(
+            field.unwrap().field_type(),
+            FieldType::FloatVector(8)
+        ));
+
+        // a data type of a newer server
+        let result = FieldSchema::try_from(proto(999, vec![]));
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::UnsupportedDataType(ref f, 999))) if f == "f"
+        ));
+
+        // a type parameter missing or not a number
+        let result = FieldSchema::try_from(proto(DataType::FloatVector as i32, vec![]));
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::InvalidParameter(..)))
+        ));
+        let result =
+            FieldSchema::try_from(proto(DataType::VarChar as i32, vec![("max_length", "x")]));
+        assert!(matches!(
+            result,
+            Err(Error::Schema(SchemaError::InvalidParameter(..)))
+        ));
+    }
 }
@@ -1,7 +1,13 @@
 use milvus::proto::common::{ErrorCode, MsgBase, MsgType, Status};
+use std::future::Future;
+use std::time::{Duration, Instant};
 
+use crate::common::ConsistencyLevel;
 use crate::my_error::{Error, Result};
 
+/// Default interval between two polls when waiting on a server-side state.
+pub const WAIT_LOAD_DURATION_MS: u64 = 100;
+
 pub fn new_msg(mtype: MsgType) -> MsgBase {
     MsgBase {
         msg_type: mtype as i32,
@@ -9,6 +15,7 @@ pub fn new_msg(mtype: MsgType) -> MsgBase {
         source_id: 0,
         msg_id: 0,
         target_id: 0,
+        ..Default::default()
     }
 }
 
@@ -17,14 +24,251 @@ pub fn status_to_result(status: &Option<Status>) -> Result<()> {
         .clone()
         .ok_or(Error::Unexpected("no status".to_owned()))?;
 
-    match ErrorCode::from_i32(status.error_code) {
-        Some(i) => match i {
-            ErrorCode::Success => Ok(()),
-            _ => Err(Error::from(status)),
-        },
-        None => Err(Error::Unexpected(format!(
-            "unknown error code {}",
-            status.error_code
-        ))),
+    // newer servers report failures through `code`, possibly along with a legacy `error_code`
+    if status.error_code == ErrorCode::Success as i32 && status.code == 0 {
+        Ok(())
+    } else {
+        Err(Error::from(status))
+    }
+}
+
+/// Same as `status_to_result`, but prefix the error message with the name of the operation `op` for diagnostics.
+pub fn status_to_result_ctx(status: &Option<Status>, op: &'static str) -> Result<()> {
+    status_to_result(status).map_err(|err| match err {
+        Error::Server(error_code, reason, code, detail) => {
+            Error::Server(error_code, format!("{}: {}", op, reason), code, detail)
+        }
+        Error::NotFound(reason) => Error::NotFound(format!("{}: {}", op, reason)),
+        Error::Unexpected(msg) => Error::Unexpected(format!("{}: {}", op, msg)),
+        err => err,
+    })
+}
+
+/// The number of low bits of a hybrid timestamp holding the logical counter.
+const LOGICAL_BITS: u32 = 18;
+
+/// Split a hybrid timestamp into its physical part, in milliseconds since the Unix epoch, and its logical counter.
+pub fn parse_hybrid_ts(ts: u64) -> (u64, u64) {
+    (ts >> LOGICAL_BITS, ts & ((1 << LOGICAL_BITS) - 1))
+}
+
+/// Convert a hybrid timestamp to milliseconds since the Unix epoch.
+pub fn hybrid_ts_to_unix_ms(ts: u64) -> u64 {
+    parse_hybrid_ts(ts).0
+}
+
+/// The guarantee timestamp asking the server not to wait for any write.
+pub const GTS_EVENTUALLY: u64 = 1;
+
+/// The guarantee timestamp asking the server to wait for the writes older than its graceful time.
+pub const GTS_BOUNDED: u64 = 2;
+
+/// Return the guarantee timestamp of a search or query with the consistency level `level`.
+///
+/// `ts` is the hybrid timestamp the `Session` and `Customized` levels wait for: the last write of the session or the one chosen
+/// by the caller. A guarantee timestamp of 0 lets the server wait for all the writes so far, as for `Strong`.
+pub fn get_gts(level: ConsistencyLevel, ts: u64) -> u64 {
+    match level {
+        ConsistencyLevel::Strong => 0,
+        ConsistencyLevel::Session | ConsistencyLevel::Customized => ts,
+        ConsistencyLevel::Bounded => GTS_BOUNDED,
+        ConsistencyLevel::Eventually => GTS_EVENTUALLY,
+    }
+}
+
+/// Whether a server of version `server_version`, as returned by `Client::get_version`, e.g. `v2.6.3`, speaks the protocol of
+/// `PROTO_MILVUS_VERSION`: same major version and at least the same minor one.
+///
+/// A version that can not be parsed is not compatible.
+pub fn is_compatible_server_version(server_version: &str) -> bool {
+    fn major_minor(version: &str) -> Option<(u32, u32)> {
+        let mut parts = version.trim().trim_start_matches('v').split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        Some((major, minor))
+    }
+
+    match (
+        major_minor(server_version),
+        major_minor(crate::PROTO_MILVUS_VERSION),
+    ) {
+        (Some((major, minor)), Some((proto_major, proto_minor))) => {
+            major == proto_major && minor >= proto_minor
+        }
+        _ => false,
+    }
+}
+
+/// Quote `s` as a string literal of a boolean expression, escaping backslashes and double quotes.
+pub fn quote_str(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Call `check` every `interval` until it returns `Some`, or give up with `Error::Timeout` once `timeout` has elapsed.
+///
+/// # Arguments
+///
+/// * `op` - name of the operation being waited on, used in the timeout error
+///
+/// * `interval` - time to sleep between two checks
+///
+/// * `timeout` - maximum time to wait
+///
+/// * `check` - returns `Some` once the awaited state is reached
+pub async fn poll_until<T, F, Fut>(
+    op: &str,
+    interval: Duration,
+    timeout: Duration,
+    mut check: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<T>>>,
+{
+    let start = Instant::now();
+    loop {
+        if let Some(res) = check().await? {
+            return Ok(res);
+        }
+
+        if start.elapsed() >= timeout {
+            return Err(Error::Timeout(op.to_string(), timeout));
+        }
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_parse_hybrid_ts() {
+        // 2023-11-14T22:13:20Z, 5th timestamp of the millisecond
+        let ts = (1_700_000_000_000 << 18) | 5;
+        assert_eq!(parse_hybrid_ts(ts), (1_700_000_000_000, 5));
+        assert_eq!(hybrid_ts_to_unix_ms(ts), 1_700_000_000_000);
+    }
+
+    #[test]
+    fn test_get_gts() {
+        assert_eq!(get_gts(ConsistencyLevel::Strong, 42), 0);
+        assert_eq!(get_gts(ConsistencyLevel::Session, 42), 42);
+        assert_eq!(get_gts(ConsistencyLevel::Bounded, 42), GTS_BOUNDED);
+        assert_eq!(get_gts(ConsistencyLevel::Eventually, 42), GTS_EVENTUALLY);
+        assert_eq!(get_gts(ConsistencyLevel::Customized, 42), 42);
+    }
+
+    #[test]
+    fn test_status_to_result_ctx() {
+        let status = Status {
+            error_code: ErrorCode::CollectionNotExists as i32,
+            reason: "collection c1 not found".to_string(),
+            ..Default::default()
+        };
+
+        let err = status_to_result_ctx(&Some(status), "describe_collection").unwrap_err();
+        assert!(matches!(err, Error::NotFound(_)));
+        let msg = err.to_string();
+        assert!(
+            msg.contains("describe_collection: collection c1 not found"),
+            "{}",
+            msg
+        );
+
+        let err = status_to_result_ctx(&None, "flush").unwrap_err();
+        assert_eq!(err.to_string(), "flush: no status");
+
+        let status = Status {
+            error_code: ErrorCode::Success as i32,
+            ..Default::default()
+        };
+        assert!(status_to_result_ctx(&Some(status), "flush").is_ok());
+    }
+
+    #[test]
+    fn test_status_to_result_code_and_detail() {
+        // a newer server failing with only the numeric code set
+        let status = Status {
+            error_code: ErrorCode::Success as i32,
+            reason: "collection not found[collection=c1]".to_string(),
+            code: 100,
+            detail: "collection not found".to_string(),
+            ..Default::default()
+        };
+        let err = status_to_result(&Some(status)).unwrap_err();
+        assert!(matches!(err, Error::NotFound(_)));
+
+        let status = Status {
+            error_code: ErrorCode::Success as i32,
+            reason: "invalid parameter[limit=0]".to_string(),
+            code: 1100,
+            detail: "invalid parameter".to_string(),
+            ..Default::default()
+        };
+        let err = status_to_result(&Some(status)).unwrap_err();
+        assert!(
+            matches!(err, Error::Server(_, _, 1100, ref detail) if detail == "invalid parameter")
+        );
+
+        // an unknown legacy code does not panic
+        let status = Status {
+            error_code: 10_000,
+            reason: "new failure".to_string(),
+            ..Default::default()
+        };
+        let err = status_to_result(&Some(status)).unwrap_err();
+        assert!(matches!(err, Error::Server(ErrorCode::UnexpectedError, ..)));
+    }
+
+    #[test]
+    fn test_is_compatible_server_version() {
+        assert!(is_compatible_server_version(crate::PROTO_MILVUS_VERSION));
+        assert!(is_compatible_server_version("v2.6.3"));
+        assert!(is_compatible_server_version("2.7.0-beta"));
+        assert!(!is_compatible_server_version("v2.3.4"));
+        assert!(!is_compatible_server_version("v3.0.0"));
+        assert!(!is_compatible_server_version("unknown"));
+    }
+
+    #[test]
+    fn test_quote_str() {
+        assert_eq!(quote_str("abc"), r#""abc""#);
+        assert_eq!(quote_str(r#"a"b\c"#), r#""a\"b\\c""#);
+    }
+
+    #[tokio::test]
+    async fn test_poll_until_not_flushed_then_flushed() {
+        // the first two polls report "not flushed", the third one "flushed"
+        let calls = AtomicUsize::new(0);
+        let result = poll_until(
+            "flush",
+            Duration::from_millis(1),
+            Duration::from_secs(10),
+            || {
+                let n = calls.fetch_add(1, Ordering::SeqCst);
+                async move { Ok((n >= 2).then_some(n)) }
+            },
+        )
+        .await;
+
+        assert_eq!(result.unwrap(), 2);
+        // no more polls once flushed
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_poll_until_timeout() {
+        let result: Result<()> = poll_until(
+            "flush",
+            Duration::from_millis(1),
+            Duration::from_millis(5),
+            || async { Ok(None) },
+        )
+        .await;
+
+        assert!(matches!(result, Err(Error::Timeout(op, _)) if op == "flush"));
     }
 }